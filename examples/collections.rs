@@ -1,13 +1,10 @@
 /// Complete demonstration of CollectionStore with generic components
 /// Shows Vec<T>, HashMap<K, V>, BTreeMap<K, V>, and CircularBuffer<T> using reusable components
 use dioxus::prelude::*;
+use dioxus_collection_store::components::{ItemList, Selector};
 use dioxus_collection_store::{SequentialCollection, use_collection};
 use std::collections::{BTreeMap, HashMap};
 
-// Import shared UI components
-mod components;
-use components::{ItemList, Selector};
-
 // Import custom collection implementation
 #[path = "lib/custom_collection.rs"]
 mod custom_collection;
@@ -56,6 +53,8 @@ fn App() -> Element {
     });
 
     rsx! {
+        // The built-in components only set classes
+        style { "ul {{ list-style: none; padding: 0; }} li {{ display: flex; gap: 10px; padding: 5px; border: 1px solid #ccc; }} li.selected {{ border: 2px solid #000; }}" }
         div { style: "padding: 20px; font-family: sans-serif; max-width: 1200px;",
             h1 { "Collection Store Example" }
             p { "Vec, HashMap, BTreeMap, and CircularBuffer with generic components" }
@@ -1,9 +0,0 @@
-#[allow(unused_imports)]
-mod item_editor;
-mod item_list;
-mod selector;
-
-#[allow(unused_imports)]
-pub use item_list::ItemList;
-#[allow(unused_imports)]
-pub use selector::Selector;
@@ -1,4 +1,5 @@
-use dioxus_collection_store::{Collection, SequentialCollection};
+use dioxus_collection_store::{Collection, KeyIter, SequentialCollection};
+use std::cmp::Ordering;
 
 /// Example implementation of the Collection trait for a custom collection type
 ///
@@ -51,12 +52,25 @@ impl<T> CircularBuffer<T> {
         }
         Some((self.start + logical_index) % self.data.len())
     }
+
+    /// Rotate the internal vector so the oldest item comes first
+    ///
+    /// Bulk operations can then work on the vector directly.
+    fn make_contiguous(&mut self) {
+        if self.start > 0 && !self.data.is_empty() {
+            let start = self.start % self.data.len();
+            self.data.rotate_left(start);
+        }
+        self.start = 0;
+    }
 }
 
 impl<T: Clone> Collection for CircularBuffer<T> {
     type Key = usize;
     type Value = T;
 
+    const POSITIONAL_KEYS: bool = true;
+
     fn get(&self, key: &usize) -> Option<&T> {
         self.real_index(*key)
             .and_then(|idx| self.data.as_slice().get(idx))
@@ -104,10 +118,30 @@ impl<T: Clone> Collection for CircularBuffer<T> {
         Some(removed)
     }
 
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&usize, &T) -> bool,
+    {
+        // Filter in place instead of the default one-by-one removals
+        self.make_contiguous();
+        let mut index = 0;
+        self.data.retain(|value| {
+            let kept = keep(&index, value);
+            index += 1;
+            kept
+        });
+        self.len = self.data.len();
+    }
+
     fn keys(&self) -> Vec<Self::Key> {
         (0..self.len).collect()
     }
 
+    fn key_iter(&self) -> Box<dyn KeyIter<Item = Self::Key>> {
+        // Logical indices can be generated without collecting them
+        Box::new(0..self.len)
+    }
+
     fn len(&self) -> usize {
         self.len
     }
@@ -164,4 +198,13 @@ impl<T: Clone> SequentialCollection for CircularBuffer<T> {
             self.data.swap(&idx1, &idx2);
         }
     }
+
+    fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // Sort the underlying vector instead of the default insertion sort
+        self.make_contiguous();
+        self.data.sort_by(compare);
+    }
 }
@@ -0,0 +1,127 @@
+//! Derive macros for `dioxus-collection-store`
+//!
+//! Use them through the `derive` feature of `dioxus-collection-store`, which re-exports them
+//! next to the traits they implement.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Meta, parse_macro_input};
+
+/// Derive `Merge` for a struct with named fields
+///
+/// Generates a `{Struct}Patch` struct, with the same visibility, holding an `Option` of each field.
+/// Merging a patch replaces the fields that are `Some` and keeps the others.
+///
+/// - `#[merge(nested)]` on a field whose type implements `Merge` merges its patch instead of replacing it
+///   (the patch field is an `Option` of the nested patch).
+/// - `#[merge(derive(...))]` on the struct adds derives to the patch, which derives `Clone` and `Default`.
+#[proc_macro_derive(Merge, attributes(merge))]
+pub fn derive_merge(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_merge(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_merge(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Merge can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "Merge can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut patch_derives = Vec::new();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("merge"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("derive") {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let paths = content.parse_terminated(syn::Path::parse_mod_style, syn::Token![,])?;
+                patch_derives.extend(paths);
+                Ok(())
+            } else {
+                Err(meta.error("expected `derive(...)`"))
+            }
+        })?;
+    }
+
+    let mut patch_fields = Vec::new();
+    let mut merges = Vec::new();
+    for field in &fields.named {
+        let nested = is_nested(field)?;
+        let name = &field.ident;
+        let vis = &field.vis;
+        let ty = &field.ty;
+        if nested {
+            patch_fields.push(quote! { #vis #name: ::core::option::Option<<#ty as ::dioxus_collection_store::Merge>::Patch> });
+            merges.push(quote! {
+                if let ::core::option::Option::Some(value) = patch.#name {
+                    ::dioxus_collection_store::Merge::merge(&mut self.#name, value);
+                }
+            });
+        } else {
+            patch_fields.push(quote! { #vis #name: ::core::option::Option<#ty> });
+            merges.push(quote! {
+                if let ::core::option::Option::Some(value) = patch.#name {
+                    self.#name = value;
+                }
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let patch = format_ident!("{}Patch", ident);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let doc = format!("Fields to update in a `{ident}`, see `Merge`");
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Default, #(#patch_derives),*)]
+        #vis struct #patch #impl_generics #where_clause {
+            #(#patch_fields,)*
+        }
+
+        impl #impl_generics ::dioxus_collection_store::Merge for #ident #ty_generics #where_clause {
+            type Patch = #patch #ty_generics;
+
+            fn merge(&mut self, patch: Self::Patch) {
+                #(#merges)*
+            }
+        }
+    })
+}
+
+/// Check if a field is marked `#[merge(nested)]`
+fn is_nested(field: &syn::Field) -> syn::Result<bool> {
+    let mut nested = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("merge"))
+    {
+        match &attr.meta {
+            Meta::List(_) => attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("nested") {
+                    nested = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `nested`"))
+                }
+            })?,
+            _ => return Err(syn::Error::new_spanned(attr, "expected `#[merge(nested)]`")),
+        }
+    }
+    Ok(nested)
+}
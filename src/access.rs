@@ -0,0 +1,204 @@
+//! Tracking the order in which items are used
+//!
+//! "Recent files" and "recent searches" lists show the items used last, and often keep only a bounded number
+//! of them. With `store.track_access(true)`, `store.touch(&key)` marks an item as just used, which selecting it
+//! and adding it do too, and `store.recently_used(n)` returns the items used last. `store.set_lru_capacity(Some(n))`
+//! bounds the store: adding an item past `n` evicts the least recently used one.
+//!
+//! The access order is stored in the item metadata, so it follows the items when the collection changes.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus_signals::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Metadata holding the access stamp of an item, higher for the items used last
+#[derive(Default)]
+struct Accessed(u64);
+
+/// Source of the access stamps
+static NEXT_ACCESS: AtomicU64 = AtomicU64::new(1);
+
+/// Access tracking state kept in the store while it is enabled
+///
+/// The access order is keyed metadata, which needs hashable keys: `touch` and `evict` are captured where
+/// the bound is known, so selecting and adding items can record accesses without it.
+pub(crate) struct AccessTracking<C>
+where
+    C: Collection + 'static,
+{
+    capacity: Option<usize>,
+    touch: fn(&CollectionStore<C>, &C::Key),
+    evict: fn(&CollectionStore<C>),
+}
+
+impl<C> Copy for AccessTracking<C> where C: Collection + 'static {}
+
+impl<C> Clone for AccessTracking<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Enable or disable tracking the order in which items are used
+    ///
+    /// Disabling it forgets the access order and the LRU capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let files = CollectionStore::new(vec!["a.txt", "b.txt", "c.txt"]);
+    /// files.track_access(true);
+    /// files.touch(&2);
+    /// files.select(&0).unwrap();
+    /// let recent: Vec<usize> = files.recently_used(5).iter().map(|item| item.key()).collect();
+    /// assert_eq!(recent, vec![0, 2]);
+    /// ```
+    pub fn track_access(&self, enabled: bool) {
+        if enabled == self.is_tracking_access() {
+            return;
+        }
+        if enabled {
+            self.enable_access_tracking(None);
+        } else {
+            self.inner.access_tracking().set(None);
+            let tracked = self.peek_meta_table(|accessed: Option<&HashMap<C::Key, Accessed>>| {
+                accessed.is_some_and(|accessed| !accessed.is_empty())
+            });
+            if tracked {
+                self.write_meta_table(|accessed: &mut HashMap<C::Key, Accessed>| accessed.clear());
+            }
+        }
+    }
+
+    /// Keep at most `capacity` items, evicting the least recently used ones
+    ///
+    /// Enables access tracking. Items never used count as the least recently used, in collection order.
+    /// Extra items are evicted right away, then whenever an item is added through the store.
+    pub fn set_lru_capacity(&self, capacity: Option<usize>) {
+        self.enable_access_tracking(capacity);
+        self.evict_least_recently_used();
+    }
+
+    /// Get up to `n` items, the most recently used first (reactive)
+    ///
+    /// Items never used since access tracking was enabled are left out.
+    pub fn recently_used(&self, n: usize) -> Vec<CollectionItem<C>> {
+        let mut used: Vec<(u64, C::Key)> =
+            self.read_meta_table(|accessed: Option<&HashMap<C::Key, Accessed>>| {
+                accessed
+                    .into_iter()
+                    .flatten()
+                    .map(|(key, Accessed(stamp))| (*stamp, key.clone()))
+                    .collect()
+            });
+        used.sort_by_key(|(stamp, _)| std::cmp::Reverse(*stamp));
+        used.into_iter()
+            .take(n)
+            .map(|(_, key)| CollectionItem::new(*self, key))
+            .collect()
+    }
+
+    /// Track the access order, evicting the items past `capacity`
+    fn enable_access_tracking(&self, capacity: Option<usize>) {
+        self.inner.access_tracking().set(Some(AccessTracking {
+            capacity,
+            touch: Self::stamp_access,
+            evict: Self::evict_least_recently_used,
+        }));
+    }
+
+    /// Mark an item as just used
+    fn stamp_access(&self, key: &C::Key) {
+        let stamp = NEXT_ACCESS.fetch_add(1, Ordering::Relaxed);
+        self.write_meta_table(|accessed| accessed.insert(key.clone(), Accessed(stamp)));
+    }
+
+    /// Remove the least recently used items past the LRU capacity
+    fn evict_least_recently_used(&self) {
+        let Some(capacity) = self
+            .inner
+            .access_tracking()
+            .peek()
+            .and_then(|tracking| tracking.capacity)
+        else {
+            return;
+        };
+        while self.peek_len() > capacity {
+            let Some(evicted) = self.least_recently_used() else {
+                return;
+            };
+            if self.remove_many([evicted]).is_empty() {
+                return;
+            }
+        }
+    }
+
+    /// Find the least recently used item
+    fn least_recently_used(&self) -> Option<C::Key> {
+        let keys = self.inner.items().peek().keys();
+        self.peek_meta_table(|accessed: Option<&HashMap<C::Key, Accessed>>| {
+            keys.into_iter().min_by_key(|key| {
+                accessed
+                    .and_then(|accessed| accessed.get(key))
+                    .map_or(0, |Accessed(stamp)| *stamp)
+            })
+        })
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Check if the access order is tracked
+    pub fn is_tracking_access(&self) -> bool {
+        self.inner.access_tracking().peek().is_some()
+    }
+
+    /// Mark an item as just used
+    ///
+    /// Does nothing unless access tracking is enabled. Selecting or adding an item touches it, reading it
+    /// doesn't: call `touch` when the user opens an item without selecting it.
+    pub fn touch(&self, key: &C::Key) {
+        let Some(tracking) = *self.inner.access_tracking().peek() else {
+            return;
+        };
+        if self.peek_contains_key(key) {
+            (tracking.touch)(self, key);
+        }
+    }
+
+    /// Touch an item that was just added, then evict the items past the LRU capacity
+    pub(crate) fn track_added(&self, key: &C::Key) {
+        let Some(tracking) = *self.inner.access_tracking().peek() else {
+            return;
+        };
+        self.touch(key);
+        (tracking.evict)(self);
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Mark this item as just used, see `CollectionStore::touch`
+    pub fn touch(&self) {
+        self.store.touch(&self.key);
+    }
+}
@@ -0,0 +1,199 @@
+//! Accessible live-region announcements
+//!
+//! A store can describe its changes (additions, removals, selection) as short messages meant for an
+//! `aria-live` region, so dynamic lists are usable with screen readers. The store only produces the
+//! message, rendering the region is up to the app:
+//!
+//! ```rust,ignore
+//! let message = use_live_announcer(store, AnnouncementTemplates::default(), |todo: &Todo| todo.title.clone());
+//! rsx! {
+//!     div { role: "status", aria_live: "polite", class: "visually-hidden", "{message}" }
+//! }
+//! ```
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionOperation, CollectionStore};
+use dioxus_core::use_hook;
+use dioxus_signals::*;
+use std::rc::Rc;
+
+/// Message templates for announcements
+///
+/// `{item}` is replaced by the label of the item(s) concerned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnouncementTemplates {
+    /// Announced when items are added
+    pub added: String,
+    /// Announced when items are removed
+    pub removed: String,
+    /// Announced when an item is selected
+    pub selected: String,
+    /// Announced when the collection is cleared
+    pub cleared: String,
+}
+
+impl Default for AnnouncementTemplates {
+    fn default() -> Self {
+        Self {
+            added: "{item} added".to_string(),
+            removed: "{item} removed".to_string(),
+            selected: "{item} selected".to_string(),
+            cleared: "All items removed".to_string(),
+        }
+    }
+}
+
+/// Names an item in announcements
+type LabelFn<V> = Rc<dyn Fn(&V) -> String>;
+
+/// Announcement state kept in the store while announcements are enabled
+pub struct Announcer<C>
+where
+    C: Collection,
+{
+    templates: AnnouncementTemplates,
+    label: LabelFn<C::Value>,
+    /// Captured when announcements are enabled, so mutation methods don't need a `Clone` bound
+    pub(crate) clone_value: fn(&C::Value) -> C::Value,
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Describe the changes made through this store in `announcement()`
+    ///
+    /// `label` names an item in the messages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{AnnouncementTemplates, CollectionStore};
+    ///
+    /// let store = CollectionStore::new(vec!["milk".to_string()]);
+    /// store.enable_announcements(AnnouncementTemplates::default(), |item: &String| item.clone());
+    /// store.push("eggs".to_string());
+    /// assert_eq!(store.announcement(), "eggs added");
+    /// ```
+    pub fn enable_announcements(
+        &self,
+        templates: AnnouncementTemplates,
+        label: impl Fn(&C::Value) -> String + 'static,
+    ) where
+        C::Value: Clone,
+    {
+        self.inner.announcer().set(Some(Announcer {
+            templates,
+            label: Rc::new(label),
+            clone_value: C::Value::clone,
+        }));
+    }
+
+    /// Stop describing changes, and clear the current announcement
+    pub fn disable_announcements(&self) {
+        self.inner.announcer().set(None);
+        self.inner.announcement().set(String::new());
+    }
+
+    /// Get the latest announcement (reactive)
+    ///
+    /// Empty until a change is announced.
+    pub fn announcement(&self) -> String {
+        self.inner.announcement().read().clone()
+    }
+
+    /// Check if announcements are enabled
+    pub(crate) fn is_announcing(&self) -> bool {
+        self.inner.announcer().peek().is_some()
+    }
+
+    /// Announce an operation about to be applied
+    pub(crate) fn announce(&self, operation: &CollectionOperation<C::Key, C::Value>) {
+        let message = {
+            let announcer = self.inner.announcer();
+            let announcer = announcer.peek();
+            let Some(announcer) = announcer.as_ref() else {
+                return;
+            };
+            let items = self.inner.items();
+            let items = items.peek();
+            let label_of = |key: &C::Key| items.get(key).map(|value| (announcer.label)(value));
+            let fill = |template: &str, labels: Vec<String>| {
+                (!labels.is_empty()).then(|| template.replace("{item}", &labels.join(", ")))
+            };
+            let templates = &announcer.templates;
+
+            match operation {
+                CollectionOperation::Insert { key, value } if items.get(key).is_none() => {
+                    fill(&templates.added, vec![(announcer.label)(value)])
+                }
+                CollectionOperation::Push { value }
+                | CollectionOperation::InsertBefore { value, .. }
+                | CollectionOperation::InsertAfter { value, .. } => {
+                    fill(&templates.added, vec![(announcer.label)(value)])
+                }
+                CollectionOperation::Extend { items: added }
+                | CollectionOperation::AppendEntries { items: added } => fill(
+                    &templates.added,
+                    added
+                        .iter()
+                        .map(|(_, value)| (announcer.label)(value))
+                        .collect(),
+                ),
+                CollectionOperation::Remove { key } | CollectionOperation::SwapRemove { key } => {
+                    fill(&templates.removed, label_of(key).into_iter().collect())
+                }
+                CollectionOperation::RemoveMany { keys } => fill(
+                    &templates.removed,
+                    keys.iter().filter_map(label_of).collect(),
+                ),
+                CollectionOperation::Pop => fill(
+                    &templates.removed,
+                    items
+                        .last_key()
+                        .as_ref()
+                        .and_then(label_of)
+                        .into_iter()
+                        .collect(),
+                ),
+                CollectionOperation::Select { key } => {
+                    fill(&templates.selected, label_of(key).into_iter().collect())
+                }
+                CollectionOperation::Clear if !items.is_empty() => Some(templates.cleared.clone()),
+                _ => None,
+            }
+        };
+
+        if let Some(message) = message {
+            self.inner.announcement().set(message);
+        }
+    }
+}
+
+/// Hook enabling announcements on a store, returning the latest announcement
+///
+/// Render the returned message in an `aria-live` region.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use dioxus_collection_store::{AnnouncementTemplates, use_collection, use_live_announcer};
+///
+/// let store = use_collection(|| vec!["milk".to_string()]);
+/// let message = use_live_announcer(store, AnnouncementTemplates::default(), |item: &String| item.clone());
+/// // rsx! { div { role: "status", aria_live: "polite", "{message}" } }
+/// ```
+pub fn use_live_announcer<C>(
+    store: CollectionStore<C>,
+    templates: AnnouncementTemplates,
+    label: impl Fn(&C::Value) -> String + 'static,
+) -> String
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Clone,
+{
+    use_hook(move || store.enable_announcements(templates, label));
+    store.announcement()
+}
@@ -0,0 +1,134 @@
+//! Checked items, for checkbox lists with a tri-state "check all" header
+//!
+//! Like pins, checks are stored as item metadata, so they follow their item
+//! when the collection changes. They are independent of the selection.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Metadata marking an item as checked
+#[derive(Default)]
+struct Checked;
+
+/// How many items are checked, for a tri-state "check all" checkbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckState {
+    /// No item is checked (or the collection is empty)
+    None,
+    /// Some items are checked: render the checkbox as indeterminate
+    Some,
+    /// Every item is checked
+    All,
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Check an item
+    pub fn check(&self, key: &C::Key) -> CollectionResult<()> {
+        if !self.peek_contains_key(key) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        if !self.meta_of::<Checked>(key).peek_is_set() {
+            self.write_meta_table(|checked| checked.insert(key.clone(), Checked));
+        }
+        Ok(())
+    }
+
+    /// Uncheck an item
+    pub fn uncheck(&self, key: &C::Key) {
+        self.meta_of::<Checked>(key).take();
+    }
+
+    /// Check or uncheck an item, returning whether it is now checked
+    pub fn toggle_checked(&self, key: &C::Key) -> CollectionResult<bool> {
+        if self.is_checked(key) {
+            self.uncheck(key);
+            Ok(false)
+        } else {
+            self.check(key)?;
+            Ok(true)
+        }
+    }
+
+    /// Check if an item is checked (reactive)
+    pub fn is_checked(&self, key: &C::Key) -> bool {
+        self.meta_of::<Checked>(key).is_set()
+    }
+
+    /// Get the keys of the checked items, in collection order (reactive)
+    pub fn checked_keys(&self) -> Vec<C::Key> {
+        let keys = self.inner.items().read().keys();
+        self.read_meta_table(|checked: Option<&HashMap<C::Key, Checked>>| match checked {
+            Some(checked) => keys
+                .into_iter()
+                .filter(|key| checked.contains_key(key))
+                .collect(),
+            None => Vec::new(),
+        })
+    }
+
+    /// Check every item, in a single write
+    pub fn check_all(&self) {
+        let keys = self.inner.items().peek().keys();
+        self.write_meta_table(|checked| {
+            Extend::extend(checked, keys.into_iter().map(|key| (key, Checked)))
+        });
+    }
+
+    /// Uncheck every item, in a single write
+    pub fn uncheck_all(&self) {
+        let any_checked = self.peek_meta_table(|checked: Option<&HashMap<C::Key, Checked>>| {
+            checked.is_some_and(|checked| !checked.is_empty())
+        });
+        if any_checked {
+            self.write_meta_table(|checked: &mut HashMap<C::Key, Checked>| checked.clear());
+        }
+    }
+
+    /// Get how many items are checked, for a "check all" checkbox (reactive)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CheckState, CollectionStore};
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b"]);
+    /// assert_eq!(store.check_state(), CheckState::None);
+    /// store.check(&0).ok();
+    /// assert_eq!(store.check_state(), CheckState::Some);
+    /// store.check_all();
+    /// assert_eq!(store.check_state(), CheckState::All);
+    /// ```
+    pub fn check_state(&self) -> CheckState {
+        let checked = self.checked_keys().len();
+        if checked == 0 {
+            CheckState::None
+        } else if checked == self.inner.items().read().len() {
+            CheckState::All
+        } else {
+            CheckState::Some
+        }
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Check if this item is checked
+    pub fn is_checked(&self) -> bool {
+        self.store.is_checked(&self.key)
+    }
+
+    /// Check or uncheck this item, returning whether it is now checked
+    pub fn toggle_checked(&self) -> CollectionResult<bool> {
+        self.store.toggle_checked(&self.key)
+    }
+}
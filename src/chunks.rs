@@ -0,0 +1,110 @@
+//! Grouped iteration for grid and masonry layouts
+//!
+//! `store.chunks(n)` groups the items in rows of `n`, and `store.columns(n)` deals them round-robin into `n`
+//! columns. `store.iter_pairs()` pairs each item of a sequential store with the next one, for timelines
+//! and diffs comparing a row with its neighbor. All of them subscribe to the collection like `iter()`, and each item in a group is a regular
+//! `CollectionItem`, so cells still read and write their own item.
+
+use crate::{Collection, CollectionItem, CollectionStore, SequentialCollection};
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Iterate over the items in groups of `size`, in collection order
+    ///
+    /// The last group holds the remaining items, and may be shorter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus::prelude::*;
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3, 4, 5]);
+    /// let rows: Vec<Vec<i32>> = store
+    ///     .chunks(2)
+    ///     .map(|row| row.iter().map(|item| *item.read()).collect())
+    ///     .collect();
+    /// assert_eq!(rows, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    /// ```
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = Vec<CollectionItem<C>>> + '_ {
+        assert!(size > 0, "chunk size must be greater than 0");
+        let mut items = self.iter();
+        std::iter::from_fn(move || {
+            let chunk: Vec<_> = items.by_ref().take(size).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        })
+    }
+
+    /// Distribute the items round-robin into `count` columns
+    ///
+    /// Item `i` goes to column `i % count`, so every column is returned, even empty ones,
+    /// and a new item only changes the last column it lands in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus::prelude::*;
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3, 4, 5]);
+    /// let columns: Vec<Vec<i32>> = store
+    ///     .columns(2)
+    ///     .iter()
+    ///     .map(|column| column.iter().map(|item| *item.read()).collect())
+    ///     .collect();
+    /// assert_eq!(columns, vec![vec![1, 3, 5], vec![2, 4]]);
+    /// ```
+    pub fn columns(&self, count: usize) -> Vec<Vec<CollectionItem<C>>> {
+        assert!(count > 0, "column count must be greater than 0");
+        let mut columns: Vec<Vec<_>> = (0..count).map(|_| Vec::new()).collect();
+        for (index, item) in self.iter().enumerate() {
+            columns[index % count].push(item);
+        }
+        columns
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Iterate over each item paired with the next one, `None` for the last item
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus::prelude::*;
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let readings = CollectionStore::new(vec![3, 5, 4]);
+    /// let deltas: Vec<i32> = readings
+    ///     .iter_pairs()
+    ///     .filter_map(|(item, next)| Some(*next?.read() - *item.read()))
+    ///     .collect();
+    /// assert_eq!(deltas, vec![2, -1]);
+    /// ```
+    pub fn iter_pairs(
+        &self,
+    ) -> impl Iterator<Item = (CollectionItem<C>, Option<CollectionItem<C>>)> + '_ {
+        let mut items = self.iter().peekable();
+        std::iter::from_fn(move || {
+            let item = items.next()?;
+            let next = items
+                .peek()
+                .map(|next| CollectionItem::new(*self, next.key()));
+            Some((item, next))
+        })
+    }
+}
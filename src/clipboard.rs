@@ -0,0 +1,89 @@
+//! Clipboard copy and paste of items
+//!
+//! `store.copy_selection_to_clipboard(format)` writes the selected items (the whole range, if any) to the
+//! system clipboard, one line per item, and `store.paste_from_clipboard(parse)` appends an item per parsed line.
+//! Both go through `navigator.clipboard` with `document::eval`, so they work on the web and in desktop webviews,
+//! and must be awaited from an event handler: browsers only grant clipboard access to user gestures.
+//!
+//! ```rust,ignore
+//! button {
+//!     onclick: move |_| async move {
+//!         rows.copy_selection_to_clipboard(|row| row.join("\t")).await.ok();
+//!     },
+//!     "Copy"
+//! }
+//! ```
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionResult, CollectionStore, SequentialCollection};
+use dioxus_document::{EvalError, eval};
+use dioxus_signals::*;
+
+/// Script writing the text it receives to the clipboard
+const WRITE_CLIPBOARD: &str =
+    "await navigator.clipboard.writeText(await dioxus.recv()); return true;";
+
+/// Script returning the text of the clipboard
+const READ_CLIPBOARD: &str = "return await navigator.clipboard.readText();";
+
+fn clipboard_error(error: EvalError) -> CollectionError {
+    CollectionError::Clipboard {
+        reason: error.to_string(),
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Copy the selected items to the clipboard, formatted by `format`, one per line
+    ///
+    /// Returns the number of copied items. The clipboard is left untouched if nothing is selected.
+    pub async fn copy_selection_to_clipboard(
+        &self,
+        format: impl Fn(&C::Value) -> String,
+    ) -> CollectionResult<usize> {
+        let keys = self.selected_keys();
+        let lines: Vec<String> = {
+            let items = self.inner.items();
+            let items = items.peek();
+            keys.iter()
+                .filter_map(|key| items.get(key).map(&format))
+                .collect()
+        };
+        if lines.is_empty() {
+            return Ok(0);
+        }
+        let script = eval(WRITE_CLIPBOARD);
+        script.send(lines.join("\n")).map_err(clipboard_error)?;
+        script.join::<bool>().await.map_err(clipboard_error)?;
+        Ok(lines.len())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "clipboard")))]
+impl<C> CollectionStore<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Clone,
+{
+    /// Append an item for every line of the clipboard that `parse` accepts
+    ///
+    /// Lines rejected by `parse` (`None`) are skipped. Returns the number of added items.
+    pub async fn paste_from_clipboard(
+        &self,
+        parse: impl Fn(&str) -> Option<C::Value>,
+    ) -> CollectionResult<usize> {
+        self.ensure_unlocked()?;
+        let text: String = eval(READ_CLIPBOARD).join().await.map_err(clipboard_error)?;
+        let values: Vec<C::Value> = text.lines().filter_map(parse).collect();
+        let added = values.len();
+        for value in values {
+            self.push(value);
+        }
+        Ok(added)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Collection, CollectionResult, CollectionStore};
+use crate::{Collection, CollectionError, CollectionResult, CollectionStore};
 use dioxus_signals::*;
 use dioxus_stores::Store;
 
@@ -12,6 +12,10 @@ use dioxus_stores::Store;
 /// - Call `.write()` to get a mutable write reference
 /// - Call `.boxed()` to get a `ReadSignal`
 /// - Call `.boxed_mut()` to get a `WriteSignal`
+///
+/// If the store tracks generations (see `CollectionStore::track_generations`), reading or writing an item
+/// whose item was removed or moved since it was created panics: use `try_read_value` and `try_write_value`
+/// to get a `CollectionError::StaleHandle` instead.
 #[derive(Clone, Copy, PartialEq, Store)]
 pub struct CollectionItem<C: 'static>
 where
@@ -19,6 +23,7 @@ where
 {
     pub(crate) store: CollectionStore<C>,
     pub(crate) key: C::Key,
+    pub(crate) generation: Option<u64>,
 }
 
 impl<C> std::fmt::Debug for CollectionItem<C>
@@ -46,6 +51,7 @@ where
     where
         Self::Target: 'static,
     {
+        self.assert_fresh();
         self.store.items().try_read_unchecked().map(|value| {
             UnsyncStorage::map(value, |collection: &C| {
                 collection
@@ -63,6 +69,7 @@ where
     where
         Self::Target: 'static,
     {
+        self.assert_fresh();
         self.store.items().try_peek_unchecked().map(|value| {
             UnsyncStorage::map(value, |collection: &C| {
                 collection
@@ -93,6 +100,9 @@ where
     where
         Self::Target: 'static,
     {
+        self.assert_fresh();
+        #[cfg(feature = "index")]
+        self.store.note_index_write(&self.key);
         self.store.items().try_write_unchecked().map(|value| {
             WriteLock::map(value, |collection: &mut C| {
                 collection
@@ -110,11 +120,93 @@ where
     C: Collection + 'static,
     C::Key: Clone + PartialEq,
 {
+    /// Create a handle to the item under `key`, at the key's current generation
+    pub(crate) fn new(store: CollectionStore<C>, key: C::Key) -> Self {
+        let generation = store.peek_generation(&key);
+        Self {
+            store,
+            key,
+            generation,
+        }
+    }
+
     /// Get the key of this item
     pub fn key(&self) -> C::Key {
         self.key.clone()
     }
 
+    /// Get the position of this item in the iteration order of the collection
+    ///
+    /// For a `Vec` this is the index, for maps it's the ordinal of the key in `iter()` order.
+    /// Returns `None` if the key is no longer in the collection. Like `iter()`, this subscribes
+    /// to the collection: the position is kept up to date when items are added, removed or moved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let store = CollectionStore::new(BTreeMap::from([("b", 2), ("a", 1), ("c", 3)]));
+    /// let item = store.get(&"b");
+    /// assert_eq!(item.position(), Some(1));
+    /// assert!(!item.is_first() && !item.is_last());
+    /// ```
+    pub fn position(&self) -> Option<usize> {
+        self.store.items().read().key_position(&self.key)
+    }
+
+    /// Check if this item is the first one in the iteration order, to disable "move up" for instance
+    pub fn is_first(&self) -> bool {
+        self.position() == Some(0)
+    }
+
+    /// Check if this item is the last one in the iteration order, to hide a trailing separator for instance
+    pub fn is_last(&self) -> bool {
+        self.store.items().read().last_key().as_ref() == Some(&self.key)
+    }
+
+    /// Check if the item was removed or moved since this handle was created
+    ///
+    /// Always `false` if the store doesn't track generations.
+    pub fn is_stale(&self) -> bool {
+        self.store
+            .ensure_generation(&self.key, self.generation)
+            .is_err()
+    }
+
+    /// Read the value, failing if this handle is stale or its key is no longer in the collection
+    ///
+    /// Like `read()`, this subscribes to the collection.
+    pub fn try_read_value(&self) -> CollectionResult<ReadableRef<'_, Self>> {
+        self.ensure_valid()?;
+        self.try_read().map_err(|_| CollectionError::BorrowError)
+    }
+
+    /// Write to the value, failing if this handle is stale or its key is no longer in the collection
+    pub fn try_write_value(&mut self) -> CollectionResult<WritableRef<'_, Self>> {
+        self.ensure_valid()?;
+        self.try_write()
+            .map_err(|_| CollectionError::BorrowMutError)
+    }
+
+    fn ensure_valid(&self) -> CollectionResult<()> {
+        self.store.ensure_generation(&self.key, self.generation)?;
+        if !self.store.peek_contains_key(&self.key) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        Ok(())
+    }
+
+    fn assert_fresh(&self) {
+        if self.is_stale() {
+            panic!(
+                "Attempted to access a stale CollectionItem: its item was removed or moved since the handle was created. \
+                 Use `try_read_value` or `try_write_value` to handle this case."
+            );
+        }
+    }
+
     /// Get the key-value tuple for this item
     ///
     /// This is a convenience method that returns both the key and the cloned value.
@@ -136,7 +228,7 @@ where
 
     /// Set/replace the value of this item
     ///
-    /// Directly updates the value without cloning.
+    /// Directly updates the value without cloning. Nothing is written if this handle is stale.
     ///
     /// # Examples
     ///
@@ -145,26 +237,57 @@ where
     /// item.set(new_value);
     /// ```
     pub fn set(&self, value: C::Value) {
-        self.store.items().write().set(self.key.clone(), value);
+        self.try_set(value).ok();
+    }
+
+    /// Set the value like `set`, failing with `CollectionError::StaleHandle` if this handle is stale,
+    /// or `CollectionError::Locked` while the store is locked
+    pub fn try_set(&self, value: C::Value) -> CollectionResult<()> {
+        self.store.ensure_generation(&self.key, self.generation)?;
+        self.store.try_set(self.key.clone(), value)
     }
 
-    /// Check if this item is currently selected
+    /// Set the value of this item, unless it is equal to the current one
+    ///
+    /// Returns `true` if the value was written.
+    pub fn set_if_changed(&self, value: C::Value) -> bool
+    where
+        C::Value: PartialEq,
+    {
+        !self.is_stale() && self.store.set_if_changed(self.key.clone(), value)
+    }
+
+    /// Check if this item is currently selected, including as part of a range selection
     pub fn is_selected(&self) -> bool {
-        *self.store.selected_key_signal().read() == Some(self.key.clone())
+        if *self.store.selected_key_signal().read() == Some(self.key.clone()) {
+            return true;
+        }
+        self.store.selection_anchor().is_some() && self.store.selected_keys().contains(&self.key)
     }
 
     /// Select this item
     pub fn select(&self) -> CollectionResult<()> {
+        self.store.ensure_generation(&self.key, self.generation)?;
         self.store.select(&self.key)
     }
 
     /// Remove this item from the collection
     ///
-    /// Returns the removed value.
+    /// Returns the removed value, or `None` if this handle is stale.
     pub fn remove(&self) -> Option<C::Value>
     where
         C::Value: Clone,
     {
-        self.store.remove(&self.key)
+        self.try_remove().ok().flatten()
+    }
+
+    /// Remove this item like `remove`, failing with `CollectionError::StaleHandle` if this handle is stale,
+    /// or `CollectionError::Locked` while the store is locked
+    pub fn try_remove(&self) -> CollectionResult<Option<C::Value>>
+    where
+        C::Value: Clone,
+    {
+        self.store.ensure_generation(&self.key, self.generation)?;
+        self.store.try_remove(&self.key)
     }
 }
@@ -1,9 +1,59 @@
-use crate::{Collection, CollectionError, CollectionItem, CollectionResult, SequentialCollection};
+use crate::access::AccessTracking;
+#[cfg(feature = "announcer")]
+use crate::announcer::Announcer;
+use crate::dedup::ValueEq;
+use crate::generation::GenerationTable;
+#[cfg(feature = "index")]
+use crate::index::IndexHook;
+use crate::item_meta::MetaTables;
+#[cfg(feature = "offline")]
+use crate::offline::OfflineQueue;
+use crate::placeholder::PlaceholderFn;
+use crate::recording::{CollectionOperation, Recorder};
+use crate::selectable::SelectablePredicate;
+use crate::selection_behavior::{Addition, SelectionBehavior};
+use crate::selection_change::SelectionListener;
+use crate::selection_guard::SelectionGuard;
+#[cfg(feature = "tracing")]
+use crate::trace::Tracer;
+#[cfg(feature = "ttl")]
+use crate::ttl::TtlSweep;
+use crate::{
+    BorrowedLookup, Collection, CollectionError, CollectionItem, CollectionResult,
+    SequentialCollection,
+};
+use dioxus_core::ScopeId;
 use dioxus_signals::*;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+
+/// Placeholder for the tracing state, never set without the `tracing` feature
+#[cfg(not(feature = "tracing"))]
+type Tracer<C> = std::marker::PhantomData<C>;
+
+/// Placeholder for the announcer, never set without the `announcer` feature
+#[cfg(not(feature = "announcer"))]
+type Announcer<C> = std::marker::PhantomData<C>;
+
+/// Placeholder for the offline queue, never set without the `offline` feature
+#[cfg(not(feature = "offline"))]
+type OfflineQueue<C> = std::marker::PhantomData<C>;
+
+/// Placeholder for the indexes, never added without the `index` feature
+#[cfg(not(feature = "index"))]
+type IndexHook<C> = std::marker::PhantomData<C>;
+
+/// Placeholder for the TTL sweep, never scheduled without the `ttl` feature
+#[cfg(not(feature = "ttl"))]
+type TtlSweep = ();
 
 use dioxus_stores::*;
 
 /// Generic collection data wrapper for the Store
+///
+/// This is the state behind a `CollectionStore`, reachable with `CollectionStore::as_store`.
+/// Only the `items` and `selected_key` lenses of `CollectionDataStoreExt` are part of the public API,
+/// the other fields are internal state that may change between releases.
 #[derive(Store)]
 pub struct CollectionData<C>
 where
@@ -11,6 +61,73 @@ where
 {
     pub(crate) items: C,
     pub(crate) selected_key: Option<C::Key>,
+    pub(crate) focused_key: Option<C::Key>,
+    pub(crate) recorder: Option<Recorder<C>>,
+    pub(crate) strict: bool,
+    pub(crate) meta: MetaTables<C::Key>,
+    pub(crate) announcer: Option<Announcer<C>>,
+    pub(crate) announcement: String,
+    pub(crate) selectable: Option<SelectablePredicate<C>>,
+    pub(crate) selection_anchor: Option<C::Key>,
+    pub(crate) placeholder: Option<PlaceholderFn<C::Value>>,
+    pub(crate) selection_behavior: SelectionBehavior,
+    pub(crate) position_tracking: bool,
+    pub(crate) previous_positions: Vec<(C::Key, usize)>,
+    pub(crate) tracer: Option<Tracer<C>>,
+    pub(crate) locked: bool,
+    pub(crate) write_equality: Option<ValueEq<C::Value>>,
+    pub(crate) generations: Option<Box<dyn GenerationTable<C::Key>>>,
+    pub(crate) offline_queue: Option<OfflineQueue<C>>,
+    pub(crate) access_tracking: Option<AccessTracking<C>>,
+    pub(crate) selection_guard: Option<SelectionGuard<C>>,
+    pub(crate) selection_listeners: Vec<SelectionListener<C>>,
+    pub(crate) indexes: Vec<IndexHook<C>>,
+    pub(crate) ttl_sweep: Option<TtlSweep>,
+}
+
+impl<C> CollectionData<C>
+where
+    C: Collection + 'static,
+{
+    /// Wrap a collection, with nothing selected
+    pub(crate) fn new(items: C) -> Self {
+        Self {
+            items,
+            selected_key: None,
+            focused_key: None,
+            recorder: None,
+            strict: false,
+            meta: MetaTables::new(),
+            announcer: None,
+            announcement: String::new(),
+            selectable: None,
+            selection_anchor: None,
+            placeholder: None,
+            selection_behavior: SelectionBehavior::Manual,
+            position_tracking: false,
+            previous_positions: Vec::new(),
+            tracer: None,
+            locked: false,
+            write_equality: None,
+            generations: None,
+            offline_queue: None,
+            access_tracking: None,
+            selection_guard: None,
+            selection_listeners: Vec::new(),
+            indexes: Vec::new(),
+            ttl_sweep: None,
+        }
+    }
+
+    /// Get the wrapped collection
+    pub fn items(&self) -> &C {
+        &self.items
+    }
+
+    /// Get the selected key
+    pub fn selected_key(&self) -> Option<&C::Key> {
+        self.selected_key.as_ref()
+    }
 }
 /// Generic collection store that works with any Collection implementation
 ///
@@ -49,12 +166,13 @@ where
 /// # impl<T> CircularBuffer<T> { fn new(cap: usize) -> Self { Self { data: Vec::new() } } }
 /// let logs = use_collection(|| CircularBuffer::new(5));
 /// ```
-#[derive(PartialEq)]
 pub struct CollectionStore<C>
 where
     C: Collection + 'static,
 {
-    inner: Store<CollectionData<C>>,
+    pub(crate) inner: Store<CollectionData<C>>,
+    /// Owned by the same scope as the store, so weak handles can check if the store was dropped
+    pub(crate) alive: CopyValue<()>,
 }
 
 impl<C> std::fmt::Debug for CollectionStore<C>
@@ -72,6 +190,15 @@ where
 
 impl<C> Copy for CollectionStore<C> where C: Collection + 'static {}
 
+impl<C> PartialEq for CollectionStore<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
 impl<C> Clone for CollectionStore<C>
 where
     C: Collection + 'static,
@@ -87,12 +214,45 @@ where
     C::Key: Clone + PartialEq,
 {
     /// Create a new CollectionStore from a collection
+    ///
+    /// The store is owned by the current scope and dropped with it, so calling this in an event handler
+    /// ties the store to the component handling the event. Use `use_collection` in components, and
+    /// `new_in_scope` or `new_global` to pick the owner explicitly.
     pub fn new(collection: C) -> Self {
-        let store = Store::new(CollectionData {
-            items: collection,
-            selected_key: None,
-        });
-        Self { inner: store }
+        Self {
+            inner: Store::new(CollectionData::new(collection)),
+            alive: CopyValue::new(()),
+        }
+    }
+
+    /// Create a new CollectionStore owned by `scope`, dropped when that scope is
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus::prelude::*;
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// // In an event handler of a child component: the store outlives the child
+    /// let parent = ScopeId::ROOT;
+    /// let store = CollectionStore::new_in_scope(vec![1, 2, 3], parent);
+    /// ```
+    pub fn new_in_scope(collection: C, scope: ScopeId) -> Self {
+        let runtime = dioxus_core::Runtime::current();
+        let owner = runtime.scope_owner::<UnsyncStorage>(scope);
+        // The store's subscriptions are kept in sync storage
+        let sync_owner = runtime.scope_owner::<SyncStorage>(scope);
+        dioxus_core::with_owner(sync_owner, || {
+            dioxus_core::with_owner(owner, || Self::new(collection))
+        })
+    }
+
+    /// Create a new CollectionStore owned by the root scope, living as long as the app
+    ///
+    /// Use it for stores shared through context from a top-level provider, or created lazily
+    /// outside of any component hook.
+    pub fn new_global(collection: C) -> Self {
+        Self::new_in_scope(collection, ScopeId::ROOT)
     }
 
     /// Get the length of the collection
@@ -100,6 +260,11 @@ where
         self.inner.items().read().len()
     }
 
+    /// Get the length of the collection, without subscribing to it
+    pub fn peek_len(&self) -> usize {
+        self.inner.items().peek().len()
+    }
+
     /// Check if the collection is empty
     pub fn is_empty(&self) -> bool {
         self.inner.items().read().is_empty()
@@ -115,11 +280,37 @@ where
         self.inner.items()
     }
 
+    /// Get the underlying `dioxus-stores` store, to compose custom lenses and memos
+    ///
+    /// Use the `items()` and `selected_key()` lenses of `CollectionDataStoreExt`, or implement your own
+    /// with `#[store]`. Writes made through the returned store bypass the `CollectionStore` API: they are
+    /// not recorded or announced, per-item state (metadata, focus) doesn't follow the items, and the selected
+    /// key must be kept pointing to an existing item. With strict mode enabled, the next store operation
+    /// checks the invariants in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionDataStoreExt, CollectionStore};
+    /// use dioxus_signals::*;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// let items = store.as_store().items();
+    /// let total: i32 = items.read().iter().sum();
+    /// assert_eq!(total, 6);
+    /// ```
+    pub fn as_store(&self) -> Store<CollectionData<C>> {
+        self.inner
+    }
+
     /// Get a CollectionItem for a specific key in the collection
     ///
     /// This returns a CollectionItem that provides signal-based reactive access to a single item.
     /// Use `.read()` to get a reactive reference or `.boxed()` to get a ReadSignal.
     ///
+    /// Like `HashMap::get`, the key can be any borrowed form of `C::Key` the collection can look up
+    /// (see `BorrowedLookup`), such as a `&str` for `String` keys. The item keeps an owned copy of it.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -131,19 +322,22 @@ where
     /// let signal = item.boxed();    // Or get ReadSignal
     /// item.set(42);                 // Update the value directly
     /// ```
-    pub fn get(&self, key: &C::Key) -> CollectionItem<C> {
-        CollectionItem {
-            store: *self,
-            key: key.clone(),
-        }
+    pub fn get<Q>(&self, key: &Q) -> CollectionItem<C>
+    where
+        C: BorrowedLookup<Q>,
+        C::Key: Borrow<Q>,
+        Q: ToOwned<Owned = C::Key> + ?Sized,
+    {
+        CollectionItem::new(*self, key.to_owned())
     }
 
     /// Read a value from the collection by key (returns a ReadSignal, no clone)
     ///
     /// Returns a ReadSignal that provides reactive access to the value.
-    /// Creates a reactive dependency on this item's value.
+    /// Creates a reactive dependency on this item's value when `.read()` is called on it:
+    /// use `peek_value` (or `.peek()` on the signal) to read without subscribing.
     ///
-    /// This is a convenience method equivalent to `self.get(key).boxed()`.
+    /// This is a convenience method equivalent to `self.get(key).boxed()`, so it takes the same borrowed keys.
     ///
     /// # Examples
     ///
@@ -154,18 +348,21 @@ where
     /// let signal = store.read(&1);  // ReadSignal for value at index 1
     /// assert_eq!(*signal.read(), 2);
     /// ```
-    pub fn read(&self, key: &C::Key) -> ReadSignal<C::Value>
+    pub fn read<Q>(&self, key: &Q) -> ReadSignal<C::Value>
     where
+        C: BorrowedLookup<Q>,
+        C::Key: Borrow<Q>,
+        Q: ToOwned<Owned = C::Key> + ?Sized,
         C::Value: 'static,
     {
         self.get(key).boxed()
     }
 
-    /// Write to a value in the collection by key (returns a WriteSignal, no clone)
-    ///
-    /// Returns a WriteSignal that provides mutable reactive access to the value.
+    /// Get a copy of a value, without subscribing to the collection
     ///
-    /// This is a convenience method equivalent to `self.get(key).boxed_mut()`.
+    /// Returns `None` if the key is not in the collection. Unlike `read`, which hands out a signal
+    /// subscribing whoever reads it, this is meant for event handlers and other one-off reads
+    /// that shouldn't re-run when the value changes.
     ///
     /// # Examples
     ///
@@ -173,21 +370,21 @@ where
     /// use dioxus_collection_store::CollectionStore;
     ///
     /// let store = CollectionStore::new(vec![1, 2, 3]);
-    /// let signal = store.write(&1);  // WriteSignal for value at index 1
-    /// *signal.write() = 42;
+    /// assert_eq!(store.peek_value(&1), Some(2));
+    /// assert_eq!(store.peek_value(&5), None);
     /// ```
-    pub fn write(&self, key: &C::Key) -> WriteSignal<C::Value>
+    pub fn peek_value(&self, key: &C::Key) -> Option<C::Value>
     where
-        C::Value: 'static,
+        C::Value: Clone,
     {
-        self.get(key).boxed_mut()
+        self.inner.items().peek().get(key).cloned()
     }
 
-    /// Peek at a value from the collection by key without subscribing (returns ReadSignal, no clone)
+    /// Write to a value in the collection by key (returns a WriteSignal, no clone)
     ///
-    /// Returns a ReadSignal that can be used to peek at the value without creating a reactive dependency.
+    /// Returns a WriteSignal that provides mutable reactive access to the value.
     ///
-    /// This is a convenience method equivalent to `self.get(key).boxed()`.
+    /// This is a convenience method equivalent to `self.get(key).boxed_mut()`.
     ///
     /// # Examples
     ///
@@ -195,14 +392,14 @@ where
     /// use dioxus_collection_store::CollectionStore;
     ///
     /// let store = CollectionStore::new(vec![1, 2, 3]);
-    /// let signal = store.peek(&1);  // ReadSignal for value at index 1
-    /// assert_eq!(*signal.peek(), 2);  // Peek without subscribing
+    /// let signal = store.write(&1);  // WriteSignal for value at index 1
+    /// *signal.write() = 42;
     /// ```
-    pub fn peek(&self, key: &C::Key) -> ReadSignal<C::Value>
+    pub fn write(&self, key: &C::Key) -> WriteSignal<C::Value>
     where
         C::Value: 'static,
     {
-        self.get(key).boxed()
+        self.get(key).boxed_mut()
     }
 
     /// Get a Store for the selected key
@@ -243,234 +440,1078 @@ where
     /// ```
     pub fn selected(&self) -> Option<CollectionItem<C>> {
         let key = self.selected_key()?;
-        Some(CollectionItem { store: *self, key })
+        Some(CollectionItem::new(*self, key))
     }
 
     /// Check if a key exists in the collection
-    pub fn contains_key(&self, key: &C::Key) -> bool {
-        self.inner.items().read().get(key).is_some()
+    ///
+    /// Like `HashMap::contains_key`, the key can be any borrowed form of `C::Key` the collection
+    /// can look up (see `BorrowedLookup`), and no owned key is built.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        C: BorrowedLookup<Q>,
+        C::Key: Borrow<Q>,
+        Q: ?Sized,
+    {
+        self.inner.items().read().contains_borrowed(key)
     }
 
-    /// Insert or update a value in the collection by key
+    /// Check if a key exists in the collection, without subscribing to it
     ///
-    /// This method updates an existing key or inserts a new one.
-    /// Returns the previous value if the key existed.
+    /// For event handlers and other one-off checks that shouldn't re-run when the collection changes.
+    pub fn peek_contains_key(&self, key: &C::Key) -> bool {
+        self.inner.items().peek().get(key).is_some()
+    }
+
+    /// Check if a key exists in the collection, from a borrowed form of the key (reactive)
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```rust,no_run
     /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
     ///
-    /// let store = CollectionStore::new(vec![1, 2, 3]);
-    /// store.insert(1, 42);  // Update index 1 to value 42
+    /// let store = CollectionStore::new(HashMap::from([("ada".to_string(), 36)]));
+    /// assert!(store.contains_borrowed("ada"));
     /// ```
-    pub fn insert(&self, key: C::Key, value: C::Value) -> Option<C::Value>
+    pub fn contains_borrowed<Q>(&self, key: &Q) -> bool
     where
-        C::Value: Clone,
+        C: BorrowedLookup<Q>,
+        Q: ?Sized,
     {
-        self.inner.items().write().insert(key, value)
+        self.inner.items().read().contains_borrowed(key)
     }
 
-    /// Set/replace a value in the collection by key.
+    /// Read a value from a borrowed form of its key (reactive)
     ///
-    /// For Vec collections, this will panic if the index is out of bounds.
-    /// For HashMap collections, this will insert if the key doesn't exist.
+    /// Returns `None` if the key is not in the collection. Items hold owned keys,
+    /// so this is the allocation-free alternative to `store.get(&key.to_string()).read()`.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```rust,no_run
     /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
     ///
-    /// let store = CollectionStore::new(vec![1, 2, 3]);
-    /// store.set(1, 42);  // Sets index 1 to 42
+    /// let store = CollectionStore::new(HashMap::from([("ada".to_string(), 36)]));
+    /// assert_eq!(store.map_borrowed("ada", |age| age + 1), Some(37));
     /// ```
-    pub fn set(&self, key: C::Key, value: C::Value)
+    pub fn map_borrowed<Q, R>(&self, key: &Q, map: impl FnOnce(&C::Value) -> R) -> Option<R>
     where
-        C::Value: Clone,
+        C: BorrowedLookup<Q>,
+        Q: ?Sized,
     {
-        self.inner.items().write().set(key, value);
+        self.inner.items().read().get_borrowed(key).map(map)
     }
 
-    /// Remove an item from the collection
+    /// Check if a value exists in the collection
     ///
-    /// If the removed item was selected, the selection will be cleared.
-    pub fn remove(&self, key: &C::Key) -> Option<C::Value>
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// assert!(store.contains_value(&2));
+    /// ```
+    pub fn contains_value(&self, value: &C::Value) -> bool
     where
-        C::Value: Clone,
+        C::Value: PartialEq,
     {
-        // Clear selection if we're removing the selected item
-        if self.selected_key() == Some(key.clone()) {
-            self.clear_selection();
-        }
-        self.inner.items().write().remove(key)
+        self.inner.items().read().contains_value(value)
     }
 
-    /// Get an iterator over the collection items
-    ///
-    /// Returns an iterator of `CollectionItem` references that implement both `Readable` and `Writable`.
-    /// This leverages Dioxus signals for efficient reactive access, allowing you to:
-    ///
-    /// - **Read values reactively**: `item.read()` creates a reactive dependency
-    /// - **Mutate values in-place**: `item.write()` provides mutable access
-    /// - **Combine with Rust iterators**: Use `filter()`, `map()`, `find()`, etc.
+    /// Find the key of the first value matching the predicate
     ///
-    /// Unlike traditional Rust collections that require separate `iter()` and `iter_mut()` methods,
-    /// **a single iterator provides both read and write capabilities** thanks to the signal system.
+    /// The scan is delegated to the collection, so no `CollectionItem` is created
+    /// for the values that do not match.
     ///
     /// # Examples
     ///
-    /// ## Read-only iteration
     /// ```rust,no_run
     /// use dioxus_collection_store::CollectionStore;
     ///
-    /// let store = CollectionStore::new(vec![1, 2, 3, 4, 5]);
-    ///
-    /// for item in store.iter() {
-    ///     println!("{}", item.read());
-    /// }
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// assert_eq!(store.find_key(|value| *value > 1), Some(1));
     /// ```
+    pub fn find_key(&self, predicate: impl FnMut(&C::Value) -> bool) -> Option<C::Key> {
+        self.inner.items().read().find_key(predicate)
+    }
+
+    /// Find the key of the first value equal to `value`
+    ///
+    /// # Examples
     ///
-    /// ## Combining filter and mutation in one pass
     /// ```rust,no_run
     /// use dioxus_collection_store::CollectionStore;
     ///
-    /// let store = CollectionStore::new(vec![1, 2, 3, 4, 5]);
-    ///
-    /// // Filter and mutate in a single iteration!
-    /// store.iter()
-    ///     .filter(|item| *item.read() > 2)
-    ///     .for_each(|item| {
-    ///         let current = *item.read();
-    ///         item.set(current * 10);
-    ///     });
-    /// // Result: [1, 2, 30, 40, 50]
+    /// let store = CollectionStore::new(vec!["a", "b", "c"]);
+    /// assert_eq!(store.position_of(&"c"), Some(2));
     /// ```
+    pub fn position_of(&self, value: &C::Value) -> Option<C::Key>
+    where
+        C::Value: PartialEq,
+    {
+        self.inner.items().read().position_of(value)
+    }
+
+    /// Insert or update a value in the collection by key
     ///
-    /// ## Mixed read/write in same loop
-    /// ```rust,no_run
-    /// use dioxus_collection_store::CollectionStore;
+    /// This method updates an existing key or inserts a new one.
+    /// Returns the previous value if the key existed.
+    /// A new key is selected if the store's `SelectionBehavior` asks for it.
     ///
-    /// let store = CollectionStore::new(vec!["task1", "urgent_task", "task2"]);
+    /// # Examples
     ///
-    /// for item in store.iter() {
-    ///     let value = item.read();
-    ///     if value.contains("urgent") {
-    ///         // Read-only: just log
-    ///         println!("Found urgent: {}", value);
-    ///     } else {
-    ///         // Mutate: add suffix
-    ///         item.write().push_str(" [processed]");
-    ///     }
-    /// }
+    /// ```no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.insert(1, 42);  // Update index 1 to value 42
     /// ```
-    pub fn iter(&self) -> impl Iterator<Item = CollectionItem<C>> + '_
+    pub fn insert(&self, key: C::Key, value: C::Value) -> Option<C::Value>
     where
-        C::Key: Clone,
+        C::Value: Clone,
     {
-        let keys: Vec<C::Key> = self.inner.items().read().keys();
-        let store = *self;
-
-        keys.into_iter()
-            .map(move |key| CollectionItem { store, key })
-    }
-
-    /// Select an item by its key
-    pub fn select(&self, key: &C::Key) -> CollectionResult<()> {
-        if self.contains_key(key) {
-            self.inner.selected_key().set(Some(key.clone()));
-            Ok(())
-        } else {
-            Err(CollectionError::KeyNotFound)
-        }
+        self.try_insert(key, value).ok().flatten()
     }
 
-    /// Select an item by its value
-    ///
-    /// Recommended to use `Self::select` instead whenever possible.
-    /// Not recommended for performance reasons and values may not be unique.
-    /// If values are not unique, this method selects the first matching item.
-    pub fn select_by_value(&self, value: &C::Value) -> CollectionResult<()>
+    /// Insert or update a value like `insert`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_insert(&self, key: C::Key, value: C::Value) -> CollectionResult<Option<C::Value>>
     where
-        C::Value: PartialEq,
+        C::Value: Clone,
     {
-        for item in self.iter() {
-            if &*item.read() == value {
-                return item.select();
-            }
+        self.ensure_unlocked()?;
+        self.record(|clone| CollectionOperation::Insert {
+            key: key.clone(),
+            value: clone(&value),
+        });
+        let previous = self.inner.items().write().insert(key.clone(), value);
+        if previous.is_none() && self.inner.items().peek().contains_key(&key) {
+            self.select_added(&key, Addition::Insert);
+            self.track_added(&key);
         }
-        Err(CollectionError::KeyNotFound)
+        self.finish_mutation("insert");
+        Ok(previous)
     }
 
-    /// Select an item by its display string value
+    /// Insert or update a value, returning the item with the previous value
     ///
-    /// Recommended to use `Self::select` instead whenever possible.
-    /// Not recommended for performance reasons and values may not be unique.
-    /// If displayed values are not unique, this method selects the first matching item.
-    pub fn select_by_display(&self, display_value: &str) -> CollectionResult<()>
+    /// Fails with `CollectionError::KeyNotFound` if the collection refused the key (like an out of bounds `Vec` index).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
+    ///
+    /// let store = CollectionStore::new(HashMap::new());
+    /// let (item, previous) = store.insert_and_get("ada", 36).unwrap();
+    /// assert_eq!(previous, None);
+    /// item.select().ok();
+    /// ```
+    pub fn insert_and_get(
+        &self,
+        key: C::Key,
+        value: C::Value,
+    ) -> CollectionResult<(CollectionItem<C>, Option<C::Value>)>
     where
-        C::Value: std::fmt::Display,
+        C::Value: Clone,
     {
-        for item in self.iter() {
-            if item.read().to_string() == display_value {
-                return item.select();
-            }
+        let previous = self.try_insert(key.clone(), value)?;
+        if !self.inner.items().peek().contains_key(&key) {
+            return Err(CollectionError::KeyNotFound);
         }
-        Err(CollectionError::KeyNotFound)
-    }
-
-    /// Get the currently selected key
-    pub fn selected_key(&self) -> Option<C::Key> {
-        self.inner.selected_key().read().clone()
-    }
-
-    /// Clear the selection
-    pub fn clear_selection(&self) {
-        self.selected_key_signal().set(None);
+        Ok((self.get(&key), previous))
     }
 
-    /// Remove all items from the collection
+    /// Set/replace a value in the collection by key.
+    ///
+    /// For Vec collections, this will panic if the index is out of bounds.
+    /// For HashMap collections, this will insert if the key doesn't exist.
+    /// With `dedup_writes` enabled, values equal to the current ones are skipped.
     ///
     /// # Examples
     ///
-    /// ```rust,no_run
+    /// ```no_run
     /// use dioxus_collection_store::CollectionStore;
     ///
     /// let store = CollectionStore::new(vec![1, 2, 3]);
-    /// store.clear();
-    /// assert!(store.is_empty());
+    /// store.set(1, 42);  // Sets index 1 to 42
     /// ```
-    pub fn clear(&self) {
-        self.inner.items().write().clear();
-        self.clear_selection();
+    pub fn set(&self, key: C::Key, value: C::Value) {
+        self.try_set(key, value).ok();
     }
 
-    /// Extend the collection with multiple key-value pairs
+    /// Set a value like `set`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_set(&self, key: C::Key, value: C::Value) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        if self.is_unchanged_write(&key, &value) {
+            return Ok(());
+        }
+        self.record(|clone| CollectionOperation::Set {
+            key: key.clone(),
+            value: clone(&value),
+        });
+        self.inner.items().write().set(key, value);
+        self.finish_mutation("set");
+        Ok(())
+    }
+
+    /// Remove an item from the collection
+    ///
+    /// If the removed item was selected, the selection will be cleared. Otherwise it follows the selected item:
+    /// with positional keys (like `Vec` indices), a selection after the removed item moves down by one.
+    /// Like `get`, the key can be any borrowed form of `C::Key`: an owned key is only built
+    /// once the item is known to be there.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
-    /// use std::collections::HashMap;
     /// use dioxus_collection_store::CollectionStore;
     ///
-    /// let store = CollectionStore::new(HashMap::new());
-    /// store.extend(vec![
-    ///     ("key1".to_string(), 1),
-    ///     ("key2".to_string(), 2),
-    /// ]);
-    /// assert_eq!(store.len(), 2);
+    /// let store = CollectionStore::new(vec!["a", "b", "c"]);
+    /// store.select(&2).ok();
+    /// assert_eq!(store.remove(&0), Some("a"));
+    /// // Now the collection is ["b", "c"] and "c" is still selected, at index 1
+    /// assert_eq!(store.selected_key(), Some(1));
     /// ```
-    pub fn extend<I: IntoIterator<Item = (C::Key, C::Value)>>(&self, items: I)
+    pub fn remove<Q>(&self, key: &Q) -> Option<C::Value>
     where
+        C: BorrowedLookup<Q>,
+        C::Key: Borrow<Q>,
+        Q: ToOwned<Owned = C::Key> + ?Sized,
         C::Value: Clone,
     {
-        self.inner.items().write().extend(items);
+        self.try_remove(key).ok().flatten()
     }
-}
 
-/// Conversion from `Store<CollectionData<C>>` to `CollectionStore<C>`
-///
-/// This allows you to use `.into()` for type conversion, which is more idiomatic
-/// than a custom `from_store()` method.
+    /// Remove an item like `remove`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_remove<Q>(&self, key: &Q) -> CollectionResult<Option<C::Value>>
+    where
+        C: BorrowedLookup<Q>,
+        C::Key: Borrow<Q>,
+        Q: ToOwned<Owned = C::Key> + ?Sized,
+        C::Value: Clone,
+    {
+        self.ensure_unlocked()?;
+        if !self.inner.items().peek().contains_borrowed(key) {
+            return Ok(None);
+        }
+        let key = &key.to_owned();
+        self.deselect_removed(|selected| selected == key);
+        self.record(|_| CollectionOperation::Remove { key: key.clone() });
+        let tracked_keys = self.tracked_keys();
+        // The selection follows its item too, positional keys shift down past the removed one
+        let selection_keys = match tracked_keys {
+            None if self.inner.selected_key().peek().is_some() => {
+                Some(self.inner.items().peek().keys())
+            }
+            _ => None,
+        };
+        let old_keys = tracked_keys.as_ref().or(selection_keys.as_ref());
+        let removed_position = old_keys.and_then(|keys| keys.iter().position(|k| k == key));
+        let removed = self.inner.items().write().remove(key);
+        let new_position = |position: usize| match removed_position {
+            Some(removed) if position == removed => None,
+            Some(removed) if position > removed => Some(position - 1),
+            _ => Some(position),
+        };
+        if let Some(old_keys) = old_keys {
+            self.remap_selection(old_keys, &new_position);
+        }
+        self.remap_tracked(tracked_keys, new_position);
+        self.finish_mutation("remove");
+        Ok(removed)
+    }
+
+    /// Remove several items from the collection in a single write
+    ///
+    /// Returns the removed values in collection order. Unknown keys are ignored.
+    ///
+    /// Removal is done from the last position to the first, so positional keys (like `Vec` indices)
+    /// are resolved against the collection as it was before the call. If the selected item was
+    /// removed the selection is cleared, otherwise it follows the selected item to its new key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+    /// store.select(&3).ok();
+    /// let removed = store.remove_many([0, 2]);
+    /// assert_eq!(removed, vec!["a", "c"]);
+    /// // Now the collection is ["b", "d"] and "d" is still selected, at index 1
+    /// assert_eq!(store.selected_key(), Some(1));
+    /// ```
+    pub fn remove_many(&self, keys: impl IntoIterator<Item = C::Key>) -> Vec<C::Value> {
+        self.try_remove_many(keys).unwrap_or_default()
+    }
+
+    /// Remove several items like `remove_many`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_remove_many(
+        &self,
+        keys: impl IntoIterator<Item = C::Key>,
+    ) -> CollectionResult<Vec<C::Value>> {
+        self.ensure_unlocked()?;
+        let keys: Vec<C::Key> = keys.into_iter().collect();
+        self.deselect_removed(|selected| keys.contains(selected));
+        self.record(|_| CollectionOperation::RemoveMany { keys: keys.clone() });
+        let selected_key = self.peek_selected_key();
+        let tracked_keys = self.tracked_keys();
+        let (removed, new_selection, positions) = {
+            let mut items_lens = self.inner.items();
+            let mut items = items_lens.write();
+            let old_keys = items.keys();
+
+            // `key_position` finds positional keys directly, sorting once keeps the lookups below logarithmic
+            let mut positions: Vec<usize> = keys
+                .iter()
+                .filter_map(|key| items.key_position(key))
+                .collect();
+            positions.sort_unstable();
+            positions.dedup();
+            let selected_position = selected_key.and_then(|selected| items.key_position(&selected));
+
+            let mut removed: Vec<C::Value> = positions
+                .iter()
+                .rev()
+                .filter_map(|position| items.remove(&old_keys[*position]))
+                .collect();
+            removed.reverse();
+
+            let new_selection = selected_position.and_then(|position| {
+                if positions.binary_search(&position).is_ok() {
+                    return None;
+                }
+                let shift = positions.partition_point(|removed| *removed < position);
+                items.keys().into_iter().nth(position - shift)
+            });
+            (removed, new_selection, positions)
+        };
+
+        if new_selection != self.peek_selected_key() {
+            self.inner.selected_key().set(new_selection);
+        }
+        self.remap_tracked(tracked_keys, |position| {
+            if positions.binary_search(&position).is_ok() {
+                return None;
+            }
+            Some(position - positions.partition_point(|removed| *removed < position))
+        });
+        self.finish_mutation("remove_many");
+        Ok(removed)
+    }
+
+    /// Keep only the items for which `keep` returns `true`, in a single write
+    ///
+    /// Uses the collection's own `retain`, so custom collections can filter in place.
+    /// If the selected item was removed the selection is cleared, otherwise it follows the selected item to its new key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3, 4]);
+    /// store.select(&3).ok();
+    /// store.retain(|_, value| value % 2 == 0);
+    /// // Now the collection is [2, 4] and 4 is still selected, at index 1
+    /// assert_eq!(store.selected_key(), Some(1));
+    /// ```
+    pub fn retain(&self, keep: impl FnMut(&C::Key, &C::Value) -> bool) {
+        self.try_retain(keep).ok();
+    }
+
+    /// Keep only some items like `retain`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_retain(
+        &self,
+        mut keep: impl FnMut(&C::Key, &C::Value) -> bool,
+    ) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let (old_keys, kept) = {
+            let items = self.inner.items();
+            let items = items.peek();
+            let old_keys = items.keys();
+            let kept: Vec<bool> = old_keys
+                .iter()
+                .map(|key| items.get(key).is_none_or(|value| keep(key, value)))
+                .collect();
+            (old_keys, kept)
+        };
+        let removed: Vec<usize> = (0..kept.len())
+            .filter(|position| !kept[*position])
+            .collect();
+        if removed.is_empty() {
+            return Ok(());
+        }
+        self.deselect_removed(|selected| {
+            removed
+                .iter()
+                .any(|position| old_keys[*position] == *selected)
+        });
+        self.record(|_| CollectionOperation::RemoveMany {
+            keys: removed
+                .iter()
+                .map(|position| old_keys[*position].clone())
+                .collect(),
+        });
+
+        let tracked_keys = self.tracked_keys();
+        let mut kept = kept.into_iter();
+        self.inner
+            .items()
+            .write()
+            .retain(|_, _| kept.next().unwrap_or(true));
+        let new_position = |position: usize| {
+            if removed.binary_search(&position).is_ok() {
+                return None;
+            }
+            Some(position - removed.partition_point(|removed| *removed < position))
+        };
+        self.remap_selection(&old_keys, &new_position);
+        self.remap_tracked(tracked_keys, new_position);
+        self.finish_mutation("retain");
+        Ok(())
+    }
+
+    /// Replace every value with `map(key, value)`, in a single write
+    ///
+    /// Readers are notified once, instead of once per item with a loop of `set`. Each new value is recorded
+    /// as a `Set`. With `dedup_writes` enabled, unchanged values are skipped, and nothing is written if all
+    /// of them are unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let prices = CollectionStore::new(vec![10, 20, 30]);
+    /// prices.map_in_place(|_, price| price * 2);
+    /// assert_eq!(prices.peek_value(&2), Some(60));
+    /// ```
+    pub fn map_in_place(&self, map: impl FnMut(&C::Key, &C::Value) -> C::Value) {
+        self.try_map_in_place(map).ok();
+    }
+
+    /// Replace every value like `map_in_place`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_map_in_place(
+        &self,
+        mut map: impl FnMut(&C::Key, &C::Value) -> C::Value,
+    ) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let changes: Vec<(C::Key, C::Value)> = {
+            let items = self.inner.items();
+            let items = items.peek();
+            items
+                .keys()
+                .into_iter()
+                .filter_map(|key| {
+                    let value = map(&key, items.get(&key)?);
+                    Some((key, value))
+                })
+                .collect()
+        };
+        let changes: Vec<(C::Key, C::Value)> = changes
+            .into_iter()
+            .filter(|(key, value)| !self.is_unchanged_write(key, value))
+            .collect();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        for (key, value) in &changes {
+            self.record(|clone| CollectionOperation::Set {
+                key: key.clone(),
+                value: clone(value),
+            });
+        }
+        {
+            let mut items = self.inner.items();
+            let mut items = items.write();
+            for (key, value) in changes {
+                items.set(key, value);
+            }
+        }
+        self.finish_mutation("map_in_place");
+        Ok(())
+    }
+
+    /// Mutate every value in place with `update(key, value)`, in a single write
+    ///
+    /// Like `map_in_place`, without building new values. Each updated value is recorded as a `Set`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let todos = CollectionStore::new(vec![("write docs", false), ("fix bug", false)]);
+    /// todos.for_each_mut(|_, (_, done)| *done = true);
+    /// assert_eq!(todos.peek_value(&1), Some(("fix bug", true)));
+    /// ```
+    pub fn for_each_mut(&self, update: impl FnMut(&C::Key, &mut C::Value)) {
+        self.try_for_each_mut(update).ok();
+    }
+
+    /// Mutate every value like `for_each_mut`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_for_each_mut(
+        &self,
+        mut update: impl FnMut(&C::Key, &mut C::Value),
+    ) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let keys = self.inner.items().peek().keys();
+        if keys.is_empty() {
+            return Ok(());
+        }
+        // Recorded operations need the new values before the write: when recording, update copies
+        // and write them back, otherwise update the values in place
+        let mut updated = Vec::new();
+        for key in &keys {
+            self.record(|clone| {
+                let items = self.inner.items();
+                let items = items.peek();
+                let mut value = clone(
+                    items
+                        .get(key)
+                        .expect("for_each_mut doesn't change the keys"),
+                );
+                update(key, &mut value);
+                let operation = CollectionOperation::Set {
+                    key: key.clone(),
+                    value: clone(&value),
+                };
+                updated.push((key.clone(), value));
+                operation
+            });
+        }
+        {
+            let mut items = self.inner.items();
+            let mut items = items.write();
+            if updated.is_empty() {
+                for key in &keys {
+                    if let Some(value) = items.get_mut(key) {
+                        update(key, value);
+                    }
+                }
+            } else {
+                for (key, value) in updated {
+                    if let Some(slot) = items.get_mut(&key) {
+                        *slot = value;
+                    }
+                }
+            }
+        }
+        self.finish_mutation("for_each_mut");
+        Ok(())
+    }
+
+    /// Remove all the items, returning them with their keys
+    ///
+    /// Like `clear`, the selection, focus and item metadata are cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b"]);
+    /// assert_eq!(store.drain(), vec![(0, "a"), (1, "b")]);
+    /// assert!(store.is_empty());
+    /// ```
+    pub fn drain(&self) -> Vec<(C::Key, C::Value)> {
+        self.try_drain().unwrap_or_default()
+    }
+
+    /// Remove all the items like `drain`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_drain(&self) -> CollectionResult<Vec<(C::Key, C::Value)>> {
+        self.ensure_unlocked()?;
+        self.change_selected_key(None);
+        self.record(|_| CollectionOperation::Clear);
+        self.expire_all_generations();
+        let drained = self.inner.items().write().drain();
+        self.inner.selection_anchor().set(None);
+        self.clear_meta();
+        self.clear_focus();
+        self.finish_mutation("drain");
+        Ok(drained)
+    }
+
+    /// Get an iterator over the collection items
+    ///
+    /// Returns an iterator of `CollectionItem` references that implement both `Readable` and `Writable`.
+    /// This leverages Dioxus signals for efficient reactive access, allowing you to:
+    ///
+    /// - **Read values reactively**: `item.read()` creates a reactive dependency
+    /// - **Mutate values in-place**: `item.write()` provides mutable access
+    /// - **Combine with Rust iterators**: Use `filter()`, `map()`, `find()`, etc.
+    ///
+    /// Unlike traditional Rust collections that require separate `iter()` and `iter_mut()` methods,
+    /// **a single iterator provides both read and write capabilities** thanks to the signal system.
+    ///
+    /// The keys come from `Collection::key_iter`: a `Vec` generates its indices without collecting them, while
+    /// maps collect their keys when `iter()` is called. Either way the iterator doesn't borrow the collection,
+    /// so items can be written during the iteration, and items added meanwhile are not visited.
+    /// The iterator knows its length and can be reversed, see `iter_rev()` to render newest first.
+    ///
+    /// # Examples
+    ///
+    /// ## Read-only iteration
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3, 4, 5]);
+    ///
+    /// for item in store.iter() {
+    ///     println!("{}", item.read());
+    /// }
+    /// ```
+    ///
+    /// ## Combining filter and mutation in one pass
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3, 4, 5]);
+    ///
+    /// // Filter and mutate in a single iteration!
+    /// store.iter()
+    ///     .filter(|item| *item.read() > 2)
+    ///     .for_each(|item| {
+    ///         let current = *item.read();
+    ///         item.set(current * 10);
+    ///     });
+    /// // Result: [1, 2, 30, 40, 50]
+    /// ```
+    ///
+    /// ## Mixed read/write in same loop
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["task1", "urgent_task", "task2"]);
+    ///
+    /// for item in store.iter() {
+    ///     let value = item.read();
+    ///     if value.contains("urgent") {
+    ///         // Read-only: just log
+    ///         println!("Found urgent: {}", value);
+    ///     } else {
+    ///         // Mutate: add suffix
+    ///         item.write().push_str(" [processed]");
+    ///     }
+    /// }
+    /// ```
+    pub fn iter(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = CollectionItem<C>> + ExactSizeIterator + '_
+    where
+        C::Key: Clone,
+    {
+        let keys = self.inner.items().read().key_iter();
+        let store = *self;
+
+        keys.map(move |key| CollectionItem::new(store, key))
+    }
+
+    /// Get an iterator over the collection items, last item first
+    ///
+    /// Same as `iter().rev()`, for newest-first lists like a chat.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus::prelude::*;
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let messages = CollectionStore::new(vec!["hi", "hello", "how are you?"]);
+    /// let newest: Vec<&str> = messages.iter_rev().map(|item| *item.read()).collect();
+    /// assert_eq!(newest, vec!["how are you?", "hello", "hi"]);
+    /// ```
+    pub fn iter_rev(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = CollectionItem<C>> + ExactSizeIterator + '_
+    where
+        C::Key: Clone,
+    {
+        self.iter().rev()
+    }
+
+    /// Get the keys of the collection as a memo, notifying only when keys are added, removed or reordered
+    ///
+    /// `iter()` subscribes to the whole collection, so a list component re-renders when any value changes.
+    /// Iterate over this memo instead and let each row read its own item: changing a value then only
+    /// re-renders the row displaying it. Create it once (in `use_hook` for instance), like any memo.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let keys = use_hook(|| store.keys_memo());
+    /// rsx! {
+    ///     for key in keys.read().iter().cloned() {
+    ///         Row { key: "{key}", item: store.get(&key) }
+    ///     }
+    /// }
+    /// ```
+    pub fn keys_memo(&self) -> Memo<Vec<C::Key>> {
+        let items = self.inner.items();
+        Memo::new(move || items.read().keys())
+    }
+
+    /// Select an item by its key
+    ///
+    /// Fails with `CollectionError::NotSelectable` if the item is rejected by the `set_selectable` predicate,
+    /// or with `SelectionBlocked` or `SelectionPending` if a selection guard didn't allow the change yet (see
+    /// `set_selection_guard`). Like `get`, the key can be any borrowed form of `C::Key`: an owned key
+    /// is only built once the item is known to be there.
+    pub fn select<Q>(&self, key: &Q) -> CollectionResult<()>
+    where
+        C: BorrowedLookup<Q>,
+        C::Key: Borrow<Q>,
+        Q: ToOwned<Owned = C::Key> + ?Sized,
+    {
+        if !self.inner.items().peek().contains_borrowed(key) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        let key = &key.to_owned();
+        if !self.peek_is_selectable(key) {
+            return Err(CollectionError::NotSelectable);
+        }
+        let key = self.guard_selection(key)?;
+        self.select_unguarded(&key)
+    }
+
+    /// Select an item without asking the selection guard
+    pub(crate) fn select_unguarded(&self, key: &C::Key) -> CollectionResult<()> {
+        if self.peek_contains_key(key) {
+            if !self.peek_is_selectable(key) {
+                return Err(CollectionError::NotSelectable);
+            }
+            self.record(|_| CollectionOperation::Select { key: key.clone() });
+            self.change_selected_key(Some(key.clone()));
+            self.clear_selection_anchor();
+            self.touch(key);
+            self.finish_mutation("select");
+            Ok(())
+        } else {
+            Err(CollectionError::KeyNotFound)
+        }
+    }
+
+    /// Select an item by its value
+    ///
+    /// Recommended to use `Self::select` instead whenever possible.
+    /// Not recommended for performance reasons and values may not be unique.
+    /// If values are not unique, this method selects the first matching selectable item.
+    pub fn select_by_value(&self, value: &C::Value) -> CollectionResult<()>
+    where
+        C::Value: PartialEq,
+    {
+        let key = self.find_selectable(|candidate| candidate == value)?;
+        self.select(&key)
+    }
+
+    /// Select an item by its display string value
+    ///
+    /// Recommended to use `Self::select` instead whenever possible.
+    /// Not recommended for performance reasons and values may not be unique.
+    /// If displayed values are not unique, this method selects the first matching selectable item.
+    pub fn select_by_display(&self, display_value: &str) -> CollectionResult<()>
+    where
+        C::Value: std::fmt::Display,
+    {
+        let key = self.find_selectable(|candidate| candidate.to_string() == display_value)?;
+        self.select(&key)
+    }
+
+    /// Get the currently selected key
+    pub fn selected_key(&self) -> Option<C::Key> {
+        self.inner.selected_key().read().clone()
+    }
+
+    /// Get the currently selected key, without subscribing to the selection
+    pub fn peek_selected_key(&self) -> Option<C::Key> {
+        self.inner.selected_key().peek().clone()
+    }
+
+    /// Clear the selection
+    pub fn clear_selection(&self) {
+        self.record(|_| CollectionOperation::ClearSelection);
+        self.change_selected_key(None);
+        self.clear_selection_anchor();
+        self.finish_mutation("clear_selection");
+    }
+
+    /// Remove all items from the collection
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.clear();
+    /// assert!(store.is_empty());
+    /// ```
+    pub fn clear(&self) {
+        self.try_clear().ok();
+    }
+
+    /// Remove all items like `clear`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_clear(&self) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        self.change_selected_key(None);
+        self.record(|_| CollectionOperation::Clear);
+        self.expire_all_generations();
+        self.inner.items().write().clear();
+        self.inner.selection_anchor().set(None);
+        self.clear_meta();
+        self.clear_focus();
+        self.finish_mutation("clear");
+        Ok(())
+    }
+
+    /// Extend the collection with multiple key-value pairs
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::collections::HashMap;
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(HashMap::new());
+    /// store.extend(vec![
+    ///     ("key1".to_string(), 1),
+    ///     ("key2".to_string(), 2),
+    /// ]);
+    /// assert_eq!(store.len(), 2);
+    /// ```
+    pub fn extend<I: IntoIterator<Item = (C::Key, C::Value)>>(&self, items: I)
+    where
+        C::Value: Clone,
+    {
+        self.try_extend(items).ok();
+    }
+
+    /// Extend the collection like `extend`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_extend<I: IntoIterator<Item = (C::Key, C::Value)>>(
+        &self,
+        items: I,
+    ) -> CollectionResult<()>
+    where
+        C::Value: Clone,
+    {
+        self.ensure_unlocked()?;
+        let items: Vec<(C::Key, C::Value)> = items.into_iter().collect();
+        self.record(|clone| CollectionOperation::Extend {
+            items: items
+                .iter()
+                .map(|(key, value)| (key.clone(), clone(value)))
+                .collect(),
+        });
+        self.inner.items().write().extend(items);
+        self.finish_mutation("extend");
+        Ok(())
+    }
+
+    /// Move all the items of another store into this one, in a single write
+    ///
+    /// Sequential collections add the items at the end, keyed collections insert them under
+    /// their key, replacing existing values. The other store is left empty with no selection,
+    /// while the selection of this store is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let todo = CollectionStore::new(vec!["write docs", "fix bug"]);
+    /// let done = CollectionStore::new(vec!["release"]);
+    /// done.select(&0).ok();
+    /// done.append(&todo);
+    /// assert_eq!(done.len(), 3);
+    /// assert!(todo.is_empty());
+    /// assert_eq!(done.selected_key(), Some(0));
+    /// ```
+    pub fn append(&self, other: &CollectionStore<C>)
+    where
+        C::Value: Clone,
+    {
+        self.try_append(other).ok();
+    }
+
+    /// Move all the items of another store like `append`, failing with `CollectionError::Locked` while
+    /// either store is locked
+    pub fn try_append(&self, other: &CollectionStore<C>) -> CollectionResult<()>
+    where
+        C::Value: Clone,
+    {
+        self.ensure_unlocked()?;
+        other.ensure_unlocked()?;
+        if self.inner == other.inner {
+            return Ok(());
+        }
+        let keys = other.inner.items().peek().keys();
+        let values = other.try_remove_many(keys.clone())?;
+        self.append_entries(keys.into_iter().zip(values).collect());
+        Ok(())
+    }
+
+    /// Copy the items of another store matching `filter` into this one, in a single write
+    ///
+    /// Items are added like `append` does, but the other store is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let backlog = CollectionStore::new(vec![1, 2, 3, 4]);
+    /// let sprint = CollectionStore::new(vec![10]);
+    /// sprint.extend_from_store(&backlog, |_, value| value % 2 == 0);
+    /// assert_eq!(sprint.len(), 3); // [10, 2, 4]
+    /// assert_eq!(backlog.len(), 4);
+    /// ```
+    pub fn extend_from_store(
+        &self,
+        other: &CollectionStore<C>,
+        mut filter: impl FnMut(&C::Key, &C::Value) -> bool,
+    ) where
+        C::Value: Clone,
+    {
+        let entries: Vec<(C::Key, C::Value)> = {
+            let items = other.inner.items();
+            let items = items.peek();
+            items
+                .keys()
+                .into_iter()
+                .filter_map(|key| {
+                    let value = items.get(&key)?;
+                    filter(&key, value).then(|| (key.clone(), value.clone()))
+                })
+                .collect()
+        };
+        self.append_entries(entries);
+    }
+
+    /// Move the items matching `predicate` into another store, in one pass
+    ///
+    /// The items are removed from this store in a single write, like `remove_many`, then added to `other`
+    /// like `append` does. Returns the number of moved items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let active = CollectionStore::new(vec![("write docs", true), ("fix bug", false)]);
+    /// let archive = CollectionStore::new(Vec::new());
+    /// assert_eq!(active.partition_into(&archive, |_, (_, done)| *done), 1);
+    /// assert_eq!(archive.peek_value(&0), Some(("write docs", true)));
+    /// assert_eq!(active.len(), 1);
+    /// ```
+    pub fn partition_into(
+        &self,
+        other: &CollectionStore<C>,
+        mut predicate: impl FnMut(&C::Key, &C::Value) -> bool,
+    ) -> usize
+    where
+        C::Value: Clone,
+    {
+        if self.inner == other.inner
+            || self.ensure_unlocked().is_err()
+            || other.ensure_unlocked().is_err()
+        {
+            return 0;
+        }
+        let keys: Vec<C::Key> = {
+            let items = self.inner.items();
+            let items = items.peek();
+            items
+                .keys()
+                .into_iter()
+                .filter(|key| items.get(key).is_some_and(|value| predicate(key, value)))
+                .collect()
+        };
+        if keys.is_empty() {
+            return 0;
+        }
+        let values = self.remove_many(keys.clone());
+        let moved = values.len();
+        other.append_entries(keys.into_iter().zip(values).collect());
+        moved
+    }
+
+    /// Split the items into two new stores, those matching `predicate` and the others
+    ///
+    /// This store is left untouched. The new stores are owned by the current scope, like with `new`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let numbers = CollectionStore::new(vec![1, 2, 3, 4]);
+    /// let (even, odd) = numbers.partition(|_, value| value % 2 == 0);
+    /// assert_eq!(even.len(), 2);
+    /// assert_eq!(odd.peek_value(&1), Some(3));
+    /// ```
+    pub fn partition(
+        &self,
+        mut predicate: impl FnMut(&C::Key, &C::Value) -> bool,
+    ) -> (CollectionStore<C>, CollectionStore<C>)
+    where
+        C: Default,
+        C::Value: Clone,
+    {
+        let (matching, rest) = {
+            let items = self.inner.items();
+            let items = items.peek();
+            let mut matching = Vec::new();
+            let mut rest = Vec::new();
+            for key in items.keys() {
+                if let Some(value) = items.get(&key) {
+                    let entry = (key.clone(), value.clone());
+                    if predicate(&key, value) {
+                        matching.push(entry);
+                    } else {
+                        rest.push(entry);
+                    }
+                }
+            }
+            (matching, rest)
+        };
+        let [matching, rest] = [matching, rest].map(|entries| {
+            let mut collection = C::default();
+            collection.append_entries(entries);
+            CollectionStore::new(collection)
+        });
+        (matching, rest)
+    }
+
+    /// Add entries taken from another store in a single write
+    pub(crate) fn append_entries(&self, entries: Vec<(C::Key, C::Value)>)
+    where
+        C::Value: Clone,
+    {
+        if entries.is_empty() || self.ensure_unlocked().is_err() {
+            return;
+        }
+        self.record(|clone| CollectionOperation::AppendEntries {
+            items: entries
+                .iter()
+                .map(|(key, value)| (key.clone(), clone(value)))
+                .collect(),
+        });
+        self.inner.items().write().append_entries(entries);
+        self.finish_mutation("append_entries");
+    }
+}
+
+/// Conversion from `Store<CollectionData<C>>` to `CollectionStore<C>`
+///
+/// This allows you to use `.into()` for type conversion, which is more idiomatic
+/// than a custom `from_store()` method.
+///
+/// Call it in the scope that created the store: weak handles consider the store dropped with this scope.
 ///
 /// # Examples
 ///
@@ -487,7 +1528,10 @@ where
     C::Key: Clone + PartialEq,
 {
     fn from(store: Store<CollectionData<C>>) -> Self {
-        Self { inner: store }
+        Self {
+            inner: store,
+            alive: CopyValue::new(()),
+        }
     }
 }
 
@@ -498,15 +1542,63 @@ where
     C::Key: Clone + PartialEq,
 {
     /// Push a new item to the collection (for sequential collections)
+    ///
+    /// The new item is selected if the store's `SelectionBehavior` asks for it.
     pub fn push(&self, value: C::Value)
     where
         C::Value: Clone,
     {
-        self.inner.items().write().push(value);
+        self.try_push(value).ok();
+    }
+
+    /// Push a new item like `push`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_push(&self, value: C::Value) -> CollectionResult<()>
+    where
+        C::Value: Clone,
+    {
+        self.push_key(value).map(|_| ())
+    }
+
+    /// Push a new item, returning its key
+    fn push_key(&self, value: C::Value) -> CollectionResult<C::Key>
+    where
+        C::Value: Clone,
+    {
+        self.ensure_unlocked()?;
+        self.record(|clone| CollectionOperation::Push {
+            value: clone(&value),
+        });
+        let key = self.inner.items().write().push_keyed(value);
+        self.select_added(&key, Addition::Push);
+        self.track_added(&key);
+        self.finish_mutation("push");
+        Ok(key)
+    }
+
+    /// Push a new item and return it, to select or scroll to it
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2]);
+    /// let item = store.push_and_get(3).unwrap();
+    /// assert_eq!(item.key(), 2);
+    /// item.select().ok();
+    /// ```
+    pub fn push_and_get(&self, value: C::Value) -> CollectionResult<CollectionItem<C>>
+    where
+        C::Value: Clone,
+    {
+        let key = self.push_key(value)?;
+        Ok(self.get(&key))
     }
 
     /// Remove and return the last element
     ///
+    /// If the removed item was selected, the selection will be cleared.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -520,7 +1612,25 @@ where
     where
         C::Value: Clone,
     {
-        self.inner.items().write().pop()
+        self.try_pop().ok().flatten()
+    }
+
+    /// Remove the last element like `pop`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_pop(&self) -> CollectionResult<Option<C::Value>>
+    where
+        C::Value: Clone,
+    {
+        self.ensure_unlocked()?;
+        let last_key = self.inner.items().peek().last_key();
+        if let Some(last_key) = last_key {
+            self.deselect_removed(|selected| *selected == last_key);
+        }
+        self.record(|_| CollectionOperation::Pop);
+        let tracked_keys = self.tracked_keys();
+        let popped = self.inner.items().write().pop();
+        self.remap_tracked(tracked_keys, Some);
+        self.finish_mutation("pop");
+        Ok(popped)
     }
 
     /// Get a reference to the first element
@@ -536,18 +1646,9 @@ where
     /// let first = store.first();
     /// assert_eq!(first.map(|item| *item.read()), Some(1));
     /// ```
-    pub fn first(&self) -> Option<CollectionItem<C>>
-    where
-        C::Key: From<usize>,
-    {
-        if self.is_empty() {
-            None
-        } else {
-            Some(CollectionItem {
-                store: *self,
-                key: C::Key::from(0),
-            })
-        }
+    pub fn first(&self) -> Option<CollectionItem<C>> {
+        let key = self.inner.items().read().key_iter().next()?;
+        Some(CollectionItem::new(*self, key))
     }
 
     /// Get a reference to the last element
@@ -563,19 +1664,9 @@ where
     /// let last = store.last();
     /// assert_eq!(last.map(|item| *item.read()), Some(3));
     /// ```
-    pub fn last(&self) -> Option<CollectionItem<C>>
-    where
-        C::Key: From<usize>,
-    {
-        let len = self.len();
-        if len == 0 {
-            None
-        } else {
-            Some(CollectionItem {
-                store: *self,
-                key: C::Key::from(len - 1),
-            })
-        }
+    pub fn last(&self) -> Option<CollectionItem<C>> {
+        let key = self.inner.items().read().last_key()?;
+        Some(CollectionItem::new(*self, key))
     }
 
     /// Swap two elements by their keys
@@ -592,11 +1683,443 @@ where
     /// // Now the collection is [3, 2, 1]
     /// ```
     pub fn swap(&self, key1: &C::Key, key2: &C::Key) -> CollectionResult<()> {
-        if self.contains_key(key1) && self.contains_key(key2) {
+        self.ensure_unlocked()?;
+        if self.peek_contains_key(key1) && self.peek_contains_key(key2) {
+            self.record(|_| CollectionOperation::Swap {
+                key1: key1.clone(),
+                key2: key2.clone(),
+            });
+            let tracked_keys = self.tracked_keys();
+            let positions = tracked_keys.as_ref().and_then(|keys| {
+                Some((
+                    keys.iter().position(|k| k == key1)?,
+                    keys.iter().position(|k| k == key2)?,
+                ))
+            });
             self.inner.items().write().swap(key1, key2);
+            self.remap_tracked(tracked_keys, |position| match positions {
+                Some((first, second)) if position == first => Some(second),
+                Some((first, second)) if position == second => Some(first),
+                _ => Some(position),
+            });
+            self.finish_mutation("swap");
             Ok(())
         } else {
             Err(CollectionError::KeyNotFound)
         }
     }
+
+    /// Remove an item by replacing it with the last one, in O(1) for `Vec`
+    ///
+    /// Unlike `remove`, the following items don't shift: the last item moves to the removed key instead,
+    /// so the order of the collection changes. If the removed item was selected the selection is cleared,
+    /// and if the moved last item was selected the selection follows it to its new key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+    /// store.select(&3).ok();
+    /// assert_eq!(store.swap_remove(&1), Some("b"));
+    /// // Now the collection is ["a", "d", "c"] and "d" is still selected, at index 1
+    /// assert_eq!(store.selected_key(), Some(1));
+    /// ```
+    pub fn swap_remove(&self, key: &C::Key) -> Option<C::Value> {
+        self.try_swap_remove(key).ok().flatten()
+    }
+
+    /// Remove an item like `swap_remove`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_swap_remove(&self, key: &C::Key) -> CollectionResult<Option<C::Value>> {
+        self.ensure_unlocked()?;
+        let Some(last_key) = self.inner.items().peek().last_key() else {
+            return Ok(None);
+        };
+        if !self.peek_contains_key(key) {
+            return Ok(None);
+        }
+        self.deselect_removed(|selected| selected == key);
+        self.record(|_| CollectionOperation::SwapRemove { key: key.clone() });
+
+        let selected_key = self.peek_selected_key();
+        let tracked_keys = self.tracked_keys();
+        let removed_position = tracked_keys
+            .as_ref()
+            .and_then(|keys| keys.iter().position(|k| k == key));
+        let last_position = tracked_keys.as_ref().map(|keys| keys.len() - 1);
+        let removed = self.inner.items().write().swap_remove(key);
+        self.remap_tracked(tracked_keys, |position| match removed_position {
+            Some(removed) if position == removed => None,
+            Some(removed) if Some(position) == last_position => Some(removed),
+            _ => Some(position),
+        });
+        if last_key != *key && selected_key == Some(last_key) {
+            self.inner.selected_key().set(Some(key.clone()));
+        }
+        self.finish_mutation("swap_remove");
+        Ok(removed)
+    }
+
+    /// Sort the items with a comparator function
+    ///
+    /// Uses the collection's own `sort_by`, so custom collections can sort directly. The sort is stable,
+    /// and the selection follows the selected item to its new key.
+    ///
+    /// When the selection, focus or item metadata must follow the items, or a recording is in progress,
+    /// the new order is computed first: `compare` is then called again by the collection's sort.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![3, 1, 2]);
+    /// store.select(&0).ok();
+    /// store.sort_by(|a, b| a.cmp(b));
+    /// // Now the collection is [1, 2, 3] and 3 is still selected, at index 2
+    /// assert_eq!(store.selected_key(), Some(2));
+    /// ```
+    pub fn sort_by(&self, compare: impl FnMut(&C::Value, &C::Value) -> Ordering) {
+        self.try_sort_by(compare).ok();
+    }
+
+    /// Sort the items like `sort_by`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_sort_by(
+        &self,
+        mut compare: impl FnMut(&C::Value, &C::Value) -> Ordering,
+    ) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let tracked_keys = self.tracked_keys();
+        #[cfg(feature = "index")]
+        let indexed = self.index_clone_value().is_some();
+        #[cfg(not(feature = "index"))]
+        let indexed = false;
+        if tracked_keys.is_none()
+            && self.inner.selected_key().peek().is_none()
+            && self.inner.recorder().peek().is_none()
+            && !indexed
+        {
+            self.inner.items().write().sort_by(compare);
+            self.finish_mutation("sort_by");
+            return Ok(());
+        }
+
+        let (old_keys, order) = {
+            let items = self.inner.items();
+            let items = items.peek();
+            let old_keys = items.keys();
+            let mut order: Vec<usize> = (0..old_keys.len()).collect();
+            order.sort_by(
+                |a, b| match (items.get(&old_keys[*a]), items.get(&old_keys[*b])) {
+                    (Some(a), Some(b)) => compare(a, b),
+                    _ => Ordering::Equal,
+                },
+            );
+            (old_keys, order)
+        };
+        self.record(|_| CollectionOperation::Reorder {
+            order: order
+                .iter()
+                .map(|position| old_keys[*position].clone())
+                .collect(),
+        });
+        self.inner.items().write().sort_by(&mut compare);
+        self.follow_order(&old_keys, tracked_keys, &order);
+        self.finish_mutation("sort_by");
+        Ok(())
+    }
+
+    /// Move an item to the position of another one, shifting the items in between
+    ///
+    /// This is the usual drag & drop reordering: the selection follows the selected item to its new key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+    /// store.select(&0).ok();
+    /// store.move_item(&0, &2).ok();
+    /// // Now the collection is ["b", "c", "a", "d"] and "a" is still selected, at index 2
+    /// assert_eq!(store.selected_key(), Some(2));
+    /// ```
+    pub fn move_item(&self, from: &C::Key, to: &C::Key) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let old_keys = self.inner.items().peek().keys();
+        let (Some(from_position), Some(to_position)) = (
+            old_keys.iter().position(|k| k == from),
+            old_keys.iter().position(|k| k == to),
+        ) else {
+            return Err(CollectionError::KeyNotFound);
+        };
+        self.record(|_| CollectionOperation::MoveItem {
+            from: from.clone(),
+            to: to.clone(),
+        });
+
+        let tracked_keys = self.tracked_keys();
+        self.inner.items().write().move_item(from, to);
+        let new_position = |position: usize| {
+            Some(if position == from_position {
+                to_position
+            } else if from_position < position && position <= to_position {
+                position - 1
+            } else if to_position <= position && position < from_position {
+                position + 1
+            } else {
+                position
+            })
+        };
+        self.remap_selection(&old_keys, &new_position);
+        self.remap_tracked(tracked_keys, new_position);
+        self.finish_mutation("move_item");
+        Ok(())
+    }
+
+    /// Move the first `n` items to the end, in a single write
+    ///
+    /// `n` wraps around the length of the collection. The selection follows the selected item to its new key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let slides = CollectionStore::new(vec!["a", "b", "c", "d"]);
+    /// slides.select(&1).ok();
+    /// slides.rotate_left(1);
+    /// // Now the collection is ["b", "c", "d", "a"] and "b" is still selected, at index 0
+    /// assert_eq!(slides.selected_key(), Some(0));
+    /// ```
+    pub fn rotate_left(&self, n: usize) {
+        self.try_rotate_left(n).ok();
+    }
+
+    /// Move the last `n` items to the start, in a single write, like `rotate_left`
+    pub fn rotate_right(&self, n: usize) {
+        self.try_rotate_right(n).ok();
+    }
+
+    /// Rotate the items like `rotate_left`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_rotate_left(&self, n: usize) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let old_keys = self.inner.items().peek().keys();
+        let len = old_keys.len();
+        if len == 0 || n.is_multiple_of(len) {
+            return Ok(());
+        }
+        let mut order: Vec<usize> = (0..len).collect();
+        order.rotate_left(n % len);
+        self.permute(&old_keys, &order);
+        Ok(())
+    }
+
+    /// Rotate the items like `rotate_right`, failing with `CollectionError::Locked` while the store is locked
+    pub fn try_rotate_right(&self, n: usize) -> CollectionResult<()> {
+        let len = self.peek_len();
+        if len == 0 {
+            return self.ensure_unlocked();
+        }
+        self.try_rotate_left(len - n % len)
+    }
+
+    /// Put the items in the order of `order`, the keys of all the items in their new order
+    ///
+    /// Replays the `Reorder` operations recorded by `sort_by`.
+    pub(crate) fn reorder(&self, order: &[C::Key]) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let old_keys = self.inner.items().peek().keys();
+        let order: Vec<usize> = order
+            .iter()
+            .map(|key| old_keys.iter().position(|k| k == key))
+            .collect::<Option<_>>()
+            .ok_or(CollectionError::KeyNotFound)?;
+        let mut unique = order.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        if unique.len() != old_keys.len() {
+            return Err(CollectionError::InvalidAccess {
+                reason: "reorder must list every key once".to_string(),
+            });
+        }
+        self.permute(&old_keys, &order);
+        Ok(())
+    }
+
+    /// Put the items in `order`, their old positions in their new order, in a single write
+    ///
+    /// `order` must list every position once. Recorded as a `Reorder`.
+    pub(crate) fn permute(&self, old_keys: &[C::Key], order: &[usize]) {
+        self.record(|_| CollectionOperation::Reorder {
+            order: order
+                .iter()
+                .map(|position| old_keys[*position].clone())
+                .collect(),
+        });
+
+        let tracked_keys = self.tracked_keys();
+        {
+            let mut items_lens = self.inner.items();
+            let mut items = items_lens.write();
+            // The old position of the item at each position, and the position of each old item
+            let mut current: Vec<usize> = (0..old_keys.len()).collect();
+            let mut found_at: Vec<usize> = current.clone();
+            for (position, wanted) in order.iter().enumerate() {
+                let found = found_at[*wanted];
+                if found != position {
+                    items.swap(&old_keys[position], &old_keys[found]);
+                    let displaced = current[position];
+                    current.as_mut_slice().swap(position, found);
+                    found_at[displaced] = found;
+                    found_at[*wanted] = position;
+                }
+            }
+        }
+        self.follow_order(old_keys, tracked_keys, order);
+        self.finish_mutation("reorder");
+    }
+
+    /// Move the selection and per-item state after the items were put in `order`, their old positions in their new order
+    fn follow_order(
+        &self,
+        old_keys: &[C::Key],
+        tracked_keys: Option<Vec<C::Key>>,
+        order: &[usize],
+    ) {
+        let mut new_positions = vec![0; order.len()];
+        for (new_position, old_position) in order.iter().enumerate() {
+            new_positions[*old_position] = new_position;
+        }
+        let new_position = |position: usize| new_positions.as_slice().get(position).copied();
+        self.remap_selection(old_keys, &new_position);
+        self.remap_tracked(tracked_keys, new_position);
+    }
+
+    /// Insert a value right before the item with the given key
+    ///
+    /// Returns the key of the new item. Following items shift back by one,
+    /// and the selection follows the selected item to its new key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 3]);
+    /// let key = store.insert_before(&1, 2).unwrap();
+    /// assert_eq!(key, 1);
+    /// // Now the collection is [1, 2, 3]
+    /// ```
+    pub fn insert_before(&self, key: &C::Key, value: C::Value) -> CollectionResult<C::Key> {
+        self.ensure_unlocked()?;
+        if self.peek_contains_key(key) {
+            self.record(|clone| CollectionOperation::InsertBefore {
+                key: key.clone(),
+                value: clone(&value),
+            });
+        }
+        let new_key = self.insert_relative(key, 0, value)?;
+        self.select_added(&new_key, Addition::Insert);
+        self.track_added(&new_key);
+        self.finish_mutation("insert_before");
+        Ok(new_key)
+    }
+
+    /// Insert a value right after the item with the given key
+    ///
+    /// Returns the key of the new item. Following items shift back by one,
+    /// and the selection follows the selected item to its new key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 3]);
+    /// let key = store.insert_after(&0, 2).unwrap();
+    /// assert_eq!(key, 1);
+    /// // Now the collection is [1, 2, 3]
+    /// ```
+    pub fn insert_after(&self, key: &C::Key, value: C::Value) -> CollectionResult<C::Key> {
+        self.ensure_unlocked()?;
+        if self.peek_contains_key(key) {
+            self.record(|clone| CollectionOperation::InsertAfter {
+                key: key.clone(),
+                value: clone(&value),
+            });
+        }
+        let new_key = self.insert_relative(key, 1, value)?;
+        self.select_added(&new_key, Addition::Insert);
+        self.track_added(&new_key);
+        self.finish_mutation("insert_after");
+        Ok(new_key)
+    }
+
+    /// Clone an item and insert the copy right after it
+    ///
+    /// Returns the key of the copy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b"]);
+    /// let key = store.duplicate(&0).unwrap();
+    /// assert_eq!(key, 1);
+    /// // Now the collection is ["a", "a", "b"]
+    /// ```
+    pub fn duplicate(&self, key: &C::Key) -> CollectionResult<C::Key>
+    where
+        C::Value: Clone,
+    {
+        let value = self.peek_value(key).ok_or(CollectionError::KeyNotFound)?;
+        self.insert_after(key, value)
+    }
+
+    /// Insert a value `offset` positions after the item with the given key
+    fn insert_relative(
+        &self,
+        key: &C::Key,
+        offset: usize,
+        value: C::Value,
+    ) -> CollectionResult<C::Key> {
+        // Resolve the anchor before writing, so a missing key doesn't notify the subscribers
+        let old_keys = self.inner.items().peek().keys();
+        let position = old_keys
+            .iter()
+            .position(|k| k == key)
+            .ok_or(CollectionError::KeyNotFound)?
+            + offset;
+        let selected_position = self
+            .peek_selected_key()
+            .and_then(|selected| old_keys.iter().position(|k| *k == selected));
+        let tracked_keys = self.tracked_keys();
+        let (new_key, moved_selection) = {
+            let mut items_lens = self.inner.items();
+            let mut items = items_lens.write();
+            match old_keys.as_slice().get(position) {
+                Some(next_key) => {
+                    items.insert_at(next_key, value);
+                }
+                None => items.push(value),
+            }
+
+            let new_keys = items.keys();
+            let moved_selection = selected_position
+                .filter(|selected| *selected >= position)
+                .and_then(|selected| new_keys.as_slice().get(selected + 1).cloned());
+            (new_keys[position].clone(), moved_selection)
+        };
+        self.remap_tracked(tracked_keys, |old_position| {
+            Some(old_position + usize::from(old_position >= position))
+        });
+
+        if let Some(selected) = moved_selection {
+            self.inner.selected_key().set(Some(selected));
+        }
+        Ok(new_key)
+    }
 }
@@ -1,3 +1,12 @@
+use std::cmp::Ordering;
+
+/// Iterator over the keys of a collection, returned by `Collection::key_iter`
+///
+/// It knows its length and can be reversed, like the iterator of `CollectionStore::iter`.
+pub trait KeyIter: DoubleEndedIterator + ExactSizeIterator {}
+
+impl<I> KeyIter for I where I: DoubleEndedIterator + ExactSizeIterator {}
+
 /// Base trait for all collections
 ///
 /// This trait provides a unified interface for different collection types
@@ -49,6 +58,12 @@ pub trait Collection {
     type Key: Clone;
     type Value;
 
+    /// Whether keys are positions (like `Vec` indices) that shift when items are inserted or removed
+    ///
+    /// Stores use this to keep per-item state, like metadata, attached to the items rather than to the keys.
+    /// Sequential collections indexed by position should set it to `true`.
+    const POSITIONAL_KEYS: bool = false;
+
     /// Get a reference to the value associated with the given key (or index for SequentialCollection)
     ///
     /// # Examples
@@ -128,12 +143,132 @@ pub trait Collection {
         self.get(key).is_some()
     }
 
+    /// Find the key of the first value matching the predicate
+    ///
+    /// The default implementation walks `keys()` and looks up every value.
+    /// Implementors should override it when they can scan their values directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::Collection;
+    ///
+    /// let vec = vec![10, 20, 30];
+    /// assert_eq!(vec.find_key(|value| *value > 15), Some(1));
+    /// assert_eq!(vec.find_key(|value| *value > 50), None);
+    /// ```
+    fn find_key<F>(&self, mut predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.keys()
+            .into_iter()
+            .find(|key| self.get(key).is_some_and(&mut predicate))
+    }
+
+    /// Find the key of the first value equal to `value`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::Collection;
+    ///
+    /// let vec = vec!["a", "b", "c"];
+    /// assert_eq!(vec.position_of(&"b"), Some(1));
+    /// assert_eq!(vec.position_of(&"z"), None);
+    /// ```
+    fn position_of(&self, value: &Self::Value) -> Option<Self::Key>
+    where
+        Self::Value: PartialEq,
+    {
+        self.find_key(|candidate| candidate == value)
+    }
+
+    /// Check if a value exists in the collection
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::Collection;
+    ///
+    /// let vec = vec![1, 2, 3];
+    /// assert!(vec.contains_value(&2));
+    /// assert!(!vec.contains_value(&4));
+    /// ```
+    fn contains_value(&self, value: &Self::Value) -> bool
+    where
+        Self::Value: PartialEq,
+    {
+        self.position_of(value).is_some()
+    }
+
     /// Get an iterator over all keys in the collection
     ///
     /// This is a required method that each collection must implement
     /// to provide iteration support.
     fn keys(&self) -> Vec<Self::Key>;
 
+    /// Get an iterator over all keys, in `keys()` order, that doesn't borrow the collection
+    ///
+    /// Not borrowing the collection lets items be written while walking the keys, so the keys of a map
+    /// can't be borrowed from it: the default implementation collects `keys()`. Collections whose keys
+    /// can be generated, like the indices of a `Vec`, should override it to avoid collecting them.
+    /// The iterator is boxed either way, so this costs one small allocation instead of one per key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::Collection;
+    ///
+    /// let vec = vec!["a", "b", "c"];
+    /// assert_eq!(vec.key_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+    /// ```
+    fn key_iter(&self) -> Box<dyn KeyIter<Item = Self::Key>>
+    where
+        Self::Key: 'static,
+    {
+        Box::new(self.keys().into_iter())
+    }
+
+    /// Get the last key, in `keys()` order
+    ///
+    /// The default implementation collects `keys()`. Collections that know their last key,
+    /// like the last index of a `Vec` or the greatest key of a `BTreeMap`, should override it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::Collection;
+    ///
+    /// let vec = vec!["a", "b", "c"];
+    /// assert_eq!(vec.last_key(), Some(2));
+    /// ```
+    fn last_key(&self) -> Option<Self::Key> {
+        self.keys().pop()
+    }
+
+    /// Get the position of `key` in `keys()` order, or `None` if it isn't in the collection
+    ///
+    /// The default implementation collects `keys()`. Collections should override it to find the position
+    /// directly, like the index of a `Vec` which is its own position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::Collection;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([("b", 2), ("a", 1), ("c", 3)]);
+    /// assert_eq!(map.key_position(&"b"), Some(1));
+    /// assert_eq!(map.key_position(&"z"), None);
+    /// ```
+    fn key_position(&self, key: &Self::Key) -> Option<usize>
+    where
+        Self::Key: PartialEq,
+    {
+        self.keys().iter().position(|k| k == key)
+    }
+
     /// Get the number of elements in the collection
     ///
     /// # Examples
@@ -193,6 +328,121 @@ pub trait Collection {
             self.insert(key, value);
         }
     }
+
+    /// Add entries taken from another collection of the same type
+    ///
+    /// Keyed collections insert each value under its key, replacing existing values.
+    /// Sequential collections should override this to add the values at the end instead,
+    /// since the source keys are positions in the other collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::Collection;
+    ///
+    /// let mut vec = vec![1, 2];
+    /// Collection::append_entries(&mut vec, vec![(0, 3), (1, 4)]);
+    /// assert_eq!(vec, vec![1, 2, 3, 4]);
+    /// ```
+    fn append_entries<I: IntoIterator<Item = (Self::Key, Self::Value)>>(&mut self, entries: I) {
+        for (key, value) in entries {
+            self.insert(key, value);
+        }
+    }
+
+    /// Keep only the items for which `keep` returns `true`
+    ///
+    /// Items must be visited in `keys()` order: stores rely on it to know which items were removed.
+    /// The default implementation removes the rejected items one by one, from the last to the first,
+    /// so implementors should override it when they can filter in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::Collection;
+    ///
+    /// let mut vec = vec![1, 2, 3, 4];
+    /// Collection::retain(&mut vec, |_, value| value % 2 == 0);
+    /// assert_eq!(vec, vec![2, 4]);
+    /// ```
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        let rejected: Vec<Self::Key> = self
+            .keys()
+            .into_iter()
+            .filter(|key| self.get(key).is_some_and(|value| !keep(key, value)))
+            .collect();
+        for key in rejected.iter().rev() {
+            self.remove(key);
+        }
+    }
+
+    /// Remove all the items, returning them with their keys in `keys()` order
+    ///
+    /// The default implementation removes the items one by one, from the last to the first,
+    /// so implementors should override it when they can take their items at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::Collection;
+    ///
+    /// let mut vec = vec!["a", "b"];
+    /// assert_eq!(Collection::drain(&mut vec), vec![(0, "a"), (1, "b")]);
+    /// assert!(vec.is_empty());
+    /// ```
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        let mut drained: Vec<(Self::Key, Self::Value)> = self
+            .keys()
+            .into_iter()
+            .rev()
+            .filter_map(|key| {
+                let value = self.remove(&key)?;
+                Some((key, value))
+            })
+            .collect();
+        drained.reverse();
+        drained
+    }
+}
+
+/// Trait for collections that can look values up by a borrowed form of their keys
+///
+/// Like `HashMap::get` accepting a `&str` for `String` keys, this avoids building an owned key
+/// for one-off lookups in render paths. It is a separate trait because the bounds on the borrowed form
+/// depend on the collection (`Hash + Eq` for `HashMap`, `Ord` for `BTreeMap`).
+///
+/// Every collection looks its own key type up through the blanket implementation, so the store's
+/// lookups accept `&C::Key` whatever the collection. Maps additionally implement it for the usual
+/// borrowed forms of their keys (`str` for `String`, `[T]` for `Vec<T>`, `Path` for `PathBuf`).
+///
+/// # Examples
+///
+/// ```
+/// use dioxus_collection_store::BorrowedLookup;
+/// use std::collections::HashMap;
+///
+/// let mut map = HashMap::new();
+/// map.insert("key".to_string(), 42);
+/// assert_eq!(map.get_borrowed("key"), Some(&42));
+/// assert!(!map.contains_borrowed("other"));
+/// ```
+pub trait BorrowedLookup<Q: ?Sized>: Collection {
+    /// Get a reference to the value whose key borrows as `key`
+    fn get_borrowed(&self, key: &Q) -> Option<&Self::Value>;
+
+    /// Check if a key borrowing as `key` exists in the collection
+    fn contains_borrowed(&self, key: &Q) -> bool {
+        self.get_borrowed(key).is_some()
+    }
+}
+
+impl<C: Collection> BorrowedLookup<C::Key> for C {
+    fn get_borrowed(&self, key: &C::Key) -> Option<&Self::Value> {
+        self.get(key)
+    }
 }
 
 /// Trait for sequential collections (that support push operations)
@@ -225,6 +475,25 @@ pub trait SequentialCollection: Collection {
     /// ```
     fn push(&mut self, value: Self::Value);
 
+    /// Add an element to the end of the collection, returning its key
+    ///
+    /// The default implementation reads `last_key` after `push`. Override it if the collection
+    /// knows the new key without looking it up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::SequentialCollection;
+    ///
+    /// let mut vec = vec![1, 2];
+    /// assert_eq!(vec.push_keyed(3), 2);
+    /// ```
+    fn push_keyed(&mut self, value: Self::Value) -> Self::Key {
+        self.push(value);
+        self.last_key()
+            .expect("the collection has at least the pushed item")
+    }
+
     /// Remove and return the last element
     ///
     /// # Examples
@@ -262,6 +531,138 @@ pub trait SequentialCollection: Collection {
     /// assert_eq!(vec, vec![3, 2, 1]);
     /// ```
     fn swap(&mut self, key1: &Self::Key, key2: &Self::Key);
+
+    /// Insert an element at the position of `key`, shifting that element and all following ones back
+    ///
+    /// Returns `false` if `key` is not in the collection.
+    /// The default implementation pushes the value and swaps it back into place,
+    /// so implementors should override it when they can insert directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::SequentialCollection;
+    ///
+    /// let mut vec = vec![1, 3];
+    /// assert!(vec.insert_at(&1, 2));
+    /// assert_eq!(vec, vec![1, 2, 3]);
+    /// ```
+    fn insert_at(&mut self, key: &Self::Key, value: Self::Value) -> bool
+    where
+        Self::Key: PartialEq,
+    {
+        let Some(position) = self.keys().iter().position(|k| k == key) else {
+            return false;
+        };
+        self.push(value);
+        let keys = self.keys();
+        for index in (position + 1..keys.len()).rev() {
+            self.swap(&keys[index], &keys[index - 1]);
+        }
+        true
+    }
+
+    /// Remove the element at `key` and replace it with the last element
+    ///
+    /// This doesn't preserve ordering, but doesn't shift the following elements either.
+    /// Returns `None` if `key` is not in the collection.
+    /// The default implementation swaps the element with the last one and pops it,
+    /// so implementors should override it when they can remove directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::SequentialCollection;
+    ///
+    /// let mut vec = vec![1, 2, 3, 4];
+    /// assert_eq!(SequentialCollection::swap_remove(&mut vec, &1), Some(2));
+    /// assert_eq!(vec, vec![1, 4, 3]);
+    /// ```
+    fn swap_remove(&mut self, key: &Self::Key) -> Option<Self::Value>
+    where
+        Self::Key: PartialEq,
+    {
+        let keys = self.keys();
+        let last = keys.last()?;
+        if !keys.contains(key) {
+            return None;
+        }
+        self.swap(key, last);
+        self.pop()
+    }
+
+    /// Sort the elements with a comparator function
+    ///
+    /// The sort must be stable: stores rely on it to know where each element went.
+    /// The default implementation is an insertion sort made of `swap` calls,
+    /// so implementors should override it when they can sort directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::SequentialCollection;
+    ///
+    /// let mut vec = vec![3, 1, 2];
+    /// SequentialCollection::sort_by(&mut vec, |a, b| a.cmp(b));
+    /// assert_eq!(vec, vec![1, 2, 3]);
+    /// ```
+    fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&Self::Value, &Self::Value) -> Ordering,
+    {
+        let keys = self.keys();
+        for index in 1..keys.len() {
+            for current in (1..=index).rev() {
+                let (Some(previous), Some(value)) =
+                    (self.get(&keys[current - 1]), self.get(&keys[current]))
+                else {
+                    break;
+                };
+                if compare(previous, value) != Ordering::Greater {
+                    break;
+                }
+                self.swap(&keys[current - 1], &keys[current]);
+            }
+        }
+    }
+
+    /// Move the element at `from` to the position of `to`, shifting the elements in between
+    ///
+    /// Returns `false` if either key is not in the collection.
+    /// The default implementation moves the element with successive `swap` calls,
+    /// so implementors should override it when they can move directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dioxus_collection_store::SequentialCollection;
+    ///
+    /// let mut vec = vec!["a", "b", "c", "d"];
+    /// assert!(vec.move_item(&0, &2));
+    /// assert_eq!(vec, vec!["b", "c", "a", "d"]);
+    /// ```
+    fn move_item(&mut self, from: &Self::Key, to: &Self::Key) -> bool
+    where
+        Self::Key: PartialEq,
+    {
+        let keys = self.keys();
+        let (Some(from), Some(to)) = (
+            keys.iter().position(|k| k == from),
+            keys.iter().position(|k| k == to),
+        ) else {
+            return false;
+        };
+        if from < to {
+            for index in from..to {
+                self.swap(&keys[index], &keys[index + 1]);
+            }
+        } else {
+            for index in (to..from).rev() {
+                self.swap(&keys[index + 1], &keys[index]);
+            }
+        }
+        true
+    }
 }
 
 #[cfg(test)]
@@ -292,6 +693,7 @@ mod tests {
         // Test keys
         let keys = Collection::keys(&vec);
         assert_eq!(keys.len(), 2);
+        assert_eq!(Collection::key_iter(&vec).collect::<Vec<_>>(), keys);
     }
 
     #[test]
@@ -318,12 +720,63 @@ mod tests {
         SequentialCollection::swap(&mut vec, &0, &1);
         assert_eq!(vec, vec![2, 1]);
 
+        // Test insert_at
+        assert!(SequentialCollection::insert_at(&mut vec, &1, 5));
+        assert_eq!(vec, vec![2, 5, 1]);
+        assert!(!SequentialCollection::insert_at(&mut vec, &3, 6)); // Out of bounds
+
+        // Test swap_remove
+        SequentialCollection::push(&mut vec, 7);
+        assert_eq!(SequentialCollection::swap_remove(&mut vec, &0), Some(2));
+        assert_eq!(vec, vec![7, 5, 1]);
+        assert_eq!(SequentialCollection::swap_remove(&mut vec, &3), None); // Out of bounds
+
+        // Test append_entries ignores the source keys
+        Collection::append_entries(&mut vec, vec![(0, 8), (0, 9)]);
+        assert_eq!(vec, vec![7, 5, 1, 8, 9]);
+
+        // Test sort_by and move_item
+        SequentialCollection::sort_by(&mut vec, |a, b| a.cmp(b));
+        assert_eq!(vec, vec![1, 5, 7, 8, 9]);
+        assert!(SequentialCollection::move_item(&mut vec, &4, &1));
+        assert_eq!(vec, vec![1, 9, 5, 7, 8]);
+        assert!(!SequentialCollection::move_item(&mut vec, &0, &5)); // Out of bounds
+
+        // Test retain
+        Collection::retain(&mut vec, |key, value| *key == 0 || value % 2 == 1);
+        assert_eq!(vec, vec![1, 9, 5, 7]);
+
         // Test clear
         Collection::clear(&mut vec);
         assert!(Collection::is_empty(&vec));
         assert_eq!(SequentialCollection::first(&vec), None);
     }
 
+    #[test]
+    fn test_value_lookups() {
+        let vec = vec![10, 20, 30, 20];
+        assert_eq!(Collection::find_key(&vec, |v| *v >= 20), Some(1));
+        assert_eq!(Collection::position_of(&vec, &20), Some(1));
+        assert_eq!(Collection::position_of(&vec, &99), None);
+        assert!(Collection::contains_value(&vec, &30));
+        assert!(!Collection::contains_value(&vec, &99));
+
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), 1);
+        map.insert("b".to_string(), 2);
+        assert_eq!(
+            Collection::find_key(&map, |v| *v == 2),
+            Some("b".to_string())
+        );
+        assert_eq!(Collection::position_of(&map, &1), Some("a".to_string()));
+        assert!(!Collection::contains_value(&map, &3));
+
+        let tree: std::collections::BTreeMap<_, _> =
+            [(1, "x"), (2, "y"), (3, "y")].into_iter().collect();
+        // BTreeMap yields the first match in key order
+        assert_eq!(Collection::position_of(&tree, &"y"), Some(2));
+    }
+
     #[test]
     fn test_hashmap_collection() {
         let mut map = HashMap::new();
@@ -354,6 +807,7 @@ mod tests {
         // Test keys
         let keys = Collection::keys(&map);
         assert_eq!(keys.len(), 4); // b, c, d, e
+        assert_eq!(Collection::key_iter(&map).collect::<Vec<_>>(), keys);
 
         // Test clear
         Collection::clear(&mut map);
@@ -391,6 +845,7 @@ mod tests {
         // Test keys (BTreeMap keeps keys sorted)
         let keys = Collection::keys(&map);
         assert_eq!(keys.len(), 4); // b, c, d, e
+        assert_eq!(Collection::key_iter(&map).collect::<Vec<_>>(), keys);
         assert_eq!(
             keys,
             vec![
@@ -0,0 +1,35 @@
+//! Deprecated APIs kept for one release
+//!
+//! When a public API is renamed or replaced, the old name is moved here with a `#[deprecated]`
+//! note pointing to its replacement, so downstream apps get a warning instead of a build failure.
+//! Everything in this module is behind the `compat` feature, enabled by default: disable it
+//! (`default-features = false`) to turn the remaining usages of old APIs into errors before
+//! they're removed.
+//!
+//! | Deprecated | Replacement |
+//! |------------|-------------|
+//! | `store.peek(&key)` | `store.peek_value(&key)` or `store.get(&key).peek()` |
+
+use crate::{Collection, CollectionStore};
+use dioxus_signals::*;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "compat")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get a ReadSignal for a value in the collection by key
+    ///
+    /// Despite its name, the returned signal subscribes when `.read()` is called on it,
+    /// exactly like `store.read(&key)`.
+    #[deprecated(
+        note = "`store.peek(&key)` returns the same subscribing ReadSignal as `store.read(&key)`; use `store.peek_value(&key)` or `store.get(&key).peek()` to read a value without subscribing"
+    )]
+    pub fn peek(&self, key: &C::Key) -> ReadSignal<C::Value>
+    where
+        C::Value: 'static,
+    {
+        self.get(key).boxed()
+    }
+}
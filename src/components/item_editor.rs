@@ -0,0 +1,40 @@
+use crate::{Collection, CollectionItem};
+use dioxus::prelude::*;
+
+/// Input editing the value of an item, for any value type parsed with `FromStr`
+///
+/// The value is parsed and saved on change. While the input doesn't parse, `invalid_class` is added to it.
+#[component]
+#[allow(non_snake_case)]
+pub fn ItemEditor<C>(
+    item: CollectionItem<C>,
+    #[props(default)] class: String,
+    #[props(default = "invalid".to_string())] invalid_class: String,
+) -> Element
+where
+    C: Collection + Clone + PartialEq + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: std::fmt::Display + std::str::FromStr + Clone + PartialEq + 'static,
+{
+    let mut invalid = use_signal(|| false);
+    let class = if invalid() {
+        format!("{class} {invalid_class}")
+    } else {
+        class
+    };
+    let value = item.read().to_string();
+
+    rsx! {
+        input {
+            class: "{class}",
+            value: value,
+            onchange: move |evt| match evt.value().parse::<C::Value>() {
+                Ok(parsed) => {
+                    invalid.set(false);
+                    item.set(parsed);
+                }
+                Err(_) => invalid.set(true),
+            },
+        }
+    }
+}
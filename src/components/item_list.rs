@@ -1,38 +1,41 @@
-/// Generic ItemList component - Displays a list of items with inline editing
-/// Works with any collection (Vec, HashMap, BTreeMap, CircularBuffer, etc.)
+use crate::{Collection, CollectionStore};
 use dioxus::prelude::*;
-use dioxus_collection_store::{Collection, CollectionStore};
 
+/// List of the collection items with inline editing and removal
+///
+/// Each value is edited in a text input, parsed with `FromStr` on input: values that don't parse are ignored.
 #[component]
 #[allow(non_snake_case)]
 pub fn ItemList<C>(
     collection: CollectionStore<C>,
     #[props(default = "No items yet!".to_string())] empty_message: String,
     #[props(default = false)] show_key_label: bool,
+    #[props(default)] class: String,
+    #[props(default)] item_class: String,
+    #[props(default = "selected".to_string())] selected_class: String,
 ) -> Element
 where
     C: Collection + Clone + PartialEq + 'static,
     C::Key: std::fmt::Display + Clone + PartialEq,
     C::Value: std::fmt::Display + std::str::FromStr + Clone + PartialEq + 'static,
-    <C::Value as std::str::FromStr>::Err: std::fmt::Debug,
 {
     rsx! {
         if collection.is_empty() {
-            p { "{empty_message}" }
+            p { class: "{class}", "{empty_message}" }
         } else {
-            ul { style: "list-style: none; padding: 0;",
+            ul { class: "{class}",
                 for item in collection.iter() {
                     {
                         let key = item.key();
-                        let value = item.read();
-                        let is_selected = item.is_selected();
+                        let value = item.read().to_string();
+                        let item_class = if item.is_selected() {
+                            format!("{item_class} {selected_class}")
+                        } else {
+                            item_class.clone()
+                        };
                         let item_for_edit = item.clone();
-                        let item_for_remove = item.clone();
-                        let border = if is_selected { "2px solid #000" } else { "1px solid #ccc" };
                         rsx! {
-                            li {
-                                key: "{key}",
-                                style: "display: flex; gap: 10px; padding: 5px; border: {border};",
+                            li { key: "{key}", class: "{item_class}",
                                 if show_key_label {
                                     span { "{key}:" }
                                 } else {
@@ -48,7 +51,7 @@ where
                                 }
                                 button {
                                     onclick: move |_| {
-                                        item_for_remove.remove();
+                                        item.remove();
                                     },
                                     "Remove"
                                 }
@@ -0,0 +1,14 @@
+//! Generic UI components over `CollectionStore`
+//!
+//! Ready-made building blocks for the most common collection UIs, generic over any `Collection`.
+//! They render plain HTML without inline styles: every component takes `class` props to theme it.
+
+mod item_editor;
+mod item_list;
+mod selected_editor;
+mod selector;
+
+pub use item_editor::ItemEditor;
+pub use item_list::ItemList;
+pub use selected_editor::SelectedEditor;
+pub use selector::Selector;
@@ -0,0 +1,40 @@
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus::prelude::*;
+
+/// Renders an editor for the selected item, or `fallback` when nothing is selected
+///
+/// `render` receives the selected item. If the selection goes away while it's being edited (removed item,
+/// selected key no longer in the collection), the fallback is rendered instead of reading a missing value.
+///
+/// ```rust,ignore
+/// SelectedEditor {
+///     collection: tasks,
+///     render: move |item| rsx! { ItemEditor { item } },
+///     fallback: rsx! { p { "Select a task to edit it" } },
+/// }
+/// ```
+#[component]
+#[allow(non_snake_case)]
+pub fn SelectedEditor<C>(
+    collection: CollectionStore<C>,
+    render: Callback<CollectionItem<C>, Element>,
+    #[props(default = VNode::empty())] fallback: Element,
+    #[props(default)] class: String,
+) -> Element
+where
+    C: Collection + Clone + PartialEq + 'static,
+    C::Key: Clone + PartialEq,
+{
+    let selected = collection
+        .selected()
+        .filter(|item| collection.contains_key(&item.key()) && !item.is_stale());
+
+    rsx! {
+        div { class: "{class}",
+            match selected {
+                Some(item) => render.call(item),
+                None => fallback,
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{Collection, CollectionStore, use_collection_select};
+use dioxus::prelude::*;
+
+/// Dropdown selecting an item of the collection
+///
+/// Options display the item values, the selection is kept in sync with the store.
+#[component]
+#[allow(non_snake_case)]
+pub fn Selector<C>(
+    collection: CollectionStore<C>,
+    #[props(default = "Select: ".to_string())] label: String,
+    #[props(default = "-- Choose --".to_string())] placeholder: String,
+    #[props(default)] class: String,
+) -> Element
+where
+    C: Collection + Clone + PartialEq + 'static,
+    C::Key: std::fmt::Display + std::str::FromStr + Clone + PartialEq,
+    C::Value: std::fmt::Display + Clone + PartialEq + 'static,
+{
+    let onchange = use_collection_select(collection);
+
+    rsx! {
+        div { class: "{class}",
+            label { "{label}" }
+            select {
+                value: collection.selected_option_value(),
+                onchange: move |evt| onchange.call(evt.value()),
+                option { value: "", "{placeholder}" }
+                for item in collection.iter() {
+                    option {
+                        key: "{item.key()}",
+                        value: collection.option_value(&item.key()),
+                        selected: item.is_selected(),
+                        "{item.read()}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Replicated collections for offline-first and peer-to-peer apps
+//!
+//! A `LwwMap` is a last-writer-wins map: every insert, update and removal is stamped with a Lamport clock
+//! and the id of the replica that made it, and removed keys keep a stamped tombstone. Two replicas that
+//! edited the same map concurrently converge to the same items by exchanging their state, whatever the
+//! order of the exchanges: `store.state_bytes()` encodes it, and `store.merge_remote_state(bytes)` applies
+//! the newer entries of a peer through the usual reactive API.
+//!
+//! ```rust,ignore
+//! let notes = use_hook(|| CollectionStore::new(LwwMap::new(device_id)));
+//! notes.insert("groceries".to_string(), "milk".to_string());
+//! channel.send(notes.state_bytes()?);
+//! // On the other device
+//! notes.merge_remote_state(&received)?;
+//! ```
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionOperation, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Logical time of a write: concurrent writes are ordered by replica id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Stamp {
+    counter: u64,
+    replica: u64,
+}
+
+/// Latest write of a key, `None` for a removal
+#[derive(Debug, Clone, PartialEq)]
+struct Entry<V> {
+    stamp: Stamp,
+    value: Option<V>,
+}
+
+/// A map merging concurrent edits by keeping the latest write of each key
+///
+/// Keys are iterated in order, so replicas holding the same items render them the same way. Removed keys
+/// are kept as tombstones, so a removal isn't undone by merging an older state that still has the item.
+/// Tombstones are never collected: a replica can't tell whether every peer has merged a removal, and
+/// dropping its tombstone early would let a peer bring the item back. Their count is `tombstones()`; to
+/// shed them, start every replica over from a fresh map once they have all converged.
+///
+/// Every write through `Collection`, including `get_mut`, stamps the key as changed by this replica.
+///
+/// # Examples
+///
+/// ```rust
+/// use dioxus_collection_store::{Collection, LwwMap};
+///
+/// let mut laptop = LwwMap::new(1);
+/// let mut phone = LwwMap::new(2);
+/// laptop.insert("title", "Draft");
+/// phone.merge(&laptop);
+/// phone.insert("title", "Final");
+/// laptop.remove(&"title");
+/// laptop.merge(&phone);
+/// phone.merge(&laptop);
+/// assert_eq!(laptop.get(&"title"), phone.get(&"title"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LwwMap<K, V> {
+    replica: u64,
+    clock: u64,
+    entries: BTreeMap<K, Entry<V>>,
+    /// Number of entries that aren't tombstones
+    live: usize,
+}
+
+impl<K, V> LwwMap<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    /// Create an empty map for a replica
+    ///
+    /// Every replica editing the map must have its own id, a device or session id for instance.
+    pub fn new(replica: u64) -> Self {
+        Self {
+            replica,
+            clock: 0,
+            entries: BTreeMap::new(),
+            live: 0,
+        }
+    }
+
+    /// Get the id of this replica
+    pub fn replica(&self) -> u64 {
+        self.replica
+    }
+
+    /// Get the number of removed keys kept as tombstones
+    pub fn tombstones(&self) -> usize {
+        self.entries.len() - self.live
+    }
+
+    /// Merge the state of another replica, keeping the latest write of each key
+    pub fn merge(&mut self, remote: &Self) {
+        let newer = self.newer_entries(
+            remote
+                .entries
+                .iter()
+                .map(|(key, entry)| (key.clone(), entry.clone())),
+        );
+        self.apply_entries(newer);
+    }
+
+    /// Stamp a write made by this replica
+    fn tick(&mut self) -> Stamp {
+        self.clock += 1;
+        Stamp {
+            counter: self.clock,
+            replica: self.replica,
+        }
+    }
+
+    /// Write `value` under `key` with a new stamp, returning the previous live value
+    fn write(&mut self, key: K, value: Option<V>) -> Option<V> {
+        let stamp = self.tick();
+        self.store(key, Entry { stamp, value })
+    }
+
+    /// Replace the entry of `key`, keeping the live count, and return the previous live value
+    fn store(&mut self, key: K, entry: Entry<V>) -> Option<V> {
+        if entry.value.is_some() {
+            self.live += 1;
+        }
+        let previous = self.entries.insert(key, entry)?.value;
+        if previous.is_some() {
+            self.live -= 1;
+        }
+        previous
+    }
+
+    /// Keep the remote entries that are newer than the local ones
+    fn newer_entries(&self, remote: impl IntoIterator<Item = (K, Entry<V>)>) -> Vec<(K, Entry<V>)> {
+        remote
+            .into_iter()
+            .filter(|(key, entry)| {
+                self.entries
+                    .get(key)
+                    .is_none_or(|local| local.stamp < entry.stamp)
+            })
+            .collect()
+    }
+
+    /// Write remote entries, moving the clock past their stamps
+    fn apply_entries(&mut self, entries: Vec<(K, Entry<V>)>) {
+        for (key, entry) in entries {
+            self.clock = self.clock.max(entry.stamp.counter);
+            self.store(key, entry);
+        }
+    }
+}
+
+impl<K, V> Collection for LwwMap<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        self.entries.get(key)?.value.as_ref()
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        let stamp = self.tick();
+        let entry = self.entries.get_mut(key)?;
+        entry.stamp = stamp;
+        entry.value.as_mut()
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        if !self.contains_key(&key) {
+            return false;
+        }
+        self.write(key, Some(value));
+        true
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        self.write(key, Some(value))
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        if !self.contains_key(key) {
+            return None;
+        }
+        self.write(key.clone(), None)
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        self.entries
+            .iter()
+            .filter(|(_, entry)| entry.value.is_some())
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    fn len(&self) -> usize {
+        self.live
+    }
+
+    fn clear(&mut self) {
+        for key in self.keys() {
+            self.write(key, None);
+        }
+    }
+}
+
+/// Encoded state of a `LwwMap`, as exchanged between replicas
+#[derive(Serialize, Deserialize)]
+struct State<K, V> {
+    entries: Vec<(K, Stamp, Option<V>)>,
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "crdt")))]
+impl<K, V> CollectionStore<LwwMap<K, V>>
+where
+    K: Ord + Clone + Serialize + DeserializeOwned + 'static,
+    V: Clone + Serialize + DeserializeOwned + 'static,
+{
+    /// Encode the state of the map to send it to other replicas
+    ///
+    /// The state holds every item and tombstone with their stamps, encoded as JSON.
+    pub fn state_bytes(&self) -> CollectionResult<Vec<u8>> {
+        let items = self.inner.items();
+        let items = items.peek();
+        let state = State {
+            entries: items
+                .entries
+                .iter()
+                .map(|(key, entry)| (key.clone(), entry.stamp, entry.value.clone()))
+                .collect(),
+        };
+        serde_json::to_vec(&state).map_err(|error| CollectionError::Serialization {
+            reason: error.to_string(),
+        })
+    }
+
+    /// Merge the state of another replica, encoded by its `state_bytes`
+    ///
+    /// Only the keys written more recently by the other replica change, each recorded as an `Insert`,
+    /// `Set` or `Remove` operation, in a single write. The selection is cleared if the selected item was
+    /// removed, and the focus and other per-item state of the removed items is dropped. Merging the same state twice, or states in any order, gives the same items.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionStore, LwwMap};
+    ///
+    /// let laptop = CollectionStore::new(LwwMap::new(1));
+    /// let phone = CollectionStore::new(LwwMap::new(2));
+    /// laptop.insert("milk".to_string(), 1);
+    /// phone.insert("eggs".to_string(), 12);
+    /// phone.merge_remote_state(&laptop.state_bytes().unwrap()).unwrap();
+    /// laptop.merge_remote_state(&phone.state_bytes().unwrap()).unwrap();
+    /// assert_eq!(laptop.peek_value(&"eggs".to_string()), Some(12));
+    /// assert_eq!(phone.peek_value(&"milk".to_string()), Some(1));
+    /// ```
+    pub fn merge_remote_state(&self, bytes: &[u8]) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let state: State<K, V> =
+            serde_json::from_slice(bytes).map_err(|error| CollectionError::Serialization {
+                reason: error.to_string(),
+            })?;
+        let newer = self.inner.items().peek().newer_entries(
+            state
+                .entries
+                .into_iter()
+                .map(|(key, stamp, value)| (key, Entry { stamp, value })),
+        );
+        if newer.is_empty() {
+            return Ok(());
+        }
+
+        self.deselect_removed(|selected| {
+            newer
+                .iter()
+                .any(|(key, entry)| key == selected && entry.value.is_none())
+        });
+        for (key, entry) in &newer {
+            let exists = self.peek_contains_key(key);
+            if entry.value.is_none() && !exists {
+                continue;
+            }
+            self.record(|clone| match (&entry.value, exists) {
+                (Some(value), false) => CollectionOperation::Insert {
+                    key: key.clone(),
+                    value: clone(value),
+                },
+                (Some(value), true) => CollectionOperation::Set {
+                    key: key.clone(),
+                    value: clone(value),
+                },
+                (None, _) => CollectionOperation::Remove { key: key.clone() },
+            });
+        }
+        let tracked_keys = self.tracked_keys();
+        let old_keys = tracked_keys.clone().unwrap_or_default();
+        self.inner.items().write().apply_entries(newer);
+        // Keys are iterated in order: find where each item is now
+        let new_keys = self.inner.items().peek().keys();
+        self.remap_tracked(tracked_keys, |position| {
+            new_keys.binary_search(&old_keys[position]).ok()
+        });
+        self.finish_mutation("merge_remote_state");
+        Ok(())
+    }
+}
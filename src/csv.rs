@@ -0,0 +1,165 @@
+//! CSV import and export
+//!
+//! Values describe their columns by implementing `RowCodec`, then `store.export_csv()` writes a header row
+//! followed by one row per item, and `store.import_csv(csv)` replaces the items with the parsed rows.
+//! Fields are quoted when needed, following RFC 4180.
+
+use crate::{CollectionError, CollectionResult, CollectionStore, SequentialCollection};
+use dioxus_signals::*;
+
+/// Conversion of a value to and from a CSV row
+///
+/// # Examples
+///
+/// ```rust
+/// use dioxus_collection_store::RowCodec;
+///
+/// struct User {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// impl RowCodec for User {
+///     fn headers() -> Vec<String> {
+///         vec!["name".to_string(), "age".to_string()]
+///     }
+///
+///     fn to_row(&self) -> Vec<String> {
+///         vec![self.name.clone(), self.age.to_string()]
+///     }
+///
+///     fn from_row(row: &[String]) -> Result<Self, String> {
+///         match row {
+///             [name, age] => Ok(User {
+///                 name: name.clone(),
+///                 age: age.parse().map_err(|_| format!("invalid age: {age}"))?,
+///             }),
+///             _ => Err(format!("expected 2 fields, got {}", row.len())),
+///         }
+///     }
+/// }
+/// ```
+pub trait RowCodec: Sized {
+    /// Names of the columns, written as the first row
+    fn headers() -> Vec<String>;
+
+    /// Fields of the value, in the order of `headers()`
+    fn to_row(&self) -> Vec<String>;
+
+    /// Parse a value from its fields, returning why the row is invalid otherwise
+    fn from_row(row: &[String]) -> Result<Self, String>;
+}
+
+/// Write a row, quoting the fields containing a separator, a quote or a line break
+fn write_row(csv: &mut String, fields: &[String]) {
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            csv.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push_str("\r\n");
+}
+
+/// Split a CSV text into rows of fields, skipping blank lines
+fn parse_rows(csv: &str) -> CollectionResult<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',') => row.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                row.push(std::mem::take(&mut field));
+                if row != [""] {
+                    rows.push(std::mem::take(&mut row));
+                }
+                row.clear();
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(CollectionError::Serialization {
+            reason: "unterminated quoted CSV field".to_string(),
+        });
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+impl<C> CollectionStore<C>
+where
+    C: SequentialCollection<Key = usize> + 'static,
+    C::Value: RowCodec,
+{
+    /// Write the items as CSV, after a header row
+    ///
+    /// This doesn't subscribe to the collection.
+    pub fn export_csv(&self) -> String {
+        let mut csv = String::new();
+        write_row(&mut csv, &C::Value::headers());
+        let items_lens = self.items();
+        let items = items_lens.peek();
+        for key in items.keys() {
+            if let Some(value) = items.get(&key) {
+                write_row(&mut csv, &value.to_row());
+            }
+        }
+        csv
+    }
+
+    /// Replace the items with the rows of a CSV text, skipping its header row
+    ///
+    /// The collection is cleared like `clear()`, then the rows are added in a single write.
+    /// Nothing changes if a row can't be parsed: the error tells which line is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let users = CollectionStore::new(Vec::<User>::new());
+    /// users.import_csv("name,age\r\nAda,36\r\n\"Hopper, Grace\",85\r\n")?;
+    /// assert_eq!(users.len(), 2);
+    /// ```
+    pub fn import_csv(&self, csv: &str) -> CollectionResult<()>
+    where
+        C::Value: Clone,
+    {
+        self.ensure_unlocked()?;
+        let entries = parse_rows(csv)?
+            .iter()
+            .skip(1)
+            .enumerate()
+            .map(|(index, row)| {
+                C::Value::from_row(row)
+                    .map(|value| (index, value))
+                    .map_err(|reason| CollectionError::Serialization {
+                        reason: format!("row {}: {}", index + 1, reason),
+                    })
+            })
+            .collect::<CollectionResult<Vec<_>>>()?;
+        self.clear();
+        self.append_entries(entries);
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+//! Skipping no-op writes
+//!
+//! `store.set(key, value)` notifies every subscriber of the collection, even when the value is unchanged.
+//! Forms bound with `onchange` often write back the value they just read: `set_if_changed` compares
+//! before writing, and `dedup_writes(true)` makes every `set` on the store do the same.
+//!
+//! Duplicated items, common after importing or pasting data, are removed in a single write with
+//! `dedup_by_key` or, for sequential stores, `dedup`.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionStore, SequentialCollection};
+use dioxus_signals::*;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// Compares the current and new value of a write, captured by `dedup_writes` so `set` doesn't need a `PartialEq` bound
+pub(crate) type ValueEq<V> = fn(&V, &V) -> bool;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Set a value in the collection, unless it is equal to the current one
+    ///
+    /// Returns `true` if the value was written. Nothing is written, recorded or notified otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// assert!(!store.set_if_changed(0, 1));
+    /// assert!(store.set_if_changed(0, 10));
+    /// ```
+    pub fn set_if_changed(&self, key: C::Key, value: C::Value) -> bool
+    where
+        C::Value: PartialEq,
+    {
+        if self.ensure_unlocked().is_err() || self.inner.items().peek().get(&key) == Some(&value) {
+            return false;
+        }
+        self.set(key, value);
+        true
+    }
+
+    /// Make every `set` on this store skip values equal to the current ones, like `set_if_changed`
+    ///
+    /// This covers `item.set(value)` too. Raw writes through `CollectionItem::write()` are not compared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["milk".to_string()]);
+    /// store.dedup_writes(true);
+    /// // Doesn't notify the components reading the collection
+    /// store.set(0, "milk".to_string());
+    /// ```
+    pub fn dedup_writes(&self, enabled: bool)
+    where
+        C::Value: PartialEq,
+    {
+        let equality: Option<ValueEq<C::Value>> = enabled.then_some(C::Value::eq);
+        self.inner.write_equality().set(equality);
+    }
+
+    /// Check if `set` skips unchanged values on this store
+    pub fn is_deduping_writes(&self) -> bool {
+        self.inner.write_equality().peek().is_some()
+    }
+
+    /// Check if writing `value` under `key` would be a no-op that `dedup_writes` should skip
+    pub(crate) fn is_unchanged_write(&self, key: &C::Key, value: &C::Value) -> bool {
+        let Some(equality) = *self.inner.write_equality().peek() else {
+            return false;
+        };
+        self.inner
+            .items()
+            .peek()
+            .get(key)
+            .is_some_and(|current| equality(current, value))
+    }
+
+    /// Remove the items whose `key` was already seen, in a single write
+    ///
+    /// The first item of each key, in collection order, is kept. Returns the keys of the removed items, as
+    /// they were before the call.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let contacts = CollectionStore::new(vec![("Ada", "ada@x.org"), ("Alan", "alan@x.org"), ("A. L.", "ada@x.org")]);
+    /// assert_eq!(contacts.dedup_by_key(|(_, email)| *email), vec![2]);
+    /// assert_eq!(contacts.len(), 2);
+    /// ```
+    pub fn dedup_by_key<K>(&self, mut key: impl FnMut(&C::Value) -> K) -> Vec<C::Key>
+    where
+        K: Hash + Eq,
+    {
+        self.remove_duplicates(|items| {
+            let mut seen = HashSet::new();
+            items
+                .keys()
+                .into_iter()
+                .filter(|k| items.get(k).is_some_and(|value| !seen.insert(key(value))))
+                .collect()
+        })
+    }
+
+    /// Remove the duplicates found by `find` in a single write, returning their keys
+    fn remove_duplicates(&self, find: impl FnOnce(&C) -> Vec<C::Key>) -> Vec<C::Key> {
+        if self.ensure_unlocked().is_err() {
+            return Vec::new();
+        }
+        let duplicates = find(&self.inner.items().peek());
+        if !duplicates.is_empty() {
+            self.remove_many(duplicates.clone());
+        }
+        duplicates
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Remove the items equal to an earlier one, in a single write
+    ///
+    /// Unlike `Vec::dedup`, duplicates don't need to be next to each other. Returns the positions of the
+    /// removed items, as they were before the call. Use `dedup_by_key` to compare the items by a part of
+    /// their value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let tags = CollectionStore::new(vec!["rust", "web", "rust", "ui", "web"]);
+    /// assert_eq!(tags.dedup(), vec![2, 4]);
+    /// assert_eq!(tags.len(), 3);
+    /// ```
+    pub fn dedup(&self) -> Vec<C::Key>
+    where
+        C::Value: Hash + Eq,
+    {
+        self.remove_duplicates(|items| {
+            let mut kept = HashSet::new();
+            items
+                .keys()
+                .into_iter()
+                .filter(|key| items.get(key).is_some_and(|value| !kept.insert(value)))
+                .collect()
+        })
+    }
+}
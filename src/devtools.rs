@@ -0,0 +1,163 @@
+//! Time-travel debugging
+//!
+//! With the `devtools` feature, recordings also capture when each mutation happened. `store.debug_timeline()`
+//! snapshots the recording in progress as a `DebugTimeline`, which steps backward and forward through the
+//! recorded states by replaying the operations up to the current step.
+//!
+//! Timestamps use `std::time::Instant`, which is not available on `wasm32-unknown-unknown`.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{
+    Collection, CollectionError, CollectionOperation, CollectionResult, CollectionStore,
+    RecordingBundle, Replay,
+};
+use dioxus_signals::*;
+use std::time::Duration;
+
+/// A recorded mutation with the time it happened
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry<K, V> {
+    /// The mutation
+    pub operation: CollectionOperation<K, V>,
+    /// Time elapsed between the start of the recording and the mutation
+    pub at: Duration,
+}
+
+/// A snapshot of a recording, with a cursor to step through its states
+///
+/// The cursor is the number of operations applied: 0 is the state when the recording started,
+/// `len()` is the state when the snapshot was taken.
+pub struct DebugTimeline<C>
+where
+    C: Collection,
+{
+    bundle: RecordingBundle<C>,
+    timestamps: Vec<Duration>,
+    position: usize,
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "devtools")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Snapshot the recording in progress as a timeline, positioned on the latest state
+    ///
+    /// Returns `None` if no recording is in progress.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2]);
+    /// store.start_recording();
+    /// store.push(3);
+    /// store.clear();
+    ///
+    /// let mut timeline = store.debug_timeline().unwrap();
+    /// timeline.step_back();
+    /// assert_eq!(timeline.state().unwrap().len(), 3);
+    /// ```
+    pub fn debug_timeline(&self) -> Option<DebugTimeline<C>>
+    where
+        C: Clone,
+        C::Value: Clone,
+    {
+        let recorder = self.inner.recorder();
+        let recorder = recorder.peek();
+        let recorder = recorder.as_ref()?;
+        Some(DebugTimeline {
+            bundle: recorder.bundle.clone(),
+            timestamps: recorder.timestamps.clone(),
+            position: recorder.bundle.operations.len(),
+        })
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "devtools")))]
+impl<C> DebugTimeline<C>
+where
+    C: Collection,
+{
+    /// Get the number of recorded operations
+    pub fn len(&self) -> usize {
+        self.bundle.operations.len()
+    }
+
+    /// Check if no operation was recorded
+    pub fn is_empty(&self) -> bool {
+        self.bundle.operations.is_empty()
+    }
+
+    /// Get the number of operations applied in the current state
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Get the recorded operations with their timestamps, in order
+    pub fn entries(&self) -> Vec<TimelineEntry<C::Key, C::Value>>
+    where
+        C::Key: Clone,
+        C::Value: Clone,
+    {
+        self.bundle
+            .operations
+            .iter()
+            .zip(&self.timestamps)
+            .map(|(operation, at)| TimelineEntry {
+                operation: operation.clone(),
+                at: *at,
+            })
+            .collect()
+    }
+
+    /// Go to the state before the last applied operation
+    ///
+    /// Returns `false` if already on the initial state.
+    pub fn step_back(&mut self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        self.position -= 1;
+        true
+    }
+
+    /// Go to the state after the next operation
+    ///
+    /// Returns `false` if already on the latest state.
+    pub fn step_forward(&mut self) -> bool {
+        if self.position == self.len() {
+            return false;
+        }
+        self.position += 1;
+        true
+    }
+
+    /// Go to the state after `position` operations
+    pub fn go_to(&mut self, position: usize) -> CollectionResult<()> {
+        if position > self.len() {
+            return Err(CollectionError::OutOfBounds {
+                index: position,
+                len: self.len(),
+            });
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    /// Build a new store in the current state, by replaying the operations up to it
+    pub fn state(&self) -> CollectionResult<CollectionStore<C>>
+    where
+        C: Replay + Clone,
+        C::Key: PartialEq,
+        C::Value: Clone,
+    {
+        CollectionStore::replay_bundle(RecordingBundle {
+            initial: self.bundle.initial.clone(),
+            initial_selection: self.bundle.initial_selection.clone(),
+            operations: self.bundle.operations[..self.position].to_vec(),
+        })
+    }
+}
@@ -0,0 +1,130 @@
+//! Item edit sessions with commit/cancel
+//!
+//! Row editors with Save/Cancel buttons edit a copy of the item: `item.begin_edit()` clones the value into
+//! a draft signal, inputs write to the draft, and `commit()` writes it back to the collection in a single
+//! `set` while `cancel()` discards it.
+//!
+//! ```rust,ignore
+//! let session = use_hook(|| item.begin_edit());
+//! rsx! {
+//!     input { value: "{session.draft()}", oninput: move |event| session.draft().set(event.value()) }
+//!     button { onclick: move |_| { session.commit().ok(); }, "Save" }
+//!     button { onclick: move |_| session.cancel(), "Cancel" }
+//! }
+//! ```
+
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+
+/// A draft of an item's value, written back on `commit`
+///
+/// Created with `CollectionItem::begin_edit`. The draft signal is owned by the scope that began the edit.
+pub struct EditSession<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    key: CopyValue<C::Key>,
+    draft: Signal<C::Value>,
+}
+
+impl<C> Copy for EditSession<C> where C: Collection + 'static {}
+
+impl<C> Clone for EditSession<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> PartialEq for EditSession<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner && self.key == other.key && self.draft == other.draft
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Start editing a copy of this item's value
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use dioxus_signals::*;
+    ///
+    /// let store = CollectionStore::new(vec!["milk".to_string()]);
+    /// let session = store.get(&0).begin_edit();
+    /// session.draft().set("oat milk".to_string());
+    /// assert_eq!(*store.get(&0).read(), "milk");
+    /// session.commit().unwrap();
+    /// assert_eq!(*store.get(&0).read(), "oat milk");
+    /// ```
+    pub fn begin_edit(&self) -> EditSession<C>
+    where
+        C::Value: Clone,
+    {
+        EditSession {
+            store: self.store,
+            key: CopyValue::new(self.key.clone()),
+            draft: Signal::new(self.peek().clone()),
+        }
+    }
+}
+
+impl<C> EditSession<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Clone,
+{
+    /// Get the edited item
+    pub fn item(&self) -> CollectionItem<C> {
+        self.store.get(&*self.key.peek())
+    }
+
+    /// Get the draft value, to read and write while editing
+    pub fn draft(&self) -> Signal<C::Value> {
+        self.draft
+    }
+
+    /// Check if the draft differs from the item's value (reactive)
+    pub fn is_dirty(&self) -> bool
+    where
+        C::Value: PartialEq,
+    {
+        let key = self.key.peek().clone();
+        self.store.contains_key(&key) && *self.draft.read() != *self.store.get(&key).read()
+    }
+
+    /// Write the draft back to the item
+    ///
+    /// Fails with `CollectionError::KeyNotFound` if the item was removed during the edit,
+    /// or `CollectionError::Locked` if the store is locked.
+    pub fn commit(&self) -> CollectionResult<()> {
+        let key = self.key.peek().clone();
+        if !self.store.peek_contains_key(&key) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        self.store.ensure_unlocked()?;
+        self.store.set(key, self.draft.peek().clone());
+        Ok(())
+    }
+
+    /// Discard the draft, resetting it to the item's current value
+    pub fn cancel(&self) {
+        let key = self.key.peek().clone();
+        if self.store.peek_contains_key(&key) {
+            let value = self.store.get(&key).peek().clone();
+            self.draft.clone().set(value);
+        }
+    }
+}
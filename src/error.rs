@@ -11,12 +11,42 @@ pub enum CollectionError {
     /// The specified key was not found in the collection
     KeyNotFound,
 
+    /// The key is already used by another item (see `CollectionStore::rename_key`)
+    DuplicateKey,
+
     /// The key is out of bounds (for indexed collections like Vec)
     OutOfBounds { index: usize, len: usize },
 
     /// The collection is empty
     EmptyCollection,
 
+    /// The collection is full (for fixed-capacity collections like `ArrayVec`)
+    CapacityExceeded { capacity: usize },
+
+    /// The item can't be selected (see `CollectionStore::set_selectable`)
+    NotSelectable,
+
+    /// The selection guard refused the selection change (see `CollectionStore::set_selection_guard`)
+    SelectionBlocked,
+
+    /// The selection guard decides asynchronously whether the selection can change
+    SelectionPending,
+
+    /// The store's permissions don't allow the action (see `CollectionStore::guard`)
+    PermissionDenied { action: &'static str },
+
+    /// The store is locked (see `CollectionStore::lock`)
+    Locked,
+
+    /// The item was removed or moved since the handle was created (see `CollectionStore::track_generations`)
+    StaleHandle,
+
+    /// Importing or exporting the collection failed (see `CollectionStore::import_json`)
+    Serialization { reason: String },
+
+    /// The system clipboard couldn't be read or written (see `CollectionStore::copy_selection_to_clipboard`)
+    Clipboard { reason: String },
+
     /// Failed to access an item that should exist
     InvalidAccess { reason: String },
 
@@ -33,6 +63,9 @@ impl fmt::Display for CollectionError {
             CollectionError::KeyNotFound => {
                 write!(f, "Key not found in collection")
             }
+            CollectionError::DuplicateKey => {
+                write!(f, "Key already exists in collection")
+            }
             CollectionError::OutOfBounds { index, len } => {
                 write!(
                     f,
@@ -43,6 +76,33 @@ impl fmt::Display for CollectionError {
             CollectionError::EmptyCollection => {
                 write!(f, "Collection is empty")
             }
+            CollectionError::CapacityExceeded { capacity } => {
+                write!(f, "Collection is full ({} items)", capacity)
+            }
+            CollectionError::NotSelectable => {
+                write!(f, "Item can't be selected")
+            }
+            CollectionError::SelectionBlocked => {
+                write!(f, "Selection change blocked by the selection guard")
+            }
+            CollectionError::SelectionPending => {
+                write!(f, "Selection change waiting for the selection guard")
+            }
+            CollectionError::PermissionDenied { action } => {
+                write!(f, "Permission denied: can't {} items", action)
+            }
+            CollectionError::Locked => {
+                write!(f, "Collection is locked")
+            }
+            CollectionError::StaleHandle => {
+                write!(f, "Item handle is stale: its item was removed or moved")
+            }
+            CollectionError::Serialization { reason } => {
+                write!(f, "Serialization failed: {}", reason)
+            }
+            CollectionError::Clipboard { reason } => {
+                write!(f, "Clipboard access failed: {}", reason)
+            }
             CollectionError::InvalidAccess { reason } => {
                 write!(f, "Invalid access: {}", reason)
             }
@@ -0,0 +1,106 @@
+//! Exit phase for animated removals
+//!
+//! `store.remove_with_exit(&key, duration)` marks the item as leaving, then removes it once `duration` has elapsed.
+//! Components render leaving items with an exit animation (`item.leaving()`), without keeping shadow state
+//! outside the store. The mark is stored in the item metadata, so it follows the item if the collection
+//! changes in the meantime.
+
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_core::spawn;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Metadata marking an item as leaving
+///
+/// Holds the identifier of the pending removal, so a cancelled exit can't remove the item later.
+#[derive(Default)]
+struct Leaving(u64);
+
+/// Source of the pending removal identifiers
+static NEXT_EXIT: AtomicU64 = AtomicU64::new(0);
+
+#[cfg_attr(docsrs, doc(cfg(feature = "animations")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Mark an item as leaving, and remove it once `exit` has elapsed
+    ///
+    /// Must be called from a Dioxus runtime, the removal runs in a spawned task.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::time::Duration;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b"]);
+    /// store.remove_with_exit(&0, Duration::from_millis(200)).ok();
+    /// assert!(store.get(&0).leaving());
+    /// // rsx! { li { class: if item.leaving() { "fade-out" }, ... } }
+    /// ```
+    pub fn remove_with_exit(&self, key: &C::Key, exit: Duration) -> CollectionResult<()>
+    where
+        C::Value: Clone,
+    {
+        let token = self.start_exit(key)?;
+        let store = *self;
+        spawn(async move {
+            futures_timer::Delay::new(exit).await;
+            store.finish_exit(token);
+        });
+        Ok(())
+    }
+
+    /// Check if an item is leaving (reactive)
+    pub fn is_leaving(&self, key: &C::Key) -> bool {
+        self.meta_of::<Leaving>(key).is_set()
+    }
+
+    /// Keep a leaving item in the collection
+    pub fn cancel_exit(&self, key: &C::Key) {
+        self.meta_of::<Leaving>(key).take();
+    }
+
+    /// Mark an item as leaving, returning the identifier of its pending removal
+    pub(crate) fn start_exit(&self, key: &C::Key) -> CollectionResult<u64> {
+        self.ensure_unlocked()?;
+        if !self.peek_contains_key(key) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        let token = NEXT_EXIT.fetch_add(1, Ordering::Relaxed);
+        self.write_meta_table(|leaving| leaving.insert(key.clone(), Leaving(token)));
+        Ok(token)
+    }
+
+    /// Remove the item whose pending removal is `token`, unless its exit was cancelled or it is already gone
+    pub(crate) fn finish_exit(&self, token: u64)
+    where
+        C::Value: Clone,
+    {
+        let key = self.peek_meta_table(|leaving: Option<&HashMap<C::Key, Leaving>>| {
+            leaving?
+                .iter()
+                .find(|(_, Leaving(pending))| *pending == token)
+                .map(|(key, _)| key.clone())
+        });
+        if let Some(key) = key {
+            self.remove(&key);
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "animations")))]
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Check if this item is leaving, to render its exit animation
+    pub fn leaving(&self) -> bool {
+        self.store.is_leaving(&self.key)
+    }
+}
@@ -0,0 +1,68 @@
+//! JSON import and export
+//!
+//! `store.export_json()` serializes the collection alone, without the selection or the other store state,
+//! and `store.import_json(json)` replaces the collection with the deserialized one. A download/upload button
+//! for a data table is then a single call, however the store wraps the data.
+
+use crate::{Collection, CollectionError, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Serialize the collection to JSON
+    ///
+    /// This doesn't subscribe to the collection. Fails with `CollectionError::Serialization`
+    /// if the collection can't be represented in JSON, like a map with non-string keys.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// assert_eq!(store.export_json().unwrap(), "[1,2,3]");
+    /// ```
+    pub fn export_json(&self) -> CollectionResult<String>
+    where
+        C: serde::Serialize,
+    {
+        serde_json::to_string(&*self.items().peek()).map_err(|error| {
+            CollectionError::Serialization {
+                reason: error.to_string(),
+            }
+        })
+    }
+
+    /// Replace the collection with the one deserialized from JSON
+    ///
+    /// The collection is cleared like `clear()`, then the imported items are added in a single write.
+    /// Nothing changes if the JSON can't be deserialized.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.import_json("[4, 5]").unwrap();
+    /// assert_eq!(store.len(), 2);
+    /// ```
+    pub fn import_json(&self, json: &str) -> CollectionResult<()>
+    where
+        C: serde::de::DeserializeOwned,
+        C::Value: Clone,
+    {
+        self.ensure_unlocked()?;
+        let mut imported: C =
+            serde_json::from_str(json).map_err(|error| CollectionError::Serialization {
+                reason: error.to_string(),
+            })?;
+        self.clear();
+        self.append_entries(imported.drain());
+        Ok(())
+    }
+}
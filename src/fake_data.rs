@@ -0,0 +1,277 @@
+//! Mock data generation and mutation simulation
+//!
+//! Available with the `fake-data` feature. Useful to build demos and performance tests
+//! of list UIs without writing data generators each time.
+
+use crate::recording::CollectionOperation;
+use crate::{Collection, CollectionStore, SequentialCollection};
+use dioxus_core::{Task, spawn, use_hook};
+use dioxus_signals::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::Duration;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "fake-data")))]
+impl<C> CollectionStore<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Push `n` generated values in a single write
+    ///
+    /// The generator receives the index of the value being generated (`0..n`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(Vec::new());
+    /// store.populate_fake(100, |i| format!("Row #{i}"));
+    /// assert_eq!(store.len(), 100);
+    /// ```
+    pub fn populate_fake(&self, n: usize, generator: impl FnMut(usize) -> C::Value) {
+        let values: Vec<C::Value> = (0..n).map(generator).collect();
+        for value in &values {
+            self.record(|clone| CollectionOperation::Push {
+                value: clone(value),
+            });
+        }
+        let mut items = self.items();
+        let mut items = items.write();
+        for value in values {
+            items.push(value);
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "fake-data")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Insert `n` generated key-value pairs in a single write
+    ///
+    /// This is the keyed counterpart of `populate_fake`, for maps and other non-sequential collections.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
+    ///
+    /// let store = CollectionStore::new(HashMap::new());
+    /// store.populate_fake_entries(10, |i| (format!("user-{i}"), i * 10));
+    /// assert_eq!(store.len(), 10);
+    /// ```
+    pub fn populate_fake_entries(
+        &self,
+        n: usize,
+        generator: impl FnMut(usize) -> (C::Key, C::Value),
+    ) {
+        let entries: Vec<(C::Key, C::Value)> = (0..n).map(generator).collect();
+        for (key, value) in &entries {
+            self.record(|clone| CollectionOperation::Insert {
+                key: key.clone(),
+                value: clone(value),
+            });
+        }
+        let mut items = self.items();
+        let mut items = items.write();
+        for (key, value) in entries {
+            items.insert(key, value);
+        }
+    }
+}
+
+/// Generates the values pushed by a `MutationSimulator`
+type InsertFn<V> = Box<dyn FnMut(&mut StdRng) -> V>;
+
+/// Edits the values in place for a `MutationSimulator`
+type EditFn<V> = Box<dyn FnMut(&mut StdRng, &mut V)>;
+
+/// A mutation applied by a `MutationSimulator`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SimulatedMutation<K> {
+    /// A generated value was pushed at the given key
+    Inserted(K),
+    /// The value at the given key was edited
+    Edited(K),
+    /// The value at the given key was removed
+    Removed(K),
+}
+
+/// Applies random inserts, edits and removals to a sequential store
+///
+/// Only the operations that were configured are simulated. Call `step()` to apply a single
+/// mutation, `run()` to apply one per interval, or use `use_mutation_simulator` in a component.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use dioxus_collection_store::{CollectionStore, MutationSimulator};
+/// use rand::Rng;
+///
+/// let store = CollectionStore::new(vec![0u32; 10]);
+/// let mut simulator = MutationSimulator::new(store)
+///     .seed(42)
+///     .inserts(|rng| rng.random_range(0..100))
+///     .edits(|rng, value| *value = rng.random_range(0..100))
+///     .removals()
+///     .max_len(50);
+/// simulator.step();
+/// ```
+pub struct MutationSimulator<C>
+where
+    C: SequentialCollection + 'static,
+{
+    store: CollectionStore<C>,
+    rng: StdRng,
+    insert: Option<InsertFn<C::Value>>,
+    edit: Option<EditFn<C::Value>>,
+    remove: bool,
+    max_len: Option<usize>,
+}
+
+impl<C> MutationSimulator<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Clone + 'static,
+{
+    /// Create a simulator for the given store, with no operation enabled
+    pub fn new(store: CollectionStore<C>) -> Self {
+        Self {
+            store,
+            rng: StdRng::from_rng(&mut rand::rng()),
+            insert: None,
+            edit: None,
+            remove: false,
+            max_len: None,
+        }
+    }
+
+    /// Use a deterministic random generator, so a simulation can be reproduced
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Enable random inserts, generating values with the given function
+    pub fn inserts(mut self, generator: impl FnMut(&mut StdRng) -> C::Value + 'static) -> Self {
+        self.insert = Some(Box::new(generator));
+        self
+    }
+
+    /// Enable random edits, mutating values in place with the given function
+    pub fn edits(mut self, editor: impl FnMut(&mut StdRng, &mut C::Value) + 'static) -> Self {
+        self.edit = Some(Box::new(editor));
+        self
+    }
+
+    /// Enable random removals
+    pub fn removals(mut self) -> Self {
+        self.remove = true;
+        self
+    }
+
+    /// Stop simulating inserts once the collection reaches `max_len` items
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Apply a single random mutation
+    ///
+    /// Returns `None` if no configured operation can be applied
+    /// (e.g. only removals are enabled and the collection is empty).
+    pub fn step(&mut self) -> Option<SimulatedMutation<C::Key>> {
+        let keys = self.store.items().peek().keys();
+        let can_insert =
+            self.insert.is_some() && self.max_len.is_none_or(|max_len| keys.len() < max_len);
+        let can_edit = self.edit.is_some() && !keys.is_empty();
+        let can_remove = self.remove && !keys.is_empty();
+
+        let mut candidates = Vec::with_capacity(3);
+        if can_insert {
+            candidates.push(0);
+        }
+        if can_edit {
+            candidates.push(1);
+        }
+        if can_remove {
+            candidates.push(2);
+        }
+        if candidates.is_empty() {
+            return None;
+        }
+
+        match candidates[self.rng.random_range(0..candidates.len())] {
+            0 => {
+                let generator = self.insert.as_mut()?;
+                let value = generator(&mut self.rng);
+                self.store.push(value);
+                let key = self.store.items().peek().last_key()?;
+                Some(SimulatedMutation::Inserted(key))
+            }
+            1 => {
+                let key = keys[self.rng.random_range(0..keys.len())].clone();
+                let editor = self.edit.as_mut()?;
+                // Edit a copy and set it back, so the edit goes through the store API
+                let mut value = self.store.get(&key).peek().clone();
+                editor(&mut self.rng, &mut value);
+                self.store.set(key.clone(), value);
+                Some(SimulatedMutation::Edited(key))
+            }
+            _ => {
+                let key = keys[self.rng.random_range(0..keys.len())].clone();
+                self.store.remove(&key)?;
+                Some(SimulatedMutation::Removed(key))
+            }
+        }
+    }
+
+    /// Apply one random mutation per `interval`, forever
+    ///
+    /// Spawn the returned future as a Dioxus task; it stops when the task is dropped.
+    pub async fn run(mut self, interval: Duration) {
+        loop {
+            futures_timer::Delay::new(interval).await;
+            self.step();
+        }
+    }
+}
+
+/// Hook running a `MutationSimulator` on the given store for the lifetime of the component
+///
+/// The simulator is configured once, by the `configure` closure.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use dioxus_collection_store::{use_collection, use_mutation_simulator};
+/// use std::time::Duration;
+///
+/// // In a Dioxus component:
+/// let rows = use_collection(|| vec![0u32; 100]);
+/// use_mutation_simulator(rows, Duration::from_millis(50), |simulator| {
+///     simulator
+///         .inserts(|_| 0)
+///         .edits(|_, value| *value += 1)
+///         .removals()
+///         .max_len(200)
+/// });
+/// ```
+pub fn use_mutation_simulator<C>(
+    store: CollectionStore<C>,
+    interval: Duration,
+    configure: impl FnOnce(MutationSimulator<C>) -> MutationSimulator<C>,
+) -> Task
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Clone + 'static,
+{
+    use_hook(move || spawn(configure(MutationSimulator::new(store)).run(interval)))
+}
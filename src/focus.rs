@@ -0,0 +1,142 @@
+//! Focus cursor, distinct from the selection
+//!
+//! Listboxes need a roving focus that moves with the keyboard without changing the committed selection.
+//! The focused key follows its item when the collection changes, and is cleared when the item is removed.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::tracking::follow_key;
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the currently focused key (reactive)
+    pub fn focused_key(&self) -> Option<C::Key> {
+        self.inner.focused_key().read().clone()
+    }
+
+    /// Get a Store for the focused key
+    pub fn focused_key_signal(&self) -> impl Writable<Target = Option<C::Key>> + Copy {
+        self.inner.focused_key()
+    }
+
+    /// Get the currently focused item as a CollectionItem
+    pub fn focused(&self) -> Option<CollectionItem<C>> {
+        let key = self.focused_key()?;
+        Some(CollectionItem::new(*self, key))
+    }
+
+    /// Focus an item by its key
+    pub fn focus(&self, key: &C::Key) -> CollectionResult<()> {
+        if !self.peek_contains_key(key) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        if self.inner.focused_key().peek().as_ref() != Some(key) {
+            self.inner.focused_key().set(Some(key.clone()));
+        }
+        Ok(())
+    }
+
+    /// Clear the focus
+    pub fn clear_focus(&self) {
+        if self.inner.focused_key().peek().is_some() {
+            self.inner.focused_key().set(None);
+        }
+    }
+
+    /// Move the focus to the next item, or to the first item if nothing is focused
+    ///
+    /// The focus stays on the last item. Returns the focused key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c"]);
+    /// assert_eq!(store.focus_next(), Some(0));
+    /// assert_eq!(store.focus_next(), Some(1));
+    /// store.commit_focus().ok();
+    /// assert_eq!(store.selected_key(), Some(1));
+    /// ```
+    pub fn focus_next(&self) -> Option<C::Key> {
+        self.move_focus(|position, len| match position {
+            Some(position) => (position + 1).min(len - 1),
+            None => 0,
+        })
+    }
+
+    /// Move the focus to the previous item, or to the last item if nothing is focused
+    ///
+    /// The focus stays on the first item. Returns the focused key.
+    pub fn focus_prev(&self) -> Option<C::Key> {
+        self.move_focus(|position, len| match position {
+            Some(position) => position.saturating_sub(1),
+            None => len - 1,
+        })
+    }
+
+    /// Select the focused item
+    pub fn commit_focus(&self) -> CollectionResult<()> {
+        let key =
+            self.inner
+                .focused_key()
+                .peek()
+                .clone()
+                .ok_or(CollectionError::InvalidAccess {
+                    reason: "no item is focused".to_string(),
+                })?;
+        self.select(&key)
+    }
+
+    /// Move the focus to the position computed from the focused position and the collection length
+    fn move_focus(&self, next: impl FnOnce(Option<usize>, usize) -> usize) -> Option<C::Key> {
+        let keys = self.inner.items().peek().keys();
+        if keys.is_empty() {
+            return None;
+        }
+        let focused = self.inner.focused_key().peek().clone();
+        let position = focused.and_then(|focused| keys.iter().position(|k| *k == focused));
+        let key = keys[next(position, keys.len())].clone();
+        self.focus(&key).ok()?;
+        Some(key)
+    }
+
+    /// Move the focus along with its item after a structural change
+    pub(crate) fn remap_focus(
+        &self,
+        old_keys: &[C::Key],
+        new_position: &impl Fn(usize) -> Option<usize>,
+    ) {
+        let Some(focused) = self.inner.focused_key().peek().clone() else {
+            return;
+        };
+        let new_focus = {
+            let items = self.inner.items();
+            let items = items.peek();
+            follow_key(&*items, old_keys, &items.keys(), &focused, new_position)
+        };
+        if new_focus.as_ref() != Some(&focused) {
+            self.inner.focused_key().set(new_focus);
+        }
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Check if this item is currently focused
+    pub fn is_focused(&self) -> bool {
+        *self.store.inner.focused_key().read() == Some(self.key.clone())
+    }
+
+    /// Focus this item
+    pub fn focus(&self) -> CollectionResult<()> {
+        self.store.focus(&self.key)
+    }
+}
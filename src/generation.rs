@@ -0,0 +1,150 @@
+//! Generational keys, to detect stale `CollectionItem` handles
+//!
+//! A `CollectionItem` only holds a key: once its item is removed or moved (a `Vec` index shifting after a removal,
+//! a sort...), the handle silently points to whichever item now has that key. With generation tracking enabled,
+//! each key (slot) carries a counter bumped whenever its item leaves it, and items remember the generation
+//! they were created at. Stale items then fail with `CollectionError::StaleHandle` instead of using the wrong data.
+//!
+//! Updating a value in place (`set`, `insert` on an existing key, `item.write()`) keeps the generation.
+//! Like the other per-item state, generations only follow changes made through the store API.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::tracking::follow_key_at;
+use crate::{Collection, CollectionError, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The generations of the keys, seen without the bounds of the key so every handle can look them up
+///
+/// Keys whose item never left them are at generation 0 and aren't stored.
+pub(crate) trait GenerationTable<K> {
+    fn get(&self, key: &K) -> u64;
+
+    fn bump(&mut self, key: K);
+}
+
+impl<K> GenerationTable<K> for HashMap<K, u64>
+where
+    K: Hash + Eq,
+{
+    fn get(&self, key: &K) -> u64 {
+        HashMap::get(self, key).copied().unwrap_or(0)
+    }
+
+    fn bump(&mut self, key: K) {
+        *self.entry(key).or_insert(0) += 1;
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Enable or disable generation tracking for this store
+    ///
+    /// Only the items created while tracking is enabled can detect that they're stale.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionError, CollectionStore};
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c"]);
+    /// store.track_generations(true);
+    /// let item = store.get(&1);
+    /// store.remove(&0);
+    /// // Index 1 now holds "c"
+    /// assert!(item.is_stale());
+    /// assert_eq!(item.try_read_value().err(), Some(CollectionError::StaleHandle));
+    /// ```
+    pub fn track_generations(&self, enabled: bool) {
+        if enabled != self.is_tracking_generations() {
+            self.inner.generations().set(enabled.then(|| {
+                Box::new(HashMap::<C::Key, u64>::new()) as Box<dyn GenerationTable<C::Key>>
+            }));
+        }
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Check if generation tracking is enabled for this store
+    pub fn is_tracking_generations(&self) -> bool {
+        self.inner.generations().peek().is_some()
+    }
+
+    /// Get the current generation of a key, or `None` if generation tracking is disabled
+    pub(crate) fn peek_generation(&self, key: &C::Key) -> Option<u64> {
+        let generations = self.inner.generations();
+        let generations = generations.peek();
+        Some(generations.as_ref()?.get(key))
+    }
+
+    /// Fail with `CollectionError::StaleHandle` if `key` moved past the `generation` of a handle
+    pub(crate) fn ensure_generation(
+        &self,
+        key: &C::Key,
+        generation: Option<u64>,
+    ) -> CollectionResult<()> {
+        match (generation, self.peek_generation(key)) {
+            (Some(generation), Some(current)) if generation != current => {
+                Err(CollectionError::StaleHandle)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Bump the generation of the keys whose item was removed or moved by a structural change
+    pub(crate) fn remap_generations(
+        &self,
+        old_keys: &[C::Key],
+        new_position: &impl Fn(usize) -> Option<usize>,
+    ) {
+        if !self.is_tracking_generations() {
+            return;
+        }
+        let expired: Vec<C::Key> = {
+            let items = self.inner.items();
+            let items = items.peek();
+            let new_keys = items.keys();
+            old_keys
+                .iter()
+                .enumerate()
+                .filter(|(position, key)| {
+                    follow_key_at(&*items, &new_keys, key, *position, new_position).as_ref()
+                        != Some(*key)
+                })
+                .map(|(_, key)| key.clone())
+                .collect()
+        };
+        self.expire_generations(expired);
+    }
+
+    /// Bump the generation of every key, before the collection is emptied
+    pub(crate) fn expire_all_generations(&self) {
+        if self.is_tracking_generations() {
+            let keys = self.inner.items().peek().keys();
+            self.expire_generations(keys);
+        }
+    }
+
+    /// Bump the generation of `keys`, making the handles created before stale
+    pub(crate) fn expire_generations(&self, keys: Vec<C::Key>) {
+        if keys.is_empty() {
+            return;
+        }
+        let mut generations = self.inner.generations();
+        let mut generations = generations.write();
+        let Some(generations) = generations.as_mut() else {
+            return;
+        };
+        for key in keys {
+            generations.bump(key);
+        }
+    }
+}
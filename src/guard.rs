@@ -0,0 +1,432 @@
+//! Permission guards on mutations
+//!
+//! `store.guard(permissions)` returns a `GuardedCollectionStore`, a handle whose mutation methods fail with
+//! `CollectionError::PermissionDenied` when its permissions don't allow them. Each handle keeps its own
+//! permissions, so a read-only handle stays read-only when another one is created with more rights. Pass it to
+//! the components that shouldn't get full access, and read `permissions()` to hide the controls they can't use.
+//!
+//! Like the `try_*` store methods, the handle's methods fail with `CollectionError::Locked` while the store
+//! is locked.
+//!
+//! Items returned by `get`, `iter` and `selected` are `GuardedItem`s: they read like a `CollectionItem`,
+//! but only write through `set` and `remove`, checked against the same permissions.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{
+    Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore,
+    SequentialCollection,
+};
+use dioxus_signals::*;
+use std::cmp::Ordering;
+
+/// Mutations allowed through a `GuardedCollectionStore`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permissions {
+    /// Add new items
+    pub can_insert: bool,
+    /// Remove items
+    pub can_remove: bool,
+    /// Replace the value of existing items
+    pub can_edit: bool,
+    /// Change the order of items
+    pub can_reorder: bool,
+}
+
+impl Permissions {
+    /// Allow every mutation
+    pub const fn all() -> Self {
+        Self {
+            can_insert: true,
+            can_remove: true,
+            can_edit: true,
+            can_reorder: true,
+        }
+    }
+
+    /// Deny every mutation
+    pub const fn read_only() -> Self {
+        Self {
+            can_insert: false,
+            can_remove: false,
+            can_edit: false,
+            can_reorder: false,
+        }
+    }
+}
+
+impl Default for Permissions {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// A store handle whose mutations are checked against its own permissions
+///
+/// Created with `CollectionStore::guard`.
+pub struct GuardedCollectionStore<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    permissions: Signal<Permissions>,
+}
+
+impl<C> Copy for GuardedCollectionStore<C> where C: Collection + 'static {}
+
+impl<C> Clone for GuardedCollectionStore<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> PartialEq for GuardedCollectionStore<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner && self.permissions == other.permissions
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get a handle enforcing `permissions`
+    ///
+    /// Permissions belong to the handle: guarding the store again creates another handle, leaving the
+    /// permissions of this one untouched. The store itself and its items stay writable.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionError, CollectionStore, Permissions};
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// let guarded = store.guard(Permissions { can_remove: false, ..Permissions::all() });
+    /// assert!(matches!(guarded.remove(&0), Err(CollectionError::PermissionDenied { .. })));
+    /// guarded.push(4).unwrap();
+    /// ```
+    pub fn guard(&self, permissions: Permissions) -> GuardedCollectionStore<C> {
+        GuardedCollectionStore {
+            store: *self,
+            permissions: Signal::new(permissions),
+        }
+    }
+}
+
+impl<C> GuardedCollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the permissions enforced by this handle (reactive)
+    pub fn permissions(&self) -> Permissions {
+        *self.permissions.read()
+    }
+
+    /// Replace the permissions enforced by this handle, and by its copies
+    pub fn set_permissions(&self, permissions: Permissions) {
+        let mut current = self.permissions;
+        current.set(permissions);
+    }
+
+    /// Fail with `CollectionError::PermissionDenied` unless `allowed` holds for the handle's permissions,
+    /// or with `CollectionError::Locked` while the store is locked
+    fn require(
+        &self,
+        allowed: impl FnOnce(Permissions) -> bool,
+        action: &'static str,
+    ) -> CollectionResult<()> {
+        self.store.ensure_unlocked()?;
+        if allowed(*self.permissions.peek()) {
+            Ok(())
+        } else {
+            Err(CollectionError::PermissionDenied { action })
+        }
+    }
+
+    /// Get the number of items in the collection
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    /// Check if the collection is empty
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    /// Get an item by key
+    pub fn get(&self, key: &C::Key) -> GuardedItem<C> {
+        self.wrap(self.store.get(key))
+    }
+
+    /// Iterate over the items of the collection
+    pub fn iter(&self) -> impl Iterator<Item = GuardedItem<C>> + '_ {
+        self.store.iter().map(|item| self.wrap(item))
+    }
+
+    /// Get the selected item
+    pub fn selected(&self) -> Option<GuardedItem<C>> {
+        self.store.selected().map(|item| self.wrap(item))
+    }
+
+    fn wrap(&self, item: CollectionItem<C>) -> GuardedItem<C> {
+        GuardedItem { item, guard: *self }
+    }
+
+    /// Select an item, selection is always allowed
+    pub fn select(&self, key: &C::Key) -> CollectionResult<()> {
+        self.store.select(key)
+    }
+
+    /// Insert a new item, or replace the value of an existing one
+    ///
+    /// Requires `can_insert` for a new key, `can_edit` for an existing one.
+    pub fn insert(&self, key: C::Key, value: C::Value) -> CollectionResult<Option<C::Value>>
+    where
+        C::Value: Clone,
+    {
+        if self.store.inner.items().peek().contains_key(&key) {
+            self.require(|permissions| permissions.can_edit, "edit")?;
+        } else {
+            self.require(|permissions| permissions.can_insert, "insert")?;
+        }
+        self.store.try_insert(key, value)
+    }
+
+    /// Replace the value of an item, requires `can_edit`
+    pub fn set(&self, key: C::Key, value: C::Value) -> CollectionResult<()> {
+        self.require(|permissions| permissions.can_edit, "edit")?;
+        self.store.try_set(key, value)
+    }
+
+    /// Add items, or replace the values of existing ones
+    ///
+    /// Like `insert`, requires `can_insert` if any key is new and `can_edit` if any key already exists.
+    /// Nothing is written unless every item is allowed.
+    pub fn extend<I: IntoIterator<Item = (C::Key, C::Value)>>(
+        &self,
+        items: I,
+    ) -> CollectionResult<()>
+    where
+        C::Value: Clone,
+    {
+        let items: Vec<_> = items.into_iter().collect();
+        let edits = items
+            .iter()
+            .filter(|(key, _)| self.store.peek_contains_key(key))
+            .count();
+        if edits > 0 {
+            self.require(|permissions| permissions.can_edit, "edit")?;
+        }
+        if edits < items.len() {
+            self.require(|permissions| permissions.can_insert, "insert")?;
+        }
+        self.store.try_extend(items)
+    }
+
+    /// Remove an item, requires `can_remove`
+    pub fn remove(&self, key: &C::Key) -> CollectionResult<Option<C::Value>>
+    where
+        C::Value: Clone,
+    {
+        self.require(|permissions| permissions.can_remove, "remove")?;
+        self.store.try_remove(key)
+    }
+
+    /// Remove several items, requires `can_remove`
+    pub fn remove_many(
+        &self,
+        keys: impl IntoIterator<Item = C::Key>,
+    ) -> CollectionResult<Vec<C::Value>> {
+        self.require(|permissions| permissions.can_remove, "remove")?;
+        self.store.try_remove_many(keys)
+    }
+
+    /// Remove every item, requires `can_remove`
+    pub fn clear(&self) -> CollectionResult<()> {
+        self.require(|permissions| permissions.can_remove, "remove")?;
+        self.store.try_clear()
+    }
+}
+
+/// An item of a `GuardedCollectionStore`
+///
+/// Reads like a `CollectionItem` (it implements `Readable`, not `Writable`), and writes only through
+/// `set` and `remove`, which fail with `CollectionError::PermissionDenied` like the handle's own methods.
+pub struct GuardedItem<C>
+where
+    C: Collection + 'static,
+{
+    item: CollectionItem<C>,
+    guard: GuardedCollectionStore<C>,
+}
+
+impl<C> Clone for GuardedItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            item: CollectionItem {
+                store: self.item.store,
+                key: self.item.key.clone(),
+                generation: self.item.generation,
+            },
+            guard: self.guard,
+        }
+    }
+}
+
+impl<C> PartialEq for GuardedItem<C>
+where
+    C: Collection + 'static,
+    C::Key: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.item.key == other.item.key
+            && self.item.generation == other.item.generation
+            && self.guard == other.guard
+    }
+}
+
+impl<C> Readable for GuardedItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    type Target = C::Value;
+    type Storage = UnsyncStorage;
+
+    fn try_read_unchecked(&self) -> Result<ReadableRef<'static, Self>, BorrowError>
+    where
+        Self::Target: 'static,
+    {
+        self.item.try_read_unchecked()
+    }
+
+    fn try_peek_unchecked(&self) -> Result<ReadableRef<'static, Self>, BorrowError>
+    where
+        Self::Target: 'static,
+    {
+        self.item.try_peek_unchecked()
+    }
+
+    fn subscribers(&self) -> dioxus_core::Subscribers
+    where
+        Self::Target: 'static,
+    {
+        self.item.subscribers()
+    }
+}
+
+impl<C> GuardedItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the key of this item
+    pub fn key(&self) -> C::Key {
+        self.item.key()
+    }
+
+    /// Get the position of this item in the iteration order of the collection
+    pub fn position(&self) -> Option<usize> {
+        self.item.position()
+    }
+
+    /// Check if the item was removed or moved since this handle was created
+    pub fn is_stale(&self) -> bool {
+        self.item.is_stale()
+    }
+
+    /// Check if this item is currently selected
+    pub fn is_selected(&self) -> bool {
+        self.item.is_selected()
+    }
+
+    /// Select this item, selection is always allowed
+    pub fn select(&self) -> CollectionResult<()> {
+        self.item.select()
+    }
+
+    /// Replace the value of this item, requires `can_edit`
+    pub fn set(&self, value: C::Value) -> CollectionResult<()> {
+        self.guard
+            .require(|permissions| permissions.can_edit, "edit")?;
+        self.item.try_set(value)
+    }
+
+    /// Remove this item, requires `can_remove`
+    pub fn remove(&self) -> CollectionResult<Option<C::Value>>
+    where
+        C::Value: Clone,
+    {
+        self.guard
+            .require(|permissions| permissions.can_remove, "remove")?;
+        self.item.try_remove()
+    }
+}
+
+impl<C> GuardedCollectionStore<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Add an item at the end, requires `can_insert`
+    pub fn push(&self, value: C::Value) -> CollectionResult<()>
+    where
+        C::Value: Clone,
+    {
+        self.require(|permissions| permissions.can_insert, "insert")?;
+        self.store.try_push(value)
+    }
+
+    /// Add an item before another one, requires `can_insert`
+    pub fn insert_before(&self, key: &C::Key, value: C::Value) -> CollectionResult<C::Key> {
+        self.require(|permissions| permissions.can_insert, "insert")?;
+        self.store.insert_before(key, value)
+    }
+
+    /// Add an item after another one, requires `can_insert`
+    pub fn insert_after(&self, key: &C::Key, value: C::Value) -> CollectionResult<C::Key> {
+        self.require(|permissions| permissions.can_insert, "insert")?;
+        self.store.insert_after(key, value)
+    }
+
+    /// Remove the last item, requires `can_remove`
+    pub fn pop(&self) -> CollectionResult<Option<C::Value>>
+    where
+        C::Value: Clone,
+    {
+        self.require(|permissions| permissions.can_remove, "remove")?;
+        self.store.try_pop()
+    }
+
+    /// Swap two items, requires `can_reorder`
+    pub fn swap(&self, key1: &C::Key, key2: &C::Key) -> CollectionResult<()> {
+        self.require(|permissions| permissions.can_reorder, "reorder")?;
+        self.store.swap(key1, key2)
+    }
+
+    /// Move an item to another position, requires `can_reorder`
+    pub fn move_item(&self, from: &C::Key, to: &C::Key) -> CollectionResult<()> {
+        self.require(|permissions| permissions.can_reorder, "reorder")?;
+        self.store.move_item(from, to)
+    }
+
+    /// Sort the items, requires `can_reorder`
+    pub fn sort_by(
+        &self,
+        compare: impl FnMut(&C::Value, &C::Value) -> Ordering,
+    ) -> CollectionResult<()> {
+        self.require(|permissions| permissions.can_reorder, "reorder")?;
+        self.store.try_sort_by(compare)
+    }
+}
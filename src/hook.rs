@@ -1,6 +1,6 @@
-use crate::{Collection, CollectionData, CollectionStore};
+use crate::{Collection, CollectionStore};
 
-use dioxus_stores::*;
+use dioxus_core::{provide_context, try_consume_context, use_hook};
 
 /// Hook for creating a generic reactive collection store
 ///
@@ -46,9 +46,62 @@ where
     C: Collection + 'static,
     C::Key: Clone + PartialEq,
 {
-    let store = use_store(|| CollectionData {
-        items: initial(),
-        selected_key: None,
-    });
-    CollectionStore::from(store)
+    use_hook(|| CollectionStore::new(initial()))
+}
+
+/// Share a store with the descendants of the current component
+///
+/// Descendants get it back with `use_collection_context`, without passing it down as a prop.
+/// Returns the store, so it can be called on the result of a hook.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_collection_store::{CollectionStore, provide_collection, use_collection_context};
+///
+/// fn App() -> Element {
+///     let todos = use_hook(|| provide_collection(CollectionStore::new(vec!["write docs"])));
+///     rsx! { TodoCount {} }
+/// }
+///
+/// #[component]
+/// fn TodoCount() -> Element {
+///     let todos = use_collection_context::<Vec<&'static str>>();
+///     rsx! { "{todos.len()} todos" }
+/// }
+/// ```
+pub fn provide_collection<C>(store: CollectionStore<C>) -> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    provide_context(store)
+}
+
+/// Get the store of collection type `C` provided by an ancestor with `provide_collection`
+///
+/// # Panics
+///
+/// Panics if no ancestor provides a store of this collection type, see `try_use_collection_context`.
+pub fn use_collection_context<C>() -> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    try_use_collection_context().unwrap_or_else(|| {
+        panic!(
+            "no `CollectionStore<{}>` was provided: call `provide_collection` in an ancestor component",
+            std::any::type_name::<C>()
+        )
+    })
+}
+
+/// Get the store of collection type `C` provided by an ancestor, or `None` if there is none
+pub fn try_use_collection_context<C>() -> Option<CollectionStore<C>>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    use_hook(try_consume_context::<CollectionStore<C>>)
 }
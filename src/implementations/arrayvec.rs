@@ -0,0 +1,187 @@
+use crate::collection_trait::{Collection, KeyIter, SequentialCollection};
+use crate::{CollectionError, CollectionOperation, CollectionResult, CollectionStore, Replay};
+use arrayvec::ArrayVec;
+use dioxus_signals::*;
+use std::cmp::Ordering;
+
+/// Implementation of Collection trait for `ArrayVec<T, CAP>`
+///
+/// Like `Vec`, the Key type is usize (index) and Value is the element type T.
+/// Items are stored inline and never allocate: pushing onto a full ArrayVec drops its first item,
+/// use `store.push_within_capacity(value)` to get an error instead.
+impl<T, const CAP: usize> Collection for ArrayVec<T, CAP> {
+    type Key = usize;
+    type Value = T;
+
+    const POSITIONAL_KEYS: bool = true;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        <[T]>::get(self, *key)
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        <[T]>::get_mut(self, *key)
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        if key < self.len() {
+            self[key] = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        if key == self.len() {
+            SequentialCollection::push(self, value);
+            None
+        } else if key < self.len() {
+            Some(std::mem::replace(&mut self[key], value))
+        } else {
+            None
+        }
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        ArrayVec::pop_at(self, *key)
+    }
+
+    fn find_key<F>(&self, predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    fn append_entries<I: IntoIterator<Item = (Self::Key, Self::Value)>>(&mut self, entries: I) {
+        for (_, value) in entries {
+            SequentialCollection::push(self, value);
+        }
+    }
+
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        let mut index = 0;
+        ArrayVec::retain(self, |value| {
+            let kept = keep(&index, value);
+            index += 1;
+            kept
+        });
+    }
+
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        ArrayVec::drain(self, ..).enumerate().collect()
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        (0..self.len()).collect()
+    }
+
+    fn key_iter(&self) -> Box<dyn KeyIter<Item = Self::Key>> {
+        Box::new(0..self.len())
+    }
+
+    fn last_key(&self) -> Option<Self::Key> {
+        self.len().checked_sub(1)
+    }
+
+    fn key_position(&self, key: &Self::Key) -> Option<usize> {
+        (*key < self.len()).then_some(*key)
+    }
+
+    fn len(&self) -> usize {
+        ArrayVec::len(self)
+    }
+
+    fn clear(&mut self) {
+        ArrayVec::clear(self);
+    }
+}
+
+impl<T, const CAP: usize> SequentialCollection for ArrayVec<T, CAP> {
+    /// Push an item, dropping the first one if the ArrayVec is full
+    fn push(&mut self, value: Self::Value) {
+        if self.is_full() {
+            ArrayVec::remove(self, 0);
+        }
+        ArrayVec::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<Self::Value> {
+        ArrayVec::pop(self)
+    }
+
+    fn first(&self) -> Option<&Self::Value> {
+        <[T]>::first(self)
+    }
+
+    fn swap(&mut self, key1: &Self::Key, key2: &Self::Key) {
+        if *key1 < self.len() && *key2 < self.len() {
+            <[T]>::swap(self, *key1, *key2);
+        }
+    }
+
+    /// Returns `false` as well if the ArrayVec is full
+    fn insert_at(&mut self, key: &Self::Key, value: Self::Value) -> bool {
+        *key < self.len() && ArrayVec::try_insert(self, *key, value).is_ok()
+    }
+
+    fn swap_remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        ArrayVec::swap_pop(self, *key)
+    }
+
+    fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&Self::Value, &Self::Value) -> Ordering,
+    {
+        <[T]>::sort_by(self, compare);
+    }
+
+    fn move_item(&mut self, from: &Self::Key, to: &Self::Key) -> bool {
+        if *from < self.len() && *to < self.len() {
+            let value = ArrayVec::remove(self, *from);
+            ArrayVec::insert(self, *to, value);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<T: Clone + 'static, const CAP: usize> Replay for ArrayVec<T, CAP> {
+    fn apply(
+        store: &CollectionStore<Self>,
+        operation: CollectionOperation<usize, T>,
+    ) -> CollectionResult<()> {
+        operation.apply_sequential(store)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+impl<T, const CAP: usize> CollectionStore<ArrayVec<T, CAP>>
+where
+    T: Clone + 'static,
+{
+    /// Push an item, or fail with `CapacityExceeded` instead of dropping the first item when full
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use arrayvec::ArrayVec;
+    /// use dioxus_collection_store::{CollectionError, CollectionStore};
+    ///
+    /// let store = CollectionStore::new(ArrayVec::<i32, 2>::from([1, 2]));
+    /// assert_eq!(store.push_within_capacity(3), Err(CollectionError::CapacityExceeded { capacity: 2 }));
+    /// ```
+    pub fn push_within_capacity(&self, value: T) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        if self.items().peek().is_full() {
+            return Err(CollectionError::CapacityExceeded { capacity: CAP });
+        }
+        self.push(value);
+        Ok(())
+    }
+}
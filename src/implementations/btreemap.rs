@@ -1,5 +1,11 @@
-use crate::collection_trait::Collection;
+use crate::collection_store::CollectionDataStoreExt;
+use crate::collection_trait::{BorrowedLookup, Collection};
+use crate::{CollectionItem, CollectionStore};
+use dioxus_signals::*;
 use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::ops::RangeBounds;
+use std::path::{Path, PathBuf};
 
 /// Implementation of Collection trait for BTreeMap<K, V>
 ///
@@ -37,10 +43,38 @@ where
         self.remove(key)
     }
 
+    fn find_key<F>(&self, mut predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(key, _)| key.clone())
+    }
+
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        BTreeMap::retain(self, |key, value| keep(key, value));
+    }
+
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        std::mem::take(self).into_iter().collect()
+    }
+
     fn keys(&self) -> Vec<Self::Key> {
         self.keys().cloned().collect()
     }
 
+    fn last_key(&self) -> Option<Self::Key> {
+        self.last_key_value().map(|(key, _)| key.clone())
+    }
+
+    fn key_position(&self, key: &Self::Key) -> Option<usize> {
+        self.contains_key(key).then(|| self.range(..key).count())
+    }
+
     fn len(&self) -> usize {
         self.len()
     }
@@ -53,3 +87,119 @@ where
         self.clear();
     }
 }
+
+/// Look owned keys up by their borrowed form, like `BTreeMap::get` does
+macro_rules! borrowed_lookup {
+    ($owned:ty => $borrowed:ty) => {
+        impl<V> BorrowedLookup<$borrowed> for BTreeMap<$owned, V> {
+            fn get_borrowed(&self, key: &$borrowed) -> Option<&Self::Value> {
+                self.get(key)
+            }
+        }
+    };
+}
+
+borrowed_lookup!(String => str);
+borrowed_lookup!(PathBuf => Path);
+borrowed_lookup!(OsString => OsStr);
+
+impl<T: Ord + Clone, V> BorrowedLookup<[T]> for BTreeMap<Vec<T>, V> {
+    fn get_borrowed(&self, key: &[T]) -> Option<&Self::Value> {
+        self.get(key)
+    }
+}
+
+/// Ordered queries, answered by the `BTreeMap` without scanning every key
+impl<K, V> CollectionStore<BTreeMap<K, V>>
+where
+    K: Ord + Clone + 'static,
+    V: 'static,
+{
+    /// Get the item with the smallest key (reactive)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let store = CollectionStore::new(BTreeMap::from([(3, "c"), (1, "a"), (2, "b")]));
+    /// assert_eq!(store.first_item().map(|item| item.key()), Some(1));
+    /// assert_eq!(store.last_item().map(|item| item.key()), Some(3));
+    /// ```
+    pub fn first_item(&self) -> Option<CollectionItem<BTreeMap<K, V>>> {
+        let key = self.inner.items().read().first_key_value()?.0.clone();
+        Some(CollectionItem::new(*self, key))
+    }
+
+    /// Get the item with the largest key (reactive)
+    pub fn last_item(&self) -> Option<CollectionItem<BTreeMap<K, V>>> {
+        let key = self.inner.items().read().last_key_value()?.0.clone();
+        Some(CollectionItem::new(*self, key))
+    }
+
+    /// Remove and return the entry with the smallest key, like a priority queue
+    ///
+    /// Goes through `remove`, so the selection and the item metadata are updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let jobs = CollectionStore::new(BTreeMap::from([(2, "deploy"), (1, "build")]));
+    /// assert_eq!(jobs.pop_first(), Some((1, "build")));
+    /// assert_eq!(jobs.pop_last(), Some((2, "deploy")));
+    /// assert_eq!(jobs.pop_first(), None);
+    /// ```
+    pub fn pop_first(&self) -> Option<(K, V)>
+    where
+        V: Clone,
+    {
+        let key = self.inner.items().peek().first_key_value()?.0.clone();
+        let value = self.remove(&key)?;
+        Some((key, value))
+    }
+
+    /// Remove and return the entry with the largest key
+    ///
+    /// Goes through `remove`, so the selection and the item metadata are updated.
+    pub fn pop_last(&self) -> Option<(K, V)>
+    where
+        V: Clone,
+    {
+        let key = self.inner.items().peek().last_key_value()?.0.clone();
+        let value = self.remove(&key)?;
+        Some((key, value))
+    }
+
+    /// Iterate over the items whose key is in `range`, in key order (reactive)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let store = CollectionStore::new(BTreeMap::from([(1, "a"), (2, "b"), (3, "c"), (4, "d")]));
+    /// let keys: Vec<i32> = store.range(2..4).map(|item| item.key()).collect();
+    /// assert_eq!(keys, vec![2, 3]);
+    /// ```
+    pub fn range(
+        &self,
+        range: impl RangeBounds<K>,
+    ) -> impl DoubleEndedIterator<Item = CollectionItem<BTreeMap<K, V>>> + ExactSizeIterator + '_
+    {
+        let keys: Vec<K> = self
+            .inner
+            .items()
+            .read()
+            .range(range)
+            .map(|(key, _)| key.clone())
+            .collect();
+        let store = *self;
+        keys.into_iter()
+            .map(move |key| CollectionItem::new(store, key))
+    }
+}
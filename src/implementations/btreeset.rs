@@ -0,0 +1,181 @@
+use crate::collection_store::CollectionDataStoreExt;
+use crate::collection_trait::{BorrowedLookup, Collection};
+use crate::{CollectionItem, CollectionStore};
+use dioxus_signals::*;
+use std::collections::BTreeSet;
+use std::ffi::{OsStr, OsString};
+use std::ops::RangeBounds;
+use std::path::{Path, PathBuf};
+
+/// Implementation of Collection trait for BTreeSet<T>
+///
+/// For BTreeSet, the Key type is the element and Value is `()`: elements order the set, so they can't be
+/// edited in place. Read them with `item.key()`, and replace one by removing it and inserting the new one.
+impl<T> Collection for BTreeSet<T>
+where
+    T: Ord + Clone,
+{
+    type Key = T;
+    type Value = ();
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        self.contains(key).then_some(&())
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        // `()` is zero-sized: leaking it doesn't allocate
+        self.contains(key).then(|| Box::leak(Box::new(())))
+    }
+
+    fn set(&mut self, key: Self::Key, _value: Self::Value) -> bool {
+        self.contains(&key)
+    }
+
+    fn insert(&mut self, key: Self::Key, _value: Self::Value) -> Option<Self::Value> {
+        (!self.insert(key)).then_some(())
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        self.remove(key).then_some(())
+    }
+
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        BTreeSet::retain(self, |key| keep(key, &()));
+    }
+
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        std::mem::take(self)
+            .into_iter()
+            .map(|key| (key, ()))
+            .collect()
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        self.iter().cloned().collect()
+    }
+
+    fn last_key(&self) -> Option<Self::Key> {
+        self.last().cloned()
+    }
+
+    fn key_position(&self, key: &Self::Key) -> Option<usize> {
+        self.contains(key).then(|| self.range(..key).count())
+    }
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn clear(&mut self) {
+        self.clear();
+    }
+}
+
+/// Look owned elements up by their borrowed form, like `BTreeSet::contains` does
+macro_rules! borrowed_lookup {
+    ($owned:ty => $borrowed:ty) => {
+        impl BorrowedLookup<$borrowed> for BTreeSet<$owned> {
+            fn get_borrowed(&self, key: &$borrowed) -> Option<&Self::Value> {
+                self.contains(key).then_some(&())
+            }
+        }
+    };
+}
+
+borrowed_lookup!(String => str);
+borrowed_lookup!(PathBuf => Path);
+borrowed_lookup!(OsString => OsStr);
+
+impl<T: Ord + Clone> BorrowedLookup<[T]> for BTreeSet<Vec<T>> {
+    fn get_borrowed(&self, key: &[T]) -> Option<&Self::Value> {
+        self.contains(key).then_some(&())
+    }
+}
+
+/// Ordered queries, answered by the `BTreeSet` without scanning every element
+impl<T> CollectionStore<BTreeSet<T>>
+where
+    T: Ord + Clone + 'static,
+{
+    /// Get the item with the smallest element (reactive)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::BTreeSet;
+    ///
+    /// let store = CollectionStore::new(BTreeSet::from([3, 1, 2]));
+    /// assert_eq!(store.first_item().map(|item| item.key()), Some(1));
+    /// assert_eq!(store.last_item().map(|item| item.key()), Some(3));
+    /// ```
+    pub fn first_item(&self) -> Option<CollectionItem<BTreeSet<T>>> {
+        let key = self.inner.items().read().first()?.clone();
+        Some(CollectionItem::new(*self, key))
+    }
+
+    /// Get the item with the largest element (reactive)
+    pub fn last_item(&self) -> Option<CollectionItem<BTreeSet<T>>> {
+        let key = self.inner.items().read().last()?.clone();
+        Some(CollectionItem::new(*self, key))
+    }
+
+    /// Remove and return the smallest element, like a priority queue
+    ///
+    /// Goes through `remove`, so the selection and the item metadata are updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::BTreeSet;
+    ///
+    /// let jobs = CollectionStore::new(BTreeSet::from([2, 1]));
+    /// assert_eq!(jobs.pop_first(), Some(1));
+    /// assert_eq!(jobs.pop_last(), Some(2));
+    /// assert_eq!(jobs.pop_first(), None);
+    /// ```
+    pub fn pop_first(&self) -> Option<T> {
+        let key = self.inner.items().peek().first()?.clone();
+        self.remove(&key)?;
+        Some(key)
+    }
+
+    /// Remove and return the largest element
+    ///
+    /// Goes through `remove`, so the selection and the item metadata are updated.
+    pub fn pop_last(&self) -> Option<T> {
+        let key = self.inner.items().peek().last()?.clone();
+        self.remove(&key)?;
+        Some(key)
+    }
+
+    /// Iterate over the items whose element is in `range`, in order (reactive)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::BTreeSet;
+    ///
+    /// let store = CollectionStore::new(BTreeSet::from([1, 2, 3, 4]));
+    /// let keys: Vec<i32> = store.range(2..4).map(|item| item.key()).collect();
+    /// assert_eq!(keys, vec![2, 3]);
+    /// ```
+    pub fn range(
+        &self,
+        range: impl RangeBounds<T>,
+    ) -> impl DoubleEndedIterator<Item = CollectionItem<BTreeSet<T>>> + ExactSizeIterator + '_ {
+        let keys: Vec<T> = self.inner.items().read().range(range).cloned().collect();
+        let store = *self;
+        keys.into_iter()
+            .map(move |key| CollectionItem::new(store, key))
+    }
+}
@@ -1,6 +1,8 @@
-use crate::collection_trait::Collection;
+use crate::collection_trait::{BorrowedLookup, Collection};
 use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
 use std::hash::Hash;
+use std::path::{Path, PathBuf};
 
 /// Implementation of Collection trait for HashMap<K, V>
 ///
@@ -38,10 +40,34 @@ where
         self.remove(key)
     }
 
+    fn find_key<F>(&self, mut predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(key, _)| key.clone())
+    }
+
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        HashMap::retain(self, |key, value| keep(key, value));
+    }
+
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        std::mem::take(self).into_iter().collect()
+    }
+
     fn keys(&self) -> Vec<Self::Key> {
         self.keys().cloned().collect()
     }
 
+    fn key_position(&self, key: &Self::Key) -> Option<usize> {
+        self.keys().position(|k| k == key)
+    }
+
     fn len(&self) -> usize {
         self.len()
     }
@@ -54,3 +80,24 @@ where
         self.clear();
     }
 }
+
+/// Look owned keys up by their borrowed form, like `HashMap::get` does
+macro_rules! borrowed_lookup {
+    ($owned:ty => $borrowed:ty) => {
+        impl<V> BorrowedLookup<$borrowed> for HashMap<$owned, V> {
+            fn get_borrowed(&self, key: &$borrowed) -> Option<&Self::Value> {
+                self.get(key)
+            }
+        }
+    };
+}
+
+borrowed_lookup!(String => str);
+borrowed_lookup!(PathBuf => Path);
+borrowed_lookup!(OsString => OsStr);
+
+impl<T: Eq + Hash + Clone, V> BorrowedLookup<[T]> for HashMap<Vec<T>, V> {
+    fn get_borrowed(&self, key: &[T]) -> Option<&Self::Value> {
+        self.get(key)
+    }
+}
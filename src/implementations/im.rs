@@ -0,0 +1,273 @@
+use crate::collection_trait::{Collection, KeyIter, SequentialCollection};
+use crate::{CollectionOperation, CollectionResult, CollectionStore, Replay};
+use std::hash::Hash;
+
+/// Implementation of Collection trait for `im::Vector<T>`
+///
+/// Like `Vec`, the Key type is usize (index) and Value is the element type T.
+/// Cloning the collection is O(1), which makes snapshots for undo cheap.
+impl<T> Collection for im::Vector<T>
+where
+    T: Clone,
+{
+    type Key = usize;
+    type Value = T;
+
+    const POSITIONAL_KEYS: bool = true;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        im::Vector::get(self, *key)
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        im::Vector::get_mut(self, *key)
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        if key < self.len() {
+            im::Vector::set(self, key, value);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        if key == self.len() {
+            self.push_back(value);
+            None
+        } else if key < self.len() {
+            Some(im::Vector::set(self, key, value))
+        } else {
+            None
+        }
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        (*key < self.len()).then(|| im::Vector::remove(self, *key))
+    }
+
+    fn find_key<F>(&self, predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    fn append_entries<I: IntoIterator<Item = (Self::Key, Self::Value)>>(&mut self, entries: I) {
+        for (_, value) in entries {
+            self.push_back(value);
+        }
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        (0..self.len()).collect()
+    }
+
+    fn key_iter(&self) -> Box<dyn KeyIter<Item = Self::Key>> {
+        Box::new(0..self.len())
+    }
+
+    fn last_key(&self) -> Option<Self::Key> {
+        self.len().checked_sub(1)
+    }
+
+    fn key_position(&self, key: &Self::Key) -> Option<usize> {
+        (*key < self.len()).then_some(*key)
+    }
+
+    fn len(&self) -> usize {
+        im::Vector::len(self)
+    }
+
+    fn clear(&mut self) {
+        im::Vector::clear(self);
+    }
+}
+
+impl<T> SequentialCollection for im::Vector<T>
+where
+    T: Clone,
+{
+    fn push(&mut self, value: Self::Value) {
+        self.push_back(value);
+    }
+
+    fn pop(&mut self) -> Option<Self::Value> {
+        self.pop_back()
+    }
+
+    fn first(&self) -> Option<&Self::Value> {
+        self.front()
+    }
+
+    fn swap(&mut self, key1: &Self::Key, key2: &Self::Key) {
+        if *key1 < self.len() && *key2 < self.len() {
+            im::Vector::swap(self, *key1, *key2);
+        }
+    }
+
+    fn insert_at(&mut self, key: &Self::Key, value: Self::Value) -> bool {
+        if *key < self.len() {
+            im::Vector::insert(self, *key, value);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn move_item(&mut self, from: &Self::Key, to: &Self::Key) -> bool {
+        if *from < self.len() && *to < self.len() {
+            let value = im::Vector::remove(self, *from);
+            im::Vector::insert(self, *to, value);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Implementation of Collection trait for `im::HashMap<K, V>`
+///
+/// For im::HashMap, the Key type is K and Value is V.
+impl<K, V> Collection for im::HashMap<K, V>
+where
+    K: Eq + Hash + Clone,
+    V: Clone,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        im::HashMap::get(self, key)
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        im::HashMap::get_mut(self, key)
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        match im::HashMap::get_mut(self, &key) {
+            Some(current) => {
+                *current = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        im::HashMap::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        im::HashMap::remove(self, key)
+    }
+
+    fn find_key<F>(&self, mut predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(key, _)| key.clone())
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        im::HashMap::keys(self).cloned().collect()
+    }
+
+    fn len(&self) -> usize {
+        im::HashMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        im::HashMap::clear(self);
+    }
+}
+
+/// Implementation of Collection trait for `im::OrdMap<K, V>`
+///
+/// For im::OrdMap, the Key type is K and Value is V, iterated in key order like `BTreeMap`.
+impl<K, V> Collection for im::OrdMap<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        im::OrdMap::get(self, key)
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        im::OrdMap::get_mut(self, key)
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        match im::OrdMap::get_mut(self, &key) {
+            Some(current) => {
+                *current = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        im::OrdMap::insert(self, key, value)
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        im::OrdMap::remove(self, key)
+    }
+
+    fn find_key<F>(&self, mut predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(key, _)| key.clone())
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        im::OrdMap::keys(self).cloned().collect()
+    }
+
+    fn last_key(&self) -> Option<Self::Key> {
+        self.get_max().map(|(key, _)| key.clone())
+    }
+
+    fn len(&self) -> usize {
+        im::OrdMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        im::OrdMap::clear(self);
+    }
+}
+
+impl<T: Clone + 'static> Replay for im::Vector<T> {
+    fn apply(
+        store: &CollectionStore<Self>,
+        operation: CollectionOperation<usize, T>,
+    ) -> CollectionResult<()> {
+        operation.apply_sequential(store)
+    }
+}
+
+impl<K, V> Replay for im::HashMap<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: Clone + 'static,
+{
+}
+
+impl<K, V> Replay for im::OrdMap<K, V>
+where
+    K: Ord + Clone + 'static,
+    V: Clone + 'static,
+{
+}
@@ -1,3 +1,12 @@
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
 mod btreemap;
+mod btreeset;
 mod hashmap;
+#[cfg(feature = "im")]
+mod im;
+#[cfg(feature = "slotmap")]
+mod slotmap;
+#[cfg(feature = "smallvec")]
+mod smallvec;
 mod vec;
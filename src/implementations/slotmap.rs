@@ -0,0 +1,194 @@
+use crate::collection_trait::Collection;
+use crate::selection_behavior::Addition;
+use crate::{CollectionDataStoreExt, CollectionOperation, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+use slotmap::{DenseSlotMap, Key, SlotMap};
+
+/// Implementation of Collection trait for `SlotMap<K, V>`
+///
+/// For SlotMap, the Key type is K and Value is V. Keys are generated by the map and stay valid
+/// until their item is removed, whatever else is added or removed: use `store.insert_value(value)`
+/// to add items. `insert` only replaces the value of an existing key, and refuses unknown keys.
+impl<K: Key, V> Collection for SlotMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        SlotMap::get(self, *key)
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        SlotMap::get_mut(self, *key)
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        match SlotMap::get_mut(self, key) {
+            Some(current) => {
+                *current = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        SlotMap::get_mut(self, key).map(|current| std::mem::replace(current, value))
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        SlotMap::remove(self, *key)
+    }
+
+    fn contains_key(&self, key: &Self::Key) -> bool {
+        SlotMap::contains_key(self, *key)
+    }
+
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        SlotMap::retain(self, |key, value| keep(&key, value));
+    }
+
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        SlotMap::drain(self).collect()
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        SlotMap::keys(self).collect()
+    }
+
+    fn len(&self) -> usize {
+        SlotMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        SlotMap::clear(self);
+    }
+}
+
+/// Implementation of Collection trait for `DenseSlotMap<K, V>`
+///
+/// Same keys as `SlotMap`, with values stored contiguously for faster iteration.
+impl<K: Key, V> Collection for DenseSlotMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        DenseSlotMap::get(self, *key)
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        DenseSlotMap::get_mut(self, *key)
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        match DenseSlotMap::get_mut(self, key) {
+            Some(current) => {
+                *current = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        DenseSlotMap::get_mut(self, key).map(|current| std::mem::replace(current, value))
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        DenseSlotMap::remove(self, *key)
+    }
+
+    fn contains_key(&self, key: &Self::Key) -> bool {
+        DenseSlotMap::contains_key(self, *key)
+    }
+
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        DenseSlotMap::retain(self, |key, value| keep(&key, value));
+    }
+
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        DenseSlotMap::drain(self).collect()
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        DenseSlotMap::keys(self).collect()
+    }
+
+    fn len(&self) -> usize {
+        DenseSlotMap::len(self)
+    }
+
+    fn clear(&mut self) {
+        DenseSlotMap::clear(self);
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Add a value with `insert`, which generates its key
+    fn insert_generated(
+        &self,
+        value: C::Value,
+        insert: impl FnOnce(&mut C, C::Value) -> C::Key,
+    ) -> CollectionResult<C::Key> {
+        self.ensure_unlocked()?;
+        let key = insert(&mut self.inner.items().write(), value);
+        self.record(|clone| CollectionOperation::Insert {
+            key: key.clone(),
+            value: self.inner.items().peek().get(&key).map(clone).unwrap(),
+        });
+        self.select_added(&key, Addition::Insert);
+        self.track_added(&key);
+        self.finish_mutation("insert_value");
+        Ok(key)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "slotmap")))]
+impl<K, V> CollectionStore<SlotMap<K, V>>
+where
+    K: Key + 'static,
+    V: Clone + 'static,
+{
+    /// Add a value and return its generated key
+    ///
+    /// The key stays valid until the item is removed, unlike a `Vec` index.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use slotmap::{DefaultKey, SlotMap};
+    ///
+    /// let store = CollectionStore::new(SlotMap::<DefaultKey, &str>::new());
+    /// let ada = store.insert_value("Ada").unwrap();
+    /// let alan = store.insert_value("Alan").unwrap();
+    /// store.remove(&ada);
+    /// assert_eq!(store.peek_value(&alan), Some("Alan"));
+    /// ```
+    pub fn insert_value(&self, value: V) -> CollectionResult<K> {
+        self.insert_generated(value, |map, value| SlotMap::insert(map, value))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "slotmap")))]
+impl<K, V> CollectionStore<DenseSlotMap<K, V>>
+where
+    K: Key + 'static,
+    V: Clone + 'static,
+{
+    /// Add a value and return its generated key
+    ///
+    /// The key stays valid until the item is removed, unlike a `Vec` index.
+    pub fn insert_value(&self, value: V) -> CollectionResult<K> {
+        self.insert_generated(value, |map, value| DenseSlotMap::insert(map, value))
+    }
+}
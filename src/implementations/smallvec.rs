@@ -0,0 +1,161 @@
+use crate::collection_trait::{Collection, KeyIter, SequentialCollection};
+use crate::{CollectionOperation, CollectionResult, CollectionStore, Replay};
+use smallvec::{Array, SmallVec};
+use std::cmp::Ordering;
+
+/// Implementation of Collection trait for `SmallVec<[T; N]>`
+///
+/// Like `Vec`, the Key type is usize (index) and Value is the element type T.
+/// Up to N items are stored inline, pushing more moves them to the heap.
+impl<A: Array> Collection for SmallVec<A> {
+    type Key = usize;
+    type Value = A::Item;
+
+    const POSITIONAL_KEYS: bool = true;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        <[A::Item]>::get(self, *key)
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        <[A::Item]>::get_mut(self, *key)
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        if key < self.len() {
+            self[key] = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        if key == self.len() {
+            self.push(value);
+            None
+        } else if key < self.len() {
+            Some(std::mem::replace(&mut self[key], value))
+        } else {
+            None
+        }
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        (*key < self.len()).then(|| SmallVec::remove(self, *key))
+    }
+
+    fn find_key<F>(&self, predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    fn append_entries<I: IntoIterator<Item = (Self::Key, Self::Value)>>(&mut self, entries: I) {
+        std::iter::Extend::extend(self, entries.into_iter().map(|(_, value)| value));
+    }
+
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        let mut index = 0;
+        SmallVec::retain(self, |value| {
+            let kept = keep(&index, value);
+            index += 1;
+            kept
+        });
+    }
+
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        SmallVec::drain(self, ..).enumerate().collect()
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        (0..self.len()).collect()
+    }
+
+    fn key_iter(&self) -> Box<dyn KeyIter<Item = Self::Key>> {
+        Box::new(0..self.len())
+    }
+
+    fn last_key(&self) -> Option<Self::Key> {
+        self.len().checked_sub(1)
+    }
+
+    fn key_position(&self, key: &Self::Key) -> Option<usize> {
+        (*key < self.len()).then_some(*key)
+    }
+
+    fn len(&self) -> usize {
+        SmallVec::len(self)
+    }
+
+    fn clear(&mut self) {
+        SmallVec::clear(self);
+    }
+}
+
+impl<A: Array> SequentialCollection for SmallVec<A> {
+    fn push(&mut self, value: Self::Value) {
+        SmallVec::push(self, value);
+    }
+
+    fn pop(&mut self) -> Option<Self::Value> {
+        SmallVec::pop(self)
+    }
+
+    fn first(&self) -> Option<&Self::Value> {
+        <[A::Item]>::first(self)
+    }
+
+    fn swap(&mut self, key1: &Self::Key, key2: &Self::Key) {
+        if *key1 < self.len() && *key2 < self.len() {
+            <[A::Item]>::swap(self, *key1, *key2);
+        }
+    }
+
+    fn insert_at(&mut self, key: &Self::Key, value: Self::Value) -> bool {
+        if *key < self.len() {
+            SmallVec::insert(self, *key, value);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn swap_remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        (*key < self.len()).then(|| SmallVec::swap_remove(self, *key))
+    }
+
+    fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&Self::Value, &Self::Value) -> Ordering,
+    {
+        <[A::Item]>::sort_by(self, compare);
+    }
+
+    fn move_item(&mut self, from: &Self::Key, to: &Self::Key) -> bool {
+        if *from < self.len() && *to < self.len() {
+            let value = SmallVec::remove(self, *from);
+            SmallVec::insert(self, *to, value);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<A> Replay for SmallVec<A>
+where
+    A: Array + 'static,
+    A::Item: Clone + 'static,
+{
+    fn apply(
+        store: &CollectionStore<Self>,
+        operation: CollectionOperation<usize, A::Item>,
+    ) -> CollectionResult<()> {
+        operation.apply_sequential(store)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::collection_trait::{Collection, SequentialCollection};
+use crate::collection_trait::{Collection, KeyIter, SequentialCollection};
+use std::cmp::Ordering;
 
 /// Implementation of Collection trait for `Vec<T>`
 ///
@@ -7,6 +8,8 @@ impl<T> Collection for Vec<T> {
     type Key = usize;
     type Value = T;
 
+    const POSITIONAL_KEYS: bool = true;
+
     fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
         <[T]>::get(self, *key)
     }
@@ -46,10 +49,49 @@ impl<T> Collection for Vec<T> {
         }
     }
 
+    fn find_key<F>(&self, predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.iter().position(predicate)
+    }
+
+    fn append_entries<I: IntoIterator<Item = (Self::Key, Self::Value)>>(&mut self, entries: I) {
+        std::iter::Extend::extend(self, entries.into_iter().map(|(_, value)| value));
+    }
+
+    fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        let mut index = 0;
+        Vec::retain(self, |value| {
+            let kept = keep(&index, value);
+            index += 1;
+            kept
+        });
+    }
+
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        Vec::drain(self, ..).enumerate().collect()
+    }
+
     fn keys(&self) -> Vec<Self::Key> {
         (0..self.len()).collect()
     }
 
+    fn key_iter(&self) -> Box<dyn KeyIter<Item = Self::Key>> {
+        Box::new(0..self.len())
+    }
+
+    fn last_key(&self) -> Option<Self::Key> {
+        self.len().checked_sub(1)
+    }
+
+    fn key_position(&self, key: &Self::Key) -> Option<usize> {
+        (*key < self.len()).then_some(*key)
+    }
+
     fn len(&self) -> usize {
         self.len()
     }
@@ -78,4 +120,38 @@ impl<T> SequentialCollection for Vec<T> {
             <[T]>::swap(self, *key1, *key2);
         }
     }
+
+    fn insert_at(&mut self, key: &Self::Key, value: Self::Value) -> bool {
+        if *key < self.len() {
+            Vec::insert(self, *key, value);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn swap_remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        if *key < self.len() {
+            Some(Vec::swap_remove(self, *key))
+        } else {
+            None
+        }
+    }
+
+    fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&Self::Value, &Self::Value) -> Ordering,
+    {
+        <[T]>::sort_by(self, compare);
+    }
+
+    fn move_item(&mut self, from: &Self::Key, to: &Self::Key) -> bool {
+        if *from < self.len() && *to < self.len() {
+            let value = Vec::remove(self, *from);
+            Vec::insert(self, *to, value);
+            true
+        } else {
+            false
+        }
+    }
 }
@@ -0,0 +1,356 @@
+//! Secondary index on a derived value
+//!
+//! `store.index_by(|task| task.tag.clone())` maintains a multimap from the derived value to the keys of the
+//! matching items, so lookups (typically many per render) are hash lookups instead of a full scan. The index
+//! is updated from the operations of the store: only the items they touch are derived again. Changes that
+//! shift the keys of a positional collection (inserting or removing before its end, reordering), and writes
+//! made around the store API, rebuild it instead.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionItem, CollectionOperation, CollectionStore};
+use dioxus_signals::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Clones the values of the collection `C`
+type CloneValue<C> = fn(&<C as Collection>::Value) -> <C as Collection>::Value;
+
+/// Changes of a store not applied to an index yet, noted as the store records its operations
+pub(crate) struct IndexChanges<K> {
+    /// Keys whose item was added, changed or removed
+    touched: Vec<K>,
+    /// Position of the first item pushed at the end, whose key is only known after the write
+    pushed_from: Option<usize>,
+    /// Keys shifted or changed in a way the index can't follow
+    rebuild: bool,
+}
+
+impl<K> Default for IndexChanges<K> {
+    fn default() -> Self {
+        Self {
+            touched: Vec::new(),
+            pushed_from: None,
+            rebuild: false,
+        }
+    }
+}
+
+impl<K> IndexChanges<K> {
+    fn is_empty(&self) -> bool {
+        self.touched.is_empty() && self.pushed_from.is_none() && !self.rebuild
+    }
+}
+
+/// The changes of an index, registered in its store until the index is dropped
+pub(crate) struct IndexHook<C>
+where
+    C: Collection + 'static,
+{
+    pub(crate) clone_value: CloneValue<C>,
+    changes: CopyValue<IndexChanges<C::Key>>,
+}
+
+impl<C> IndexHook<C>
+where
+    C: Collection + 'static,
+{
+    fn is_alive(&self) -> bool {
+        self.changes.try_peek_unchecked().is_ok()
+    }
+}
+
+/// Groups of an index, with the derived value of each key to find its group again
+struct IndexState<G, K> {
+    groups: HashMap<G, Vec<K>>,
+    derived: HashMap<K, G>,
+    /// Incremented each time the groups change, to notify the readers
+    version: u64,
+}
+
+impl<G, K> IndexState<G, K>
+where
+    G: Hash + Eq + Clone,
+    K: Hash + Eq + Clone,
+{
+    /// Derive every item again
+    fn rebuild<C>(&mut self, items: &C, derive: &impl Fn(&C::Value) -> G)
+    where
+        C: Collection<Key = K>,
+    {
+        self.groups.clear();
+        self.derived.clear();
+        for key in items.keys() {
+            if let Some(value) = items.get(&key) {
+                self.add(key, derive(value));
+            }
+        }
+    }
+
+    /// Derive the item of `key` again, or drop it if it was removed
+    fn refresh<C>(&mut self, items: &C, key: K, derive: &impl Fn(&C::Value) -> G) -> bool
+    where
+        C: Collection<Key = K>,
+    {
+        let group = items.get(&key).map(derive);
+        if self.derived.get(&key) == group.as_ref() {
+            return false;
+        }
+        if let Some(previous) = self.derived.remove(&key)
+            && let Some(keys) = self.groups.get_mut(&previous)
+        {
+            keys.retain(|k| *k != key);
+            if keys.is_empty() {
+                self.groups.remove(&previous);
+            }
+        }
+        if let Some(group) = group {
+            self.add(key, group);
+        }
+        true
+    }
+
+    fn add(&mut self, key: K, group: G) {
+        self.groups
+            .entry(group.clone())
+            .or_default()
+            .push(key.clone());
+        self.derived.insert(key, group);
+    }
+}
+
+/// Reactive multimap from a derived value to the keys of the items it was derived from
+///
+/// Created with `CollectionStore::index_by`. Reads subscribe to the index, which updates when the store changes.
+pub struct CollectionIndex<G, C>
+where
+    G: 'static,
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    state: CopyValue<IndexState<G, C::Key>>,
+    /// Version of the groups, updated when the store changes
+    version: Memo<u64>,
+}
+
+impl<G, C> Copy for CollectionIndex<G, C>
+where
+    G: 'static,
+    C: Collection + 'static,
+{
+}
+
+impl<G, C> Clone for CollectionIndex<G, C>
+where
+    G: 'static,
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G, C> PartialEq for CollectionIndex<G, C>
+where
+    G: 'static,
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner && self.state == other.state
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Index the items by a value derived from them
+    ///
+    /// Create it once (in `use_hook` for instance), like any memo. The index belongs to the current component.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let tasks = CollectionStore::new(vec![("docs", "write"), ("bug", "fix"), ("docs", "review")]);
+    /// let by_tag = tasks.index_by(|(tag, _)| *tag);
+    /// assert_eq!(by_tag.get(&"docs"), vec![0, 2]);
+    /// ```
+    pub fn index_by<G>(&self, derive: impl Fn(&C::Value) -> G + 'static) -> CollectionIndex<G, C>
+    where
+        G: Hash + Eq + Clone + 'static,
+        C::Key: Hash + Eq,
+        C::Value: Clone,
+    {
+        let store = *self;
+        let changes = CopyValue::new(IndexChanges::default());
+        self.inner.indexes().write().push(IndexHook {
+            clone_value: C::Value::clone,
+            changes,
+        });
+        let state = CopyValue::new(IndexState {
+            groups: HashMap::new(),
+            derived: HashMap::new(),
+            version: 0,
+        });
+        let version = Memo::new(move || {
+            let items = store.items();
+            let items = items.read();
+            let changes = std::mem::take(&mut *changes.write_unchecked());
+            let mut state = state.write_unchecked();
+            // Without noted changes, the items were written around the store API
+            let changed = if state.version == 0 || changes.rebuild || changes.is_empty() {
+                state.rebuild(&*items, &derive);
+                true
+            } else {
+                let mut changed = false;
+                for key in changes.touched {
+                    changed |= state.refresh(&*items, key, &derive);
+                }
+                if let Some(from) = changes.pushed_from {
+                    for key in items.keys().into_iter().skip(from) {
+                        changed |= state.refresh(&*items, key, &derive);
+                    }
+                }
+                changed
+            };
+            if changed {
+                state.version += 1;
+            }
+            state.version
+        });
+        CollectionIndex {
+            store,
+            state,
+            version,
+        }
+    }
+
+    /// Get the value cloner of the indexes, if the store is indexed
+    pub(crate) fn index_clone_value(&self) -> Option<CloneValue<C>> {
+        let mut indexes = self.inner.indexes();
+        if indexes.peek().iter().any(|index| !index.is_alive()) {
+            indexes.write().retain(IndexHook::is_alive);
+        }
+        let indexes = indexes.peek();
+        indexes.first().map(|index| index.clone_value)
+    }
+
+    /// Note an operation about to be applied in the changes of the indexes
+    pub(crate) fn note_index_changes(&self, operation: &CollectionOperation<C::Key, C::Value>) {
+        let indexes = self.inner.indexes();
+        let indexes = indexes.peek();
+        if indexes.is_empty() {
+            return;
+        }
+        let items = self.inner.items();
+        for index in indexes.iter() {
+            let mut changes = index.changes.write_unchecked();
+            let mut touch = |key: &C::Key| changes.touched.push(key.clone());
+            match operation {
+                CollectionOperation::Set { key, .. } => touch(key),
+                CollectionOperation::Insert { key, .. } | CollectionOperation::Remove { key }
+                    if !C::POSITIONAL_KEYS =>
+                {
+                    touch(key)
+                }
+                CollectionOperation::RemoveMany { keys } if !C::POSITIONAL_KEYS => {
+                    keys.iter().for_each(touch)
+                }
+                CollectionOperation::Extend { items: entries }
+                | CollectionOperation::AppendEntries { items: entries }
+                    if !C::POSITIONAL_KEYS =>
+                {
+                    entries.iter().for_each(|(key, _)| touch(key))
+                }
+                CollectionOperation::RenameKey { from, to }
+                | CollectionOperation::Swap {
+                    key1: from,
+                    key2: to,
+                } => {
+                    touch(from);
+                    touch(to);
+                }
+                CollectionOperation::Pop => {
+                    if let Some(last) = items.peek().last_key() {
+                        touch(&last);
+                    }
+                }
+                CollectionOperation::SwapRemove { key } => {
+                    touch(key);
+                    if let Some(last) = items.peek().last_key() {
+                        touch(&last);
+                    }
+                }
+                CollectionOperation::Push { .. } | CollectionOperation::AppendEntries { .. } => {
+                    changes
+                        .pushed_from
+                        .get_or_insert_with(|| items.peek().len());
+                }
+                CollectionOperation::Select { .. }
+                | CollectionOperation::ClearSelection
+                | CollectionOperation::SelectRange { .. } => {}
+                _ => changes.rebuild = true,
+            }
+        }
+    }
+
+    /// Note an item written in place, around the operations, in the changes of the indexes
+    pub(crate) fn note_index_write(&self, key: &C::Key) {
+        for index in self.inner.indexes().peek().iter() {
+            if let Ok(mut changes) = index.changes.try_write_unchecked() {
+                changes.touched.push(key.clone());
+            }
+        }
+    }
+}
+
+impl<G, C> CollectionIndex<G, C>
+where
+    G: Hash + Eq + 'static,
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Get the keys of the items deriving `group`, in the order they got it (reactive)
+    ///
+    /// Items are in collection order when the index is built, then the items joining the group are added last.
+    pub fn get(&self, group: &G) -> Vec<C::Key> {
+        self.version.read();
+        self.state
+            .peek()
+            .groups
+            .get(group)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Get the items deriving `group`, in the order of `get` (reactive)
+    pub fn items(&self, group: &G) -> Vec<CollectionItem<C>> {
+        self.get(group)
+            .into_iter()
+            .map(|key| self.store.get(&key))
+            .collect()
+    }
+
+    /// Get the number of items deriving `group` (reactive)
+    pub fn count(&self, group: &G) -> usize {
+        self.version.read();
+        self.state.peek().groups.get(group).map_or(0, Vec::len)
+    }
+
+    /// Check if any item derives `group` (reactive)
+    pub fn contains(&self, group: &G) -> bool {
+        self.version.read();
+        self.state.peek().groups.contains_key(group)
+    }
+
+    /// Get every derived value, in no particular order (reactive)
+    pub fn groups(&self) -> Vec<G>
+    where
+        G: Clone,
+    {
+        self.version.read();
+        self.state.peek().groups.keys().cloned().collect()
+    }
+}
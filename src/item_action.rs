@@ -0,0 +1,225 @@
+//! Async actions on a single item
+//!
+//! Row buttons like "send", "retry" or "archive" start an async call for their item, and need to show a
+//! spinner while it runs and its error once it fails. `item.run_action(|value| async { ... })` runs the
+//! action in a task and keeps its status in the item metadata, so `item.is_running()` and `item.last_error()`
+//! follow the item when the collection changes, without a map of statuses next to the store.
+//!
+//! ```rust,ignore
+//! rsx! {
+//!     button {
+//!         disabled: item.is_running(),
+//!         onclick: move |_| { item.run_action(|mail| async move { api.send(mail).await }).ok(); },
+//!         if item.is_running() { "Sending..." } else { "Send" }
+//!     }
+//!     if let Some(error) = item.last_error() { span { class: "error", "{error}" } }
+//! }
+//! ```
+
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_core::{Task, spawn};
+use dioxus_signals::*;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Metadata holding the action status of an item, removed once both fields are `None`
+#[derive(Default)]
+struct ActionStatus {
+    /// Identifier of the running action, so an older action finishing doesn't end the status of a newer one
+    running: Option<u64>,
+    /// Error of the last failed action
+    error: Option<String>,
+}
+
+/// Source of the action identifiers
+static NEXT_ACTION: AtomicU64 = AtomicU64::new(0);
+
+/// A running item action, returned by `run_action`
+///
+/// The handle is `Copy`, and the action is owned by the component that started it.
+pub struct ActionHandle<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    token: u64,
+    task: Task,
+    running: Signal<bool>,
+}
+
+impl<C> Copy for ActionHandle<C> where C: Collection + 'static {}
+
+impl<C> Clone for ActionHandle<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> PartialEq for ActionHandle<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.task == other.task
+    }
+}
+
+impl<C> ActionHandle<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Check if this action is still running (reactive)
+    ///
+    /// Unlike `item.is_running()`, this doesn't follow the newer actions of the item.
+    pub fn is_running(&self) -> bool {
+        *self.running.read()
+    }
+
+    /// Stop the action
+    ///
+    /// The item isn't running anymore, unless a newer action started.
+    pub fn cancel(&self) {
+        self.task.cancel();
+        let mut running = self.running;
+        if *running.peek() {
+            running.set(false);
+            self.store.finish_action(self.token, None);
+        }
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Run an async action on an item, tracking its status in the item metadata
+    ///
+    /// `action` receives a copy of the value. Its error is kept, as text, until the next action of the item
+    /// starts. Starting an action while another one runs doesn't stop it, but the item status follows the
+    /// newest. Must be called from a Dioxus runtime, the action runs in a task of the current component.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let outbox = CollectionStore::new(vec!["hello".to_string()]);
+    /// outbox
+    ///     .run_action(&0, |mail| async move {
+    ///         if mail.is_empty() { Err("empty mail") } else { Ok(()) }
+    ///     })
+    ///     .unwrap();
+    /// assert!(outbox.is_action_running(&0));
+    /// ```
+    pub fn run_action<F, E>(
+        &self,
+        key: &C::Key,
+        action: impl FnOnce(C::Value) -> F,
+    ) -> CollectionResult<ActionHandle<C>>
+    where
+        C::Value: Clone,
+        F: Future<Output = Result<(), E>> + 'static,
+        E: Display,
+    {
+        let value = self.peek_value(key).ok_or(CollectionError::KeyNotFound)?;
+        let token = NEXT_ACTION.fetch_add(1, Ordering::Relaxed);
+        self.write_meta_table(|statuses| {
+            statuses.insert(
+                key.clone(),
+                ActionStatus {
+                    running: Some(token),
+                    error: None,
+                },
+            )
+        });
+
+        let future = action(value);
+        let store = *self;
+        let mut running = Signal::new(true);
+        let task = spawn(async move {
+            let result = future.await;
+            running.set(false);
+            store.finish_action(token, result.err().map(|error| error.to_string()));
+        });
+        Ok(ActionHandle {
+            store,
+            token,
+            task,
+            running,
+        })
+    }
+
+    /// Check if an action of an item is running (reactive)
+    pub fn is_action_running(&self, key: &C::Key) -> bool {
+        self.meta_of::<ActionStatus>(key).read().running.is_some()
+    }
+
+    /// Get the error of the last failed action of an item (reactive)
+    pub fn action_error(&self, key: &C::Key) -> Option<String> {
+        self.meta_of::<ActionStatus>(key).read().error.clone()
+    }
+
+    /// End the status of the action `token`, unless a newer action started or its item is gone
+    fn finish_action(&self, token: u64, error: Option<String>) {
+        let key = self.peek_meta_table(|statuses: Option<&HashMap<C::Key, ActionStatus>>| {
+            statuses?
+                .iter()
+                .find(|(_, status)| status.running == Some(token))
+                .map(|(key, _)| key.clone())
+        });
+        let Some(key) = key else {
+            return;
+        };
+        self.write_meta_table(|statuses: &mut HashMap<C::Key, ActionStatus>| match error {
+            Some(error) => {
+                statuses.insert(
+                    key,
+                    ActionStatus {
+                        running: None,
+                        error: Some(error),
+                    },
+                );
+            }
+            None => {
+                statuses.remove(&key);
+            }
+        });
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Run an async action on this item, see `CollectionStore::run_action`
+    pub fn run_action<F, E>(
+        &self,
+        action: impl FnOnce(C::Value) -> F,
+    ) -> CollectionResult<ActionHandle<C>>
+    where
+        C::Value: Clone,
+        F: Future<Output = Result<(), E>> + 'static,
+        E: Display,
+    {
+        self.store.run_action(&self.key, action)
+    }
+
+    /// Check if an action of this item is running (reactive)
+    pub fn is_running(&self) -> bool {
+        self.store.is_action_running(&self.key)
+    }
+
+    /// Get the error of the last failed action of this item (reactive)
+    pub fn last_error(&self) -> Option<String> {
+        self.store.action_error(&self.key)
+    }
+}
@@ -0,0 +1,534 @@
+//! Per-item metadata sidecar
+//!
+//! UI state like "editing", "expanded" or "pinned" often doesn't belong to the domain value type.
+//! Each item of a store can carry metadata of any type `M` instead (`store.meta_of::<M>(&key)`), or string
+//! entries with `store.meta(&key)`. Each metadata type is kept in its own map from the item keys, next to the
+//! collection, and moved along with the item when the collection changes structurally (insert, remove, swap...).
+//!
+//! ```rust,ignore
+//! #[derive(Default)]
+//! struct RowState {
+//!     expanded: bool,
+//!     draft: Option<String>,
+//! }
+//!
+//! store.meta_of::<RowState>(&key).write().expanded = true;
+//! rsx! { if item.meta_of::<RowState>().read().expanded { Details { item } } }
+//! ```
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::tracking::follow_key;
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus_signals::*;
+use dioxus_stores::scope::SelectorScope;
+use std::any::{Any, TypeId};
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Metadata attached to an item, as string entries
+pub type ItemMeta = BTreeMap<String, String>;
+
+/// Metadata tables of a store, one per metadata type
+pub(crate) type MetaTables<K> = HashMap<TypeId, Box<dyn MetaTable<K>>>;
+
+/// The metadata of one type, seen without its type so the store can keep it in sync with the items
+pub(crate) trait MetaTable<K> {
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn is_empty(&self) -> bool;
+
+    fn contains(&self, key: &K) -> bool;
+
+    /// Get the keys with metadata, in the order `remap` expects their new keys
+    fn keys(&self) -> Vec<K>;
+
+    /// Move the metadata to `new_keys`, given in `keys()` order, dropping it for `None`
+    fn remap(&mut self, new_keys: Vec<Option<K>>);
+
+    fn rename(&mut self, from: &K, to: K);
+
+    fn clear(&mut self);
+}
+
+/// The metadata of type `M` of the items
+///
+/// Tables are created by the first handle of their type and kept until the store is dropped, so reading
+/// the metadata of an item that has none can borrow the table's default instead of creating an entry.
+struct MetaEntries<K, M> {
+    entries: HashMap<K, M>,
+    default: OnceCell<M>,
+}
+
+impl<K, M> MetaEntries<K, M> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            default: OnceCell::new(),
+        }
+    }
+}
+
+impl<K, M> MetaTable<K> for MetaEntries<K, M>
+where
+    K: Hash + Eq + Clone + 'static,
+    M: 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn contains(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn keys(&self) -> Vec<K> {
+        self.entries.keys().cloned().collect()
+    }
+
+    fn remap(&mut self, new_keys: Vec<Option<K>>) {
+        let entries = std::mem::take(&mut self.entries);
+        self.entries = entries
+            .into_iter()
+            .zip(new_keys)
+            .filter_map(|((_, meta), new_key)| Some((new_key?, meta)))
+            .collect();
+    }
+
+    fn rename(&mut self, from: &K, to: K) {
+        if let Some(meta) = self.entries.remove(from) {
+            self.entries.insert(to, meta);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Get the table of the metadata type `M`, if it was created
+fn table<K, M>(tables: &MetaTables<K>) -> Option<&MetaEntries<K, M>>
+where
+    K: 'static,
+    M: 'static,
+{
+    tables
+        .get(&TypeId::of::<M>())
+        .and_then(|table| table.as_any().downcast_ref())
+}
+
+/// Get the table of the metadata type `M`, creating it if needed
+fn table_mut<K, M>(tables: &mut MetaTables<K>) -> &mut MetaEntries<K, M>
+where
+    K: Hash + Eq + Clone + 'static,
+    M: 'static,
+{
+    tables
+        .entry(TypeId::of::<M>())
+        .or_insert_with(|| Box::new(MetaEntries::<K, M>::new()))
+        .as_any_mut()
+        .downcast_mut()
+        .expect("metadata tables are keyed by the type of their metadata")
+}
+
+/// A reference to the metadata of type `M` of an item
+///
+/// Implements `Readable` and `Writable` with `M` as target. Reading the metadata of an item that has none
+/// returns `M::default()` without storing it, writing to it creates the entry.
+pub struct CollectionItemMeta<C, M = ItemMeta>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    key: C::Key,
+    meta: PhantomData<fn() -> M>,
+}
+
+impl<C, M> Clone for CollectionItemMeta<C, M>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store,
+            key: self.key.clone(),
+            meta: PhantomData,
+        }
+    }
+}
+
+impl<C, M> PartialEq for CollectionItemMeta<C, M>
+where
+    C: Collection + 'static,
+    C::Key: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store == other.store && self.key == other.key
+    }
+}
+
+impl<C, M> Readable for CollectionItemMeta<C, M>
+where
+    C: Collection + 'static,
+    C::Key: Hash + Eq,
+    M: Default + 'static,
+{
+    type Target = M;
+    type Storage = UnsyncStorage;
+
+    fn try_read_unchecked(&self) -> Result<ReadableRef<'static, Self>, BorrowError>
+    where
+        Self::Target: 'static,
+    {
+        self.scope().track_shallow();
+        self.try_peek_unchecked()
+    }
+
+    fn try_peek_unchecked(&self) -> Result<ReadableRef<'static, Self>, BorrowError>
+    where
+        Self::Target: 'static,
+    {
+        self.store.inner.meta().try_peek_unchecked().map(|value| {
+            UnsyncStorage::map(value, |tables: &MetaTables<C::Key>| {
+                let table = table::<C::Key, M>(tables)
+                    .expect("metadata tables are created with their first handle");
+                table
+                    .entries
+                    .get(&self.key)
+                    .unwrap_or_else(|| table.default.get_or_init(M::default))
+            })
+        })
+    }
+
+    fn subscribers(&self) -> dioxus_core::Subscribers
+    where
+        Self::Target: 'static,
+    {
+        self.scope().subscribers()
+    }
+}
+
+impl<C, M> Writable for CollectionItemMeta<C, M>
+where
+    C: Collection + 'static,
+    C::Key: Hash + Eq,
+    M: Default + 'static,
+{
+    type WriteMetadata = Box<dyn std::any::Any>;
+
+    fn try_write_unchecked(&self) -> Result<WritableRef<'static, Self>, BorrowMutError>
+    where
+        Self::Target: 'static,
+    {
+        // Notify the readers of this entry, and those of the whole table since the entry may be new
+        self.store.inner.meta().selector().mark_dirty_shallow();
+        self.scope().try_write_unchecked().map(|value| {
+            WriteLock::map(value, |tables: &mut MetaTables<C::Key>| {
+                table_mut(tables)
+                    .entries
+                    .entry(self.key.clone())
+                    .or_default()
+            })
+        })
+    }
+}
+
+impl<C, M> CollectionItemMeta<C, M>
+where
+    C: Collection + 'static,
+    C::Key: Hash + Eq,
+    M: Default + 'static,
+{
+    /// Check if the item has metadata of this type (reactive)
+    pub fn is_set(&self) -> bool {
+        self.scope().track_shallow();
+        self.peek_is_set()
+    }
+
+    /// Check if the item has metadata of this type, without subscribing to it
+    pub(crate) fn peek_is_set(&self) -> bool {
+        self.store
+            .peek_meta_table(|entries: Option<&HashMap<C::Key, M>>| {
+                entries.is_some_and(|entries| entries.contains_key(&self.key))
+            })
+    }
+
+    /// Get the subscriptions of this entry, a child of the `meta` lens keyed by the metadata type and item key
+    ///
+    /// Reading an entry only subscribes to it, so writing the metadata of an item doesn't re-run the readers
+    /// of the other items. Writes to the whole `meta` lens (remapping, clearing) still notify every entry.
+    fn scope(
+        &self,
+    ) -> SelectorScope<
+        impl Writable<
+            Target = MetaTables<C::Key>,
+            Storage = UnsyncStorage,
+            WriteMetadata = Box<dyn Any>,
+        > + use<C, M>,
+    > {
+        self.store
+            .inner
+            .meta()
+            .into_selector()
+            .hash_child_unmapped(&(TypeId::of::<M>(), &self.key))
+    }
+
+    /// Remove the metadata of this type from the item, returning it
+    pub fn take(&self) -> Option<M> {
+        if !self.peek_is_set() {
+            return None;
+        }
+        self.store
+            .write_meta_table(|entries: &mut HashMap<C::Key, M>| entries.remove(&self.key))
+    }
+}
+
+impl<C> CollectionItemMeta<C>
+where
+    C: Collection + 'static,
+    C::Key: Hash + Eq,
+{
+    /// Get a metadata entry
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.read().get(name).cloned()
+    }
+
+    /// Set a metadata entry
+    pub fn insert(&self, name: impl Into<String>, value: impl Into<String>) {
+        self.write_unchecked().insert(name.into(), value.into());
+    }
+
+    /// Remove a metadata entry, returning its value
+    ///
+    /// Removing the last entry removes the metadata of the item.
+    pub fn remove(&self, name: &str) -> Option<String> {
+        if !self.peek().contains_key(name) {
+            return None;
+        }
+        let removed = self.write_unchecked().remove(name);
+        if self.peek().is_empty() {
+            self.take();
+        }
+        removed
+    }
+
+    /// Check if a flag is set
+    ///
+    /// Flags are entries with the value `"true"`.
+    pub fn flag(&self, name: &str) -> bool {
+        self.read().get(name).is_some_and(|value| value == "true")
+    }
+
+    /// Set or unset a flag
+    ///
+    /// Unsetting a flag removes its entry.
+    pub fn set_flag(&self, name: impl Into<String>, enabled: bool) {
+        let name = name.into();
+        if enabled {
+            self.insert(name, "true");
+        } else {
+            self.remove(&name);
+        }
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Get the string metadata of an item
+    ///
+    /// Same as `meta_of::<ItemMeta>(key)`. The metadata follows its item when the collection changes: it moves
+    /// with the item when positional keys shift, and is dropped when the item is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b"]);
+    /// store.meta(&1).set_flag("expanded", true);
+    /// store.insert_before(&0, "z").ok();
+    /// // "b" moved to index 2, and its metadata with it
+    /// assert!(store.meta(&2).flag("expanded"));
+    /// ```
+    pub fn meta(&self, key: &C::Key) -> CollectionItemMeta<C> {
+        self.meta_of(key)
+    }
+
+    /// Get the metadata of type `M` of an item
+    ///
+    /// Each type is stored separately, so features can keep their own state next to the items without
+    /// clashing. Like `meta`, it follows its item when the collection changes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use dioxus_signals::*;
+    ///
+    /// #[derive(Default)]
+    /// struct Expanded(bool);
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b"]);
+    /// store.meta_of::<Expanded>(&1).write().0 = true;
+    /// assert!(store.meta_of::<Expanded>(&1).read().0);
+    /// assert!(!store.meta_of::<Expanded>(&0).is_set());
+    /// ```
+    pub fn meta_of<M>(&self, key: &C::Key) -> CollectionItemMeta<C, M>
+    where
+        M: Default + 'static,
+    {
+        // Create the table up front: reads borrow its default for the items without metadata
+        if table::<C::Key, M>(&self.inner.meta().peek()).is_none() {
+            table_mut::<C::Key, M>(&mut self.inner.meta().selector().write_untracked());
+        }
+        CollectionItemMeta {
+            store: *self,
+            key: key.clone(),
+            meta: PhantomData,
+        }
+    }
+
+    /// Read the metadata of type `M` of every item (reactive)
+    pub(crate) fn read_meta_table<M, R>(
+        &self,
+        read: impl FnOnce(Option<&HashMap<C::Key, M>>) -> R,
+    ) -> R
+    where
+        M: 'static,
+    {
+        read(table(&self.inner.meta().read()).map(|table| &table.entries))
+    }
+
+    /// Read the metadata of type `M` of every item, without subscribing to it
+    pub(crate) fn peek_meta_table<M, R>(
+        &self,
+        read: impl FnOnce(Option<&HashMap<C::Key, M>>) -> R,
+    ) -> R
+    where
+        M: 'static,
+    {
+        read(table(&self.inner.meta().peek()).map(|table| &table.entries))
+    }
+
+    /// Write the metadata of type `M` of the items
+    pub(crate) fn write_meta_table<M, R>(
+        &self,
+        write: impl FnOnce(&mut HashMap<C::Key, M>) -> R,
+    ) -> R
+    where
+        M: 'static,
+    {
+        write(&mut table_mut(&mut self.inner.meta().write()).entries)
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Remove the metadata of every item
+    pub fn clear_meta(&self) {
+        // Keep the tables, handles borrow their defaults
+        if self.has_meta() {
+            for table in self.inner.meta().write().values_mut() {
+                table.clear();
+            }
+        }
+    }
+
+    /// Check if any item has metadata
+    pub(crate) fn has_meta(&self) -> bool {
+        self.inner
+            .meta()
+            .peek()
+            .values()
+            .any(|table| !table.is_empty())
+    }
+
+    /// Move the metadata along with the items after a structural change
+    pub(crate) fn remap_meta(
+        &self,
+        old_keys: &[C::Key],
+        new_position: &impl Fn(usize) -> Option<usize>,
+    ) {
+        if !self.has_meta() {
+            return;
+        }
+        // Items and metadata share the store's storage: resolve the new keys before writing
+        let new_keys: Vec<(TypeId, Vec<Option<C::Key>>)> = {
+            let items = self.inner.items();
+            let items = items.peek();
+            let keys = items.keys();
+            let tables = self.inner.meta();
+            let tables = tables.peek();
+            tables
+                .iter()
+                .map(|(type_id, table)| {
+                    let new_keys = table
+                        .keys()
+                        .iter()
+                        .map(|key| follow_key(&*items, old_keys, &keys, key, new_position))
+                        .collect();
+                    (*type_id, new_keys)
+                })
+                .collect()
+        };
+
+        let mut tables = self.inner.meta();
+        let mut tables = tables.write();
+        for (type_id, new_keys) in new_keys {
+            if let Some(table) = tables.get_mut(&type_id) {
+                table.remap(new_keys);
+            }
+        }
+    }
+
+    /// Move the metadata of `from` to `to`
+    pub(crate) fn rename_meta(&self, from: &C::Key, to: &C::Key) {
+        let renamed = self
+            .inner
+            .meta()
+            .peek()
+            .values()
+            .any(|table| table.contains(from));
+        if renamed {
+            for table in self.inner.meta().write().values_mut() {
+                table.rename(from, to.clone());
+            }
+        }
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Get the string metadata of this item
+    pub fn meta(&self) -> CollectionItemMeta<C> {
+        self.store.meta(&self.key)
+    }
+
+    /// Get the metadata of type `M` of this item
+    pub fn meta_of<M>(&self) -> CollectionItemMeta<C, M>
+    where
+        M: Default + 'static,
+    {
+        self.store.meta_of(&self.key)
+    }
+}
@@ -0,0 +1,125 @@
+//! Joining two stores by key
+//!
+//! `store_a.join(&store_b)` pairs every item of the first store with the item of the second store
+//! under the same key, if any, like a SQL left join. Rendering an entity list enriched with data from
+//! another store (users + presence) then doesn't need a manual lookup per row.
+
+use crate::{Collection, CollectionItem, CollectionStore};
+
+/// Reactive left join of two stores sharing a key type
+///
+/// Created with `CollectionStore::join`. Reads subscribe to both stores.
+pub struct JoinedView<A, B>
+where
+    A: Collection + 'static,
+    B: Collection + 'static,
+{
+    left: CollectionStore<A>,
+    right: CollectionStore<B>,
+}
+
+impl<A, B> Copy for JoinedView<A, B>
+where
+    A: Collection + 'static,
+    B: Collection + 'static,
+{
+}
+
+impl<A, B> Clone for JoinedView<A, B>
+where
+    A: Collection + 'static,
+    B: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, B> PartialEq for JoinedView<A, B>
+where
+    A: Collection + 'static,
+    B: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.left.inner == other.left.inner && self.right.inner == other.right.inner
+    }
+}
+
+impl<A> CollectionStore<A>
+where
+    A: Collection + 'static,
+    A::Key: Clone + PartialEq,
+{
+    /// Pair every item of this store with the item of `other` under the same key, if any
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use dioxus_signals::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let users = CollectionStore::new(HashMap::from([(1, "ada"), (2, "alan")]));
+    /// let presence = CollectionStore::new(HashMap::from([(2, "online")]));
+    /// let rows = users.join(&presence);
+    /// let (user, status) = rows.get(&2).unwrap();
+    /// assert_eq!(*user.read(), "alan");
+    /// assert_eq!(status.map(|status| *status.read()), Some("online"));
+    /// ```
+    pub fn join<B>(&self, other: &CollectionStore<B>) -> JoinedView<A, B>
+    where
+        B: Collection<Key = A::Key> + 'static,
+    {
+        JoinedView {
+            left: *self,
+            right: *other,
+        }
+    }
+}
+
+impl<A, B> JoinedView<A, B>
+where
+    A: Collection + 'static,
+    B: Collection<Key = A::Key> + 'static,
+    A::Key: Clone + PartialEq,
+{
+    /// Get the store whose items are all listed
+    pub fn left(&self) -> CollectionStore<A> {
+        self.left
+    }
+
+    /// Get the store whose items are looked up by key
+    pub fn right(&self) -> CollectionStore<B> {
+        self.right
+    }
+
+    /// Get the number of rows, which is the length of the left store (reactive)
+    pub fn len(&self) -> usize {
+        self.left.len()
+    }
+
+    /// Check if there are no rows (reactive)
+    pub fn is_empty(&self) -> bool {
+        self.left.is_empty()
+    }
+
+    /// Get the row for a key of the left store (reactive)
+    pub fn get(&self, key: &A::Key) -> Option<(CollectionItem<A>, Option<CollectionItem<B>>)> {
+        if !self.left.contains_key(key) {
+            return None;
+        }
+        let right = self.right.contains_key(key).then(|| self.right.get(key));
+        Some((self.left.get(key), right))
+    }
+
+    /// Get an iterator over the rows, in the order of the left store (reactive)
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (A::Key, CollectionItem<A>, Option<CollectionItem<B>>)> + '_ {
+        self.left.iter().map(move |item| {
+            let key = item.key();
+            let right = self.right.contains_key(&key).then(|| self.right.get(&key));
+            (key, item, right)
+        })
+    }
+}
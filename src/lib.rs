@@ -49,23 +49,203 @@
 //! }
 //! assert!(store.is_empty());
 //! ```
+//!
+//! ### Typed keys
+//!
+//! Keys only need `Clone + PartialEq`, plus what the collection itself requires (`Eq + Hash` for `HashMap`),
+//! so domain identifiers can be newtypes. Helpers that format or parse keys (`option_value`, `render_items`,
+//! `enable_tracing`...) ask for `Display`, `FromStr` or `Debug` on their own.
+//!
+//! ```rust,no_run
+//! use dioxus_collection_store::use_collection;
+//! use std::collections::HashMap;
+//!
+//! #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+//! struct TaskId(u64);
+//!
+//! let tasks = use_collection(|| HashMap::from([(TaskId(7), "write docs")]));
+//! tasks.select(&TaskId(7)).ok();
+//! assert!(tasks.selected_key() == Some(TaskId(7)));
+//! ```
+
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+// Lets the derive macros refer to `::dioxus_collection_store` from within this crate's tests
+extern crate self as dioxus_collection_store;
 
+pub(crate) mod access;
+#[cfg(feature = "announcer")]
+pub(crate) mod announcer;
+pub(crate) mod checked;
+pub(crate) mod chunks;
+#[cfg(feature = "clipboard")]
+pub(crate) mod clipboard;
 pub(crate) mod collection_item;
 pub(crate) mod collection_store;
 pub(crate) mod collection_trait;
+#[cfg(feature = "compat")]
+pub(crate) mod compat;
+#[cfg(feature = "components")]
+#[cfg_attr(docsrs, doc(cfg(feature = "components")))]
+pub mod components;
+#[cfg(feature = "crdt")]
+pub(crate) mod crdt;
+#[cfg(feature = "csv")]
+pub(crate) mod csv;
+pub(crate) mod dedup;
+#[cfg(feature = "devtools")]
+pub(crate) mod devtools;
+pub(crate) mod edit_session;
 pub mod error;
+#[cfg(feature = "animations")]
+pub(crate) mod exit;
+#[cfg(feature = "json")]
+pub(crate) mod export;
+#[cfg(feature = "fake-data")]
+pub(crate) mod fake_data;
+pub(crate) mod focus;
+pub(crate) mod generation;
+pub(crate) mod guard;
 pub(crate) mod hook;
+#[cfg(feature = "index")]
+pub(crate) mod index;
+pub(crate) mod item_action;
+pub(crate) mod item_meta;
+pub(crate) mod join;
+#[cfg(feature = "listbox")]
+pub(crate) mod listbox;
+pub(crate) mod lock;
+pub(crate) mod mapped;
+pub(crate) mod merge;
+#[cfg(feature = "offline")]
+pub(crate) mod offline;
+pub(crate) mod pinned;
+pub(crate) mod placeholder;
+pub(crate) mod positions;
+#[cfg(feature = "prefetch")]
+pub(crate) mod prefetch;
+pub(crate) mod range_selection;
+pub(crate) mod ranked;
+pub(crate) mod recording;
+pub(crate) mod refresh;
+pub(crate) mod rename;
+pub(crate) mod render;
+pub(crate) mod select_element;
+pub(crate) mod selectable;
+pub(crate) mod selection_behavior;
+pub(crate) mod selection_change;
+#[cfg(feature = "selection-group")]
+pub(crate) mod selection_group;
+pub(crate) mod selection_guard;
+pub(crate) mod selection_model;
+#[cfg(feature = "rand")]
+pub(crate) mod shuffle;
+pub(crate) mod sorted;
+#[cfg(feature = "stats")]
+pub(crate) mod stats;
+pub(crate) mod strict;
+#[cfg(feature = "table")]
+pub(crate) mod table;
+#[cfg(feature = "text-lines")]
+pub(crate) mod text_lines;
+#[cfg(feature = "toasts")]
+pub(crate) mod toast;
+#[cfg(feature = "tracing")]
+pub(crate) mod trace;
+pub(crate) mod tracking;
+#[cfg(feature = "ttl")]
+pub(crate) mod ttl;
+#[cfg(feature = "typeahead")]
+pub(crate) mod typeahead;
+#[cfg(any(
+    test,
+    feature = "prefetch",
+    feature = "selection-group",
+    feature = "router"
+))]
+pub(crate) mod waker;
+pub(crate) mod weak;
 
 // Implementations for standard library collections
 pub mod implementations;
 
 // Re-exports
+#[cfg(feature = "announcer")]
+#[cfg_attr(docsrs, doc(cfg(feature = "announcer")))]
+pub use announcer::{AnnouncementTemplates, use_live_announcer};
+pub use checked::CheckState;
 pub use collection_item::CollectionItem;
-pub(crate) use collection_store::CollectionData;
-pub use collection_store::CollectionStore;
-pub use collection_trait::{Collection, SequentialCollection};
+pub use collection_store::{CollectionData, CollectionDataStoreExt, CollectionStore};
+pub use collection_trait::{BorrowedLookup, Collection, KeyIter, SequentialCollection};
+#[cfg(feature = "crdt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "crdt")))]
+pub use crdt::LwwMap;
+#[cfg(feature = "csv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+pub use csv::RowCodec;
+#[cfg(feature = "devtools")]
+#[cfg_attr(docsrs, doc(cfg(feature = "devtools")))]
+pub use devtools::{DebugTimeline, TimelineEntry};
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use dioxus_collection_store_macros::Merge;
+pub use edit_session::EditSession;
 pub use error::{CollectionError, CollectionResult};
-pub use hook::use_collection;
+#[cfg(feature = "fake-data")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fake-data")))]
+pub use fake_data::{MutationSimulator, SimulatedMutation, use_mutation_simulator};
+pub use guard::{GuardedCollectionStore, GuardedItem, Permissions};
+pub use hook::{
+    provide_collection, try_use_collection_context, use_collection, use_collection_context,
+};
+#[cfg(feature = "index")]
+#[cfg_attr(docsrs, doc(cfg(feature = "index")))]
+pub use index::CollectionIndex;
+pub use item_action::ActionHandle;
+pub use item_meta::{CollectionItemMeta, ItemMeta};
+pub use join::JoinedView;
+#[cfg(feature = "listbox")]
+#[cfg_attr(docsrs, doc(cfg(feature = "listbox")))]
+pub use listbox::{Listbox, ListboxAttributes, ListboxOption, use_collection_listbox};
+pub use mapped::MappedCollectionStore;
+pub use merge::Merge;
+#[cfg(feature = "prefetch")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prefetch")))]
+pub use prefetch::SelectionPrefetch;
+pub use ranked::RankedView;
+pub use recording::{CollectionCommands, CollectionOperation, RecordingBundle, Replay};
+#[cfg(feature = "polling")]
+#[cfg_attr(docsrs, doc(cfg(feature = "polling")))]
+pub use refresh::use_polling_collection;
+pub use select_element::use_collection_select;
+pub use selection_behavior::SelectionBehavior;
+pub use selection_change::SelectionSubscription;
+#[cfg(feature = "selection-group")]
+#[cfg_attr(docsrs, doc(cfg(feature = "selection-group")))]
+pub use selection_group::SelectionGroup;
+pub use selection_guard::SelectionDecision;
+pub use selection_model::SelectionModel;
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+pub use stats::CollectionStats;
+#[cfg(feature = "router")]
+#[cfg_attr(docsrs, doc(cfg(feature = "router")))]
+pub use table::use_collection_url_sync;
+#[cfg(feature = "table")]
+#[cfg_attr(docsrs, doc(cfg(feature = "table")))]
+pub use table::{
+    ColumnModel, FilterMode, FilterOp, FilterSet, RowGroup, SortDirection, TableView, ViewPreset,
+};
+#[cfg(feature = "text-lines")]
+#[cfg_attr(docsrs, doc(cfg(feature = "text-lines")))]
+pub use text_lines::TextLines;
+#[cfg(feature = "toasts")]
+#[cfg_attr(docsrs, doc(cfg(feature = "toasts")))]
+pub use toast::ToastCollection;
+#[cfg(feature = "typeahead")]
+#[cfg_attr(docsrs, doc(cfg(feature = "typeahead")))]
+pub use typeahead::{TYPEAHEAD_TIMEOUT, Typeahead, use_typeahead};
+pub use weak::{WeakCollectionItem, WeakCollectionStore};
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,232 @@
+//! WAI-ARIA listbox pattern over the focus and selection APIs
+//!
+//! The crate doesn't depend on an HTML renderer, so the listbox hands out plain attribute values and
+//! event handlers taking the pressed key name, to be wired into the app's elements:
+//!
+//! ```rust,ignore
+//! let listbox = use_collection_listbox(store);
+//! let attributes = listbox.attributes();
+//! rsx! {
+//!     ul {
+//!         id: attributes.id,
+//!         role: attributes.role,
+//!         tabindex: attributes.tabindex,
+//!         aria_activedescendant: attributes.aria_activedescendant,
+//!         onfocus: move |_| listbox.handle_focus(),
+//!         onkeydown: move |event| {
+//!             if listbox.handle_key(&event.key().to_string()) {
+//!                 event.prevent_default();
+//!             }
+//!         },
+//!         for option in listbox.options() {
+//!             li {
+//!                 id: option.id,
+//!                 role: option.role,
+//!                 aria_selected: option.aria_selected,
+//!                 onclick: move |_| listbox.handle_click(&option.item.key()),
+//!                 "{option.item}"
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus_core::use_hook;
+use dioxus_signals::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Source of unique element ids for listboxes
+static NEXT_LISTBOX_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Attributes for the listbox container element
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListboxAttributes {
+    pub id: String,
+    pub role: &'static str,
+    pub tabindex: i32,
+    /// Id of the focused option, if any
+    pub aria_activedescendant: Option<String>,
+}
+
+/// An option of the listbox, with the attributes for its element
+pub struct ListboxOption<C>
+where
+    C: Collection + 'static,
+{
+    pub item: CollectionItem<C>,
+    pub id: String,
+    pub role: &'static str,
+    pub aria_selected: bool,
+    /// Whether the option has the (virtual) keyboard focus
+    pub focused: bool,
+}
+
+/// Listbox handle over a store, see `use_collection_listbox`
+pub struct Listbox<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    id: usize,
+    selection_follows_focus: bool,
+}
+
+impl<C> Clone for Listbox<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Listbox<C> where C: Collection + 'static {}
+
+impl<C> PartialEq for Listbox<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner
+            && self.id == other.id
+            && self.selection_follows_focus == other.selection_follows_focus
+    }
+}
+
+impl<C> Listbox<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Create a listbox over a store, with a unique id
+    pub fn new(store: CollectionStore<C>) -> Self {
+        Self {
+            store,
+            id: NEXT_LISTBOX_ID.fetch_add(1, Ordering::Relaxed),
+            selection_follows_focus: false,
+        }
+    }
+
+    /// Select options as soon as they're focused, instead of on Enter/Space
+    pub fn with_selection_follows_focus(mut self, enabled: bool) -> Self {
+        self.selection_follows_focus = enabled;
+        self
+    }
+
+    /// Get the attributes of the container element (reactive)
+    pub fn attributes(&self) -> ListboxAttributes {
+        let focused = self.store.focused_key();
+        ListboxAttributes {
+            id: self.container_id(),
+            role: "listbox",
+            tabindex: 0,
+            aria_activedescendant: focused.and_then(|focused| {
+                let position = self.store.items().read().key_position(&focused)?;
+                Some(self.option_id(position))
+            }),
+        }
+    }
+
+    /// Get the options of the listbox in collection order (reactive)
+    pub fn options(&self) -> Vec<ListboxOption<C>> {
+        let selected = self.store.selected_key();
+        let focused = self.store.focused_key();
+        self.store
+            .iter()
+            .enumerate()
+            .map(|(position, item)| {
+                let key = item.key();
+                ListboxOption {
+                    id: self.option_id(position),
+                    role: "option",
+                    aria_selected: selected.as_ref() == Some(&key),
+                    focused: focused.as_ref() == Some(&key),
+                    item,
+                }
+            })
+            .collect()
+    }
+
+    /// Handle a key press on the container, returns `true` if the key was handled
+    ///
+    /// Supports ArrowDown, ArrowUp, Home and End to move the focus, and Enter or Space to select
+    /// the focused option. The default action of handled keys (like scrolling) should be prevented.
+    pub fn handle_key(&self, key: &str) -> bool {
+        match key {
+            "ArrowDown" => {
+                self.store.focus_next();
+            }
+            "ArrowUp" => {
+                self.store.focus_prev();
+            }
+            "Home" => self.focus_edge(false),
+            "End" => self.focus_edge(true),
+            "Enter" | " " => {
+                return self.store.commit_focus().is_ok();
+            }
+            _ => return false,
+        }
+        if self.selection_follows_focus {
+            let _ = self.store.commit_focus();
+        }
+        true
+    }
+
+    /// Handle the container receiving focus: focus the selected option, or the first one
+    pub fn handle_focus(&self) {
+        if self.store.focused_key_signal().peek().is_some() {
+            return;
+        }
+        match self.store.selected_key() {
+            Some(selected) => {
+                let _ = self.store.focus(&selected);
+            }
+            None => self.focus_edge(false),
+        }
+    }
+
+    /// Handle a click on an option: focus and select it
+    pub fn handle_click(&self, key: &C::Key) {
+        if self.store.focus(key).is_ok() {
+            let _ = self.store.select(key);
+        }
+    }
+
+    fn focus_edge(&self, last: bool) {
+        let keys = self.store.items().peek().keys();
+        let edge = if last { keys.last() } else { keys.first() };
+        if let Some(key) = edge {
+            let _ = self.store.focus(key);
+        }
+    }
+
+    fn container_id(&self) -> String {
+        format!("collection-listbox-{}", self.id)
+    }
+
+    fn option_id(&self, position: usize) -> String {
+        format!("collection-listbox-{}-option-{}", self.id, position)
+    }
+}
+
+/// Hook creating a listbox handle over a store
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use dioxus_collection_store::{use_collection, use_collection_listbox};
+///
+/// let store = use_collection(|| vec!["Apple", "Banana", "Cherry"]);
+/// let listbox = use_collection_listbox(store);
+/// listbox.handle_key("ArrowDown");
+/// listbox.handle_key("Enter");
+/// assert_eq!(store.selected_key(), Some(0));
+/// ```
+pub fn use_collection_listbox<C>(store: CollectionStore<C>) -> Listbox<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    use_hook(|| Listbox::new(store))
+}
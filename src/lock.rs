@@ -0,0 +1,57 @@
+//! Locking a store during async saves
+//!
+//! `store.lock()` freezes the collection while a save or another long operation is in flight, so the user
+//! can't edit what is being saved. While locked, mutations fail with `CollectionError::Locked`: store methods
+//! returning a `CollectionResult`, and the `try_*` variants of the others (`try_push`, `try_set`, `try_insert`,
+//! `try_remove`, `try_clear`...). The plain `push`, `set` or `remove` call them and drop the error, use the
+//! `try_*` variants to tell a rejected write apart. Item writes follow the same rule with `item.try_set` and
+//! `item.try_remove`, but not raw `CollectionItem::write()` access.
+//!
+//! Selection, focus and item metadata are UI state and can still change.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Reject mutations until `unlock` is called
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionError, CollectionStore};
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.lock();
+    /// assert_eq!(store.try_push(4), Err(CollectionError::Locked));
+    /// assert_eq!(store.swap(&0, &1), Err(CollectionError::Locked));
+    /// assert_eq!(store.len(), 3);
+    /// store.unlock();
+    /// ```
+    pub fn lock(&self) {
+        self.inner.locked().set(true);
+    }
+
+    /// Accept mutations again
+    pub fn unlock(&self) {
+        self.inner.locked().set(false);
+    }
+
+    /// Check if the store is locked (reactive)
+    pub fn is_locked(&self) -> bool {
+        *self.inner.locked().read()
+    }
+
+    /// Fail with `CollectionError::Locked` while the store is locked
+    pub(crate) fn ensure_unlocked(&self) -> CollectionResult<()> {
+        if *self.inner.locked().peek() {
+            Err(CollectionError::Locked)
+        } else {
+            Ok(())
+        }
+    }
+}
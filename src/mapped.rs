@@ -0,0 +1,178 @@
+//! Read-only projections of a store
+//!
+//! `store.map_values(project)` derives a collection of projected values (DTO to view model...) with the same keys.
+//! Each projection is cached next to a copy of its source value, so reads only re-run `project` for the items
+//! whose value changed since they were last projected.
+
+use crate::{Collection, CollectionStore};
+use dioxus_signals::*;
+use std::rc::Rc;
+
+/// Projects a source value
+type Projection<V, U> = Rc<dyn Fn(&V) -> U>;
+
+/// Projection cache of a `MappedCollectionStore`
+struct MappedCache<C, U>
+where
+    C: Collection,
+{
+    project: Projection<C::Value, U>,
+    /// Source key, source value when projected, projection
+    entries: Vec<(C::Key, C::Value, U)>,
+}
+
+/// Read-only reactive collection of the projected values of a store
+///
+/// Created with `CollectionStore::map_values`. Reads subscribe to the source store.
+pub struct MappedCollectionStore<C, U>
+where
+    C: Collection + 'static,
+    U: 'static,
+{
+    store: CollectionStore<C>,
+    cache: CopyValue<MappedCache<C, U>>,
+}
+
+impl<C, U> Copy for MappedCollectionStore<C, U> where C: Collection + 'static {}
+
+impl<C, U> Clone for MappedCollectionStore<C, U>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, U> PartialEq for MappedCollectionStore<C, U>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner && self.cache == other.cache
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Derive a read-only collection of projected values, with the same keys
+    ///
+    /// Create it once (in `use_hook` for instance), the projections are cached across reads.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let users = CollectionStore::new(vec![("ada", 36), ("alan", 41)]);
+    /// let labels = users.map_values(|(name, age)| format!("{name} ({age})"));
+    /// assert_eq!(labels.get(&1), Some("alan (41)".to_string()));
+    /// ```
+    pub fn map_values<U>(
+        &self,
+        project: impl Fn(&C::Value) -> U + 'static,
+    ) -> MappedCollectionStore<C, U>
+    where
+        C::Value: Clone + PartialEq,
+        U: Clone + 'static,
+    {
+        MappedCollectionStore {
+            store: *self,
+            cache: CopyValue::new(MappedCache {
+                project: Rc::new(project),
+                entries: Vec::new(),
+            }),
+        }
+    }
+}
+
+impl<C, U> MappedCollectionStore<C, U>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Clone + PartialEq,
+    U: Clone + 'static,
+{
+    /// Get the source store
+    pub fn source(&self) -> CollectionStore<C> {
+        self.store
+    }
+
+    /// Get the number of items (reactive)
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    /// Check if there are no items (reactive)
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    /// Get the projected value of an item (reactive)
+    ///
+    /// Returns `None` if the key is not in the source collection.
+    pub fn get(&self, key: &C::Key) -> Option<U> {
+        let items = self.store.items();
+        let items = items.read();
+        let value = items.get(key)?;
+        let mut cache = self.cache.write_unchecked();
+        let MappedCache { project, entries } = &mut *cache;
+        match entries.iter_mut().find(|(k, _, _)| k == key) {
+            Some((_, source, projected)) => {
+                if source != value {
+                    *source = value.clone();
+                    *projected = project(value);
+                }
+                Some(projected.clone())
+            }
+            None => {
+                let projected = project(value);
+                entries.push((key.clone(), value.clone(), projected.clone()));
+                Some(projected)
+            }
+        }
+    }
+
+    /// Get every key with its projected value, in collection order (reactive)
+    ///
+    /// Projections of removed items are dropped from the cache.
+    pub fn entries(&self) -> Vec<(C::Key, U)> {
+        let items = self.store.items();
+        let items = items.read();
+        let mut cache = self.cache.write_unchecked();
+        let MappedCache { project, entries } = &mut *cache;
+        let mut previous = std::mem::take(entries);
+        for key in items.keys() {
+            let Some(value) = items.get(&key) else {
+                continue;
+            };
+            let entry = match previous.iter().position(|(k, _, _)| *k == key) {
+                Some(position) => {
+                    let (key, source, projected) = previous.swap_remove(position);
+                    if source == *value {
+                        (key, source, projected)
+                    } else {
+                        (key, value.clone(), project(value))
+                    }
+                }
+                None => (key, value.clone(), project(value)),
+            };
+            entries.push(entry);
+        }
+        entries
+            .iter()
+            .map(|(key, _, projected)| (key.clone(), projected.clone()))
+            .collect()
+    }
+
+    /// Get the projected values, in collection order (reactive)
+    pub fn values(&self) -> Vec<U> {
+        self.entries()
+            .into_iter()
+            .map(|(_, projected)| projected)
+            .collect()
+    }
+}
@@ -0,0 +1,70 @@
+//! Partial updates of struct values
+//!
+//! Applying a server patch with `set` replaces the whole value, clobbering the fields edited locally in the
+//! meantime. A `Merge` value takes a patch holding only the fields to update, and `store.merge(key, patch)`
+//! applies it to the stored value as a single `Set` operation. With the `derive` feature, `#[derive(Merge)]`
+//! generates the patch struct:
+//!
+//! ```rust,ignore
+//! #[derive(Clone, Merge)]
+//! #[merge(derive(serde::Deserialize))]
+//! struct Task {
+//!     title: String,
+//!     done: bool,
+//! }
+//!
+//! let patch: TaskPatch = serde_json::from_str(r#"{ "done": true }"#)?;
+//! tasks.merge(id, patch)?; // the title is kept
+//! ```
+
+use crate::{Collection, CollectionError, CollectionResult, CollectionStore};
+
+/// A value that can be partially updated with a patch
+///
+/// Derive it with `#[derive(Merge)]` (`derive` feature) for structs with named fields,
+/// or implement it by hand for custom merge rules.
+///
+/// # Examples
+///
+/// ```rust
+/// use dioxus_collection_store::Merge;
+///
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// impl Merge for Counter {
+///     /// Amount to add
+///     type Patch = u32;
+///
+///     fn merge(&mut self, patch: u32) {
+///         self.count += patch;
+///     }
+/// }
+/// ```
+pub trait Merge {
+    /// The partial value applied by `merge`
+    type Patch;
+
+    /// Apply a patch to this value
+    fn merge(&mut self, patch: Self::Patch);
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Merge + Clone,
+{
+    /// Apply a patch to the value under `key`, keeping the fields it doesn't set
+    ///
+    /// The merged value is written like `set`, so it's recorded as one `Set` operation and skipped by
+    /// `dedup_writes` if nothing changed. Fails with `KeyNotFound` for a missing key, or `Locked`.
+    pub fn merge(&self, key: C::Key, patch: <C::Value as Merge>::Patch) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let mut value = self.peek_value(&key).ok_or(CollectionError::KeyNotFound)?;
+        value.merge(patch);
+        self.set(key, value);
+        Ok(())
+    }
+}
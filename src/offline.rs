@@ -0,0 +1,189 @@
+//! Queueing mutations made offline
+//!
+//! Apps that keep working without a connection still need to send the changes made meanwhile to their
+//! backend. With `store.enable_offline_queue()`, the mutations made through the store API while
+//! `store.set_online(false)` are queued as `CollectionOperation`s, and `store.flush(send)` hands them to an
+//! async function once the connection is back:
+//!
+//! ```rust,ignore
+//! store.set_online(false);
+//! store.push(todo); // applied locally, and queued
+//! // Connectivity is back
+//! store.set_online(true);
+//! spawn(async move {
+//!     store.flush(|ops| async move { api.sync(ops).await }).await.ok();
+//! });
+//! ```
+//!
+//! Selection changes are UI state and aren't queued. Like recordings, mutations made through
+//! `CollectionItem::write()` bypass the store API and aren't queued either.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionOperation, CollectionStore};
+use dioxus_signals::*;
+use std::future::Future;
+
+/// An operation of the collection `C`
+type Operation<C> = CollectionOperation<<C as Collection>::Key, <C as Collection>::Value>;
+
+/// Clones the values of the collection `C`
+type CloneValue<C> = fn(&<C as Collection>::Value) -> <C as Collection>::Value;
+
+/// Offline state kept in the store while the offline queue is enabled
+pub struct OfflineQueue<C>
+where
+    C: Collection,
+{
+    online: bool,
+    pending: Vec<Operation<C>>,
+    /// Captured when the queue is enabled, so mutation methods don't need a `Clone` bound
+    pub(crate) clone_value: CloneValue<C>,
+    clone_operation: fn(&Operation<C>) -> Operation<C>,
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Queue the mutations made through this store while it is offline
+    ///
+    /// The store starts online. Enabling the queue again discards the pending operations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionOperation, CollectionStore};
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.enable_offline_queue();
+    /// store.set_online(false);
+    /// store.push(4);
+    /// assert_eq!(store.pending_ops(), vec![CollectionOperation::Push { value: 4 }]);
+    /// ```
+    pub fn enable_offline_queue(&self)
+    where
+        C::Value: Clone,
+    {
+        self.inner.offline_queue().set(Some(OfflineQueue {
+            online: true,
+            pending: Vec::new(),
+            clone_value: C::Value::clone,
+            clone_operation: CollectionOperation::clone,
+        }));
+    }
+
+    /// Stop queueing mutations, and drop the pending operations
+    pub fn disable_offline_queue(&self) {
+        self.inner.offline_queue().set(None);
+    }
+
+    /// Queue the following mutations (`false`) or stop queueing them (`true`)
+    ///
+    /// Going back online doesn't send the pending operations, call `flush` for that.
+    /// Does nothing unless the offline queue is enabled.
+    pub fn set_online(&self, online: bool) {
+        let mut queue = self.inner.offline_queue();
+        let changed = queue
+            .peek()
+            .as_ref()
+            .is_some_and(|queue| queue.online != online);
+        if changed && let Some(queue) = queue.write().as_mut() {
+            queue.online = online;
+        }
+    }
+
+    /// Check if the store is online (reactive)
+    ///
+    /// Always `true` without an offline queue.
+    pub fn is_online(&self) -> bool {
+        self.inner
+            .offline_queue()
+            .read()
+            .as_ref()
+            .is_none_or(|queue| queue.online)
+    }
+
+    /// Get the operations queued while offline, oldest first (reactive)
+    pub fn pending_ops(&self) -> Vec<Operation<C>>
+    where
+        C::Value: Clone,
+    {
+        self.inner
+            .offline_queue()
+            .read()
+            .as_ref()
+            .map(|queue| queue.pending.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the number of operations queued while offline (reactive)
+    pub fn pending_count(&self) -> usize {
+        self.inner
+            .offline_queue()
+            .read()
+            .as_ref()
+            .map_or(0, |queue| queue.pending.len())
+    }
+
+    /// Send the queued operations with `send`, and drop them once it succeeds
+    ///
+    /// The queue is emptied while `send` runs. If it fails, the operations are queued again ahead of the ones
+    /// made meanwhile, and its error is returned. `send` isn't called when nothing is pending.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let result = store
+    ///     .flush(|ops| async move { api.post("/sync", &ops).await.map(|_| ()) })
+    ///     .await;
+    /// ```
+    pub async fn flush<F, E>(&self, send: impl FnOnce(Vec<Operation<C>>) -> F) -> Result<(), E>
+    where
+        C::Value: Clone,
+        F: Future<Output = Result<(), E>>,
+    {
+        let mut queue = self.inner.offline_queue();
+        let has_pending = queue
+            .peek()
+            .as_ref()
+            .is_some_and(|queue| !queue.pending.is_empty());
+        if !has_pending {
+            return Ok(());
+        }
+        let pending = queue
+            .write()
+            .as_mut()
+            .map(|queue| std::mem::take(&mut queue.pending))
+            .unwrap_or_default();
+        let result = send(pending.clone()).await;
+        if result.is_err()
+            && let Some(queue) = queue.write().as_mut()
+        {
+            let newer = std::mem::replace(&mut queue.pending, pending);
+            queue.pending.extend_from_slice(&newer);
+        }
+        result
+    }
+
+    /// Get the value cloner of the offline queue, if the store is offline
+    pub(crate) fn offline_clone_value(&self) -> Option<CloneValue<C>> {
+        self.inner
+            .offline_queue()
+            .peek()
+            .as_ref()
+            .filter(|queue| !queue.online)
+            .map(|queue| queue.clone_value)
+    }
+
+    /// Queue an operation if the store is offline
+    pub(crate) fn queue_offline(&self, operation: &Operation<C>) {
+        if operation.is_selection() || self.offline_clone_value().is_none() {
+            return;
+        }
+        if let Some(queue) = self.inner.offline_queue().write().as_mut() {
+            let operation = (queue.clone_operation)(operation);
+            queue.pending.push(operation);
+        }
+    }
+}
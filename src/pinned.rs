@@ -0,0 +1,100 @@
+//! Pinned (favorite) items
+//!
+//! Pins are stored as item metadata, so they follow their item when the collection
+//! changes and don't require restructuring the underlying collection.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Metadata marking an item as pinned
+#[derive(Default)]
+struct Pinned;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Pin an item
+    pub fn pin(&self, key: &C::Key) -> CollectionResult<()> {
+        if !self.peek_contains_key(key) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        if !self.meta_of::<Pinned>(key).peek_is_set() {
+            self.write_meta_table(|pinned| pinned.insert(key.clone(), Pinned));
+        }
+        Ok(())
+    }
+
+    /// Unpin an item
+    pub fn unpin(&self, key: &C::Key) {
+        self.meta_of::<Pinned>(key).take();
+    }
+
+    /// Check if an item is pinned (reactive)
+    pub fn is_pinned(&self, key: &C::Key) -> bool {
+        self.meta_of::<Pinned>(key).is_set()
+    }
+
+    /// Get the keys of the pinned items, in collection order (reactive)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c"]);
+    /// store.pin(&2).ok();
+    /// store.pin(&0).ok();
+    /// assert_eq!(store.pinned_keys(), vec![0, 2]);
+    /// ```
+    pub fn pinned_keys(&self) -> Vec<C::Key> {
+        self.pinned_partition().0
+    }
+
+    /// Get an iterator over the collection items, pinned items first
+    ///
+    /// Pinned and unpinned items each keep the collection order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c"]);
+    /// store.pin(&2).ok();
+    /// let keys: Vec<usize> = store.iter_pinned_first().map(|item| item.key()).collect();
+    /// assert_eq!(keys, vec![2, 0, 1]);
+    /// ```
+    pub fn iter_pinned_first(&self) -> impl Iterator<Item = CollectionItem<C>> + '_ {
+        let (pinned, unpinned) = self.pinned_partition();
+        let store = *self;
+        pinned
+            .into_iter()
+            .chain(unpinned)
+            .map(move |key| CollectionItem::new(store, key))
+    }
+
+    /// Split the keys between pinned and unpinned items, each in collection order (reactive)
+    fn pinned_partition(&self) -> (Vec<C::Key>, Vec<C::Key>) {
+        let keys = self.inner.items().read().keys();
+        self.read_meta_table(|pinned: Option<&HashMap<C::Key, Pinned>>| match pinned {
+            Some(pinned) => keys.into_iter().partition(|key| pinned.contains_key(key)),
+            None => (Vec::new(), keys),
+        })
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Check if this item is pinned
+    pub fn is_pinned(&self) -> bool {
+        self.store.is_pinned(&self.key)
+    }
+}
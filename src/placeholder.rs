@@ -0,0 +1,79 @@
+//! Default values and placeholder rows
+//!
+//! The usual "add a new row, then edit it" flow: `push_default()` adds an item with a placeholder value
+//! and selects it, so the UI can open it for editing right away.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionResult, CollectionStore, SequentialCollection};
+use dioxus_signals::*;
+use std::rc::Rc;
+
+/// Builds the value of new rows
+pub(crate) type PlaceholderFn<V> = Rc<dyn Fn() -> V>;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Use `placeholder` instead of `Default::default()` for the items added by `push_default` and `insert_default`
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use dioxus_signals::*;
+    ///
+    /// let store = CollectionStore::new(vec!["milk".to_string()])
+    ///     .with_placeholder(|| "New item".to_string());
+    /// let key = store.push_default().unwrap();
+    /// assert_eq!(*store.get(&key).read(), "New item");
+    /// assert_eq!(store.selected_key(), Some(key));
+    /// ```
+    pub fn with_placeholder(self, placeholder: impl Fn() -> C::Value + 'static) -> Self {
+        self.inner.placeholder().set(Some(Rc::new(placeholder)));
+        self
+    }
+
+    /// Build the value of a new item: the placeholder if configured, or the default value
+    pub fn placeholder(&self) -> C::Value
+    where
+        C::Value: Default,
+    {
+        match self.inner.placeholder().peek().as_ref() {
+            Some(placeholder) => placeholder(),
+            None => C::Value::default(),
+        }
+    }
+
+    /// Insert a placeholder value under `key` and select it
+    ///
+    /// Returns the previous value if the key already existed.
+    pub fn insert_default(&self, key: C::Key) -> Option<C::Value>
+    where
+        C::Value: Clone + Default,
+    {
+        self.ensure_unlocked().ok()?;
+        let previous = self.insert(key.clone(), self.placeholder());
+        self.select(&key).ok();
+        previous
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Push a placeholder value and select it
+    ///
+    /// Returns the key of the new item.
+    pub fn push_default(&self) -> CollectionResult<C::Key>
+    where
+        C::Value: Clone + Default,
+    {
+        let item = self.push_and_get(self.placeholder())?;
+        item.select().ok();
+        Ok(item.key())
+    }
+}
@@ -0,0 +1,101 @@
+//! Previous positions of moved items, for FLIP animations
+//!
+//! With position tracking enabled, every structural change (`swap`, `move_item`, `sort_by`, insertions and
+//! removals shifting other items...) records where the moved items were. Components read `item.previous_index()`
+//! when rendering to animate each moved item from its old place ("First, Last, Invert, Play"), then call
+//! `clear_previous_positions()` once the animation started. The next structural change replaces them anyway.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus_signals::*;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Start recording the previous positions of the items moved by structural changes
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c"]);
+    /// store.enable_position_tracking();
+    /// store.move_item(&2, &0).ok();
+    /// // "c" moved from index 2, "a" and "b" shifted by one
+    /// assert_eq!(store.get(&0).previous_index(), Some(2));
+    /// assert_eq!(store.get(&1).previous_index(), Some(0));
+    /// ```
+    pub fn enable_position_tracking(&self) {
+        self.inner.position_tracking().set(true);
+    }
+
+    /// Stop recording previous positions, and forget the recorded ones
+    pub fn disable_position_tracking(&self) {
+        self.inner.position_tracking().set(false);
+        self.clear_previous_positions();
+    }
+
+    /// Get the position of an item before the latest structural change, if it moved (reactive)
+    pub fn previous_index(&self, key: &C::Key) -> Option<usize> {
+        self.inner
+            .previous_positions()
+            .read()
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, position)| *position)
+    }
+
+    /// Forget the previous positions, once the animations started
+    pub fn clear_previous_positions(&self) {
+        if !self.inner.previous_positions().peek().is_empty() {
+            self.inner.previous_positions().write().clear();
+        }
+    }
+
+    /// Check if structural changes must record the previous positions
+    pub(crate) fn is_tracking_positions(&self) -> bool {
+        *self.inner.position_tracking().peek()
+    }
+
+    /// Record the previous positions of the items moved by a structural change
+    pub(crate) fn record_previous_positions(
+        &self,
+        old_keys: &[C::Key],
+        new_position: &impl Fn(usize) -> Option<usize>,
+    ) {
+        if !self.is_tracking_positions() {
+            return;
+        }
+        let moved: Vec<(C::Key, usize)> = {
+            let new_keys = self.inner.items().peek().keys();
+            (0..old_keys.len())
+                .filter_map(|old_position| {
+                    let position = new_position(old_position)?;
+                    if position == old_position {
+                        return None;
+                    }
+                    Some((new_keys.as_slice().get(position)?.clone(), old_position))
+                })
+                .collect()
+        };
+        if moved.is_empty() {
+            self.clear_previous_positions();
+        } else {
+            self.inner.previous_positions().set(moved);
+        }
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the position of this item before the latest structural change, if it moved
+    pub fn previous_index(&self) -> Option<usize> {
+        self.store.previous_index(&self.key)
+    }
+}
@@ -0,0 +1,170 @@
+//! Loading the details of the selected item
+//!
+//! Master-detail views list light items and load the full record of the selected one.
+//! `store.on_select_prefetch(loader)` runs the async `loader` whenever the selected key changes,
+//! cancelling the load of the previous selection if it's still in flight, so a slow response never
+//! overwrites the detail of a newer selection. The returned handle exposes the loaded detail as a
+//! signal, like a `Resource`:
+//!
+//! ```rust,ignore
+//! let prefetch = use_hook(|| users.on_select_prefetch(|id| async move { api::user(id).await }));
+//! rsx! {
+//!     match prefetch.selected_detail()() {
+//!         Some(user) => rsx! { UserDetail { user } },
+//!         None if prefetch.is_loading() => rsx! { Spinner {} },
+//!         None => rsx! { "Select a user" },
+//!     }
+//! }
+//! ```
+
+use crate::waker::ChangeWaker;
+use crate::{Collection, CollectionStore};
+use dioxus_core::{Task, spawn};
+use dioxus_signals::*;
+use std::future::Future;
+
+/// The detail of the selected item, loaded by `CollectionStore::on_select_prefetch`
+///
+/// The handle is `Copy`, and its signals are owned by the component that created it.
+pub struct SelectionPrefetch<K, T>
+where
+    K: 'static,
+    T: 'static,
+{
+    detail: Signal<Option<T>>,
+    loaded_key: Signal<Option<K>>,
+    loading: Signal<bool>,
+    watcher: Task,
+    in_flight: CopyValue<Option<Task>>,
+}
+
+impl<K, T> Copy for SelectionPrefetch<K, T> {}
+
+impl<K, T> Clone for SelectionPrefetch<K, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, T> PartialEq for SelectionPrefetch<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.detail == other.detail
+    }
+}
+
+impl<K, T> SelectionPrefetch<K, T>
+where
+    K: Clone + 'static,
+    T: 'static,
+{
+    /// Get the detail of the selected item, `None` while it loads or without selection
+    pub fn selected_detail(&self) -> ReadSignal<Option<T>> {
+        self.detail.into()
+    }
+
+    /// Get the key the current detail was loaded for (reactive)
+    pub fn loaded_key(&self) -> Option<K> {
+        self.loaded_key.read().clone()
+    }
+
+    /// Check if the detail of the selected item is loading (reactive)
+    pub fn is_loading(&self) -> bool {
+        *self.loading.read()
+    }
+
+    /// Stop following the selection, cancelling the load in flight
+    ///
+    /// The last loaded detail stays available.
+    pub fn stop(&self) {
+        self.watcher.cancel();
+        cancel_in_flight(self.in_flight);
+        let mut loading = self.loading;
+        loading.set(false);
+    }
+}
+
+/// Cancel the load of the previous selection, if it's still running
+fn cancel_in_flight(mut in_flight: CopyValue<Option<Task>>) {
+    if let Some(task) = in_flight.write().take() {
+        task.cancel();
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Load the detail of the selected item with `loader` each time the selection changes
+    ///
+    /// The current selection, if any, is loaded right away. When the selected key changes, the previous
+    /// load is cancelled and the detail is reset to `None` until the new one is loaded. Must be called from
+    /// a Dioxus runtime, once per component (in `use_hook` for instance): the loads run in tasks of the
+    /// current component and stop with it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus::prelude::*;
+    /// use dioxus_collection_store::use_collection;
+    ///
+    /// fn Users() -> Element {
+    ///     let users = use_collection(|| vec!["ada", "alan"]);
+    ///     let prefetch = use_hook(|| {
+    ///         users.on_select_prefetch(|index| async move { format!("profile #{index}") })
+    ///     });
+    ///     let detail = prefetch.selected_detail();
+    ///     rsx! { "{detail.read().clone().unwrap_or_default()}" }
+    /// }
+    /// ```
+    pub fn on_select_prefetch<T, F>(
+        &self,
+        mut loader: impl FnMut(C::Key) -> F + 'static,
+    ) -> SelectionPrefetch<C::Key, T>
+    where
+        T: 'static,
+        F: Future<Output = T> + 'static,
+    {
+        let changed = ChangeWaker::default();
+        let context = changed.reactive_context();
+
+        let mut detail = Signal::new(None);
+        let mut loaded_key = Signal::new(None);
+        let mut loading = Signal::new(false);
+        let in_flight = CopyValue::new(None);
+        let store = *self;
+        let watcher = spawn(async move {
+            let mut selected: Option<C::Key> = None;
+            loop {
+                let key = context.reset_and_run_in(|| store.selected_key());
+                if key != selected {
+                    selected = key.clone();
+                    cancel_in_flight(in_flight);
+                    detail.set(None);
+                    loaded_key.set(None);
+                    loading.set(key.is_some());
+                    if let Some(key) = key {
+                        let load = loader(key.clone());
+                        let task = spawn(async move {
+                            let value = load.await;
+                            detail.set(Some(value));
+                            loaded_key.set(Some(key));
+                            loading.set(false);
+                        });
+                        let mut in_flight = in_flight;
+                        in_flight.set(Some(task));
+                    }
+                }
+                changed.changed().await;
+            }
+        });
+
+        SelectionPrefetch {
+            detail,
+            loaded_key,
+            loading,
+            watcher,
+            in_flight,
+        }
+    }
+}
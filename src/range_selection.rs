@@ -0,0 +1,141 @@
+//! Range selection for sequential collections
+//!
+//! A range is kept as an anchor next to the selected key: it covers every item between the two, in collection
+//! order, like shift-click in a file manager. Selecting a single item drops the anchor, and the anchor follows
+//! its item through structural changes like the other per-item state.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::tracking::follow_key;
+use crate::{
+    Collection, CollectionError, CollectionOperation, CollectionResult, CollectionStore,
+    SequentialCollection,
+};
+use dioxus_signals::*;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the selected keys, in collection order (reactive)
+    ///
+    /// Without a range this is just the selected key, if any.
+    /// Items rejected by `set_selectable` are left out of ranges.
+    pub fn selected_keys(&self) -> Vec<C::Key> {
+        let Some(selected) = self.inner.selected_key().read().clone() else {
+            return Vec::new();
+        };
+        let Some(anchor) = self.inner.selection_anchor().read().clone() else {
+            return vec![selected];
+        };
+        let keys = self.inner.items().read().keys();
+        let (Some(from), Some(to)) = (
+            keys.iter().position(|k| *k == anchor),
+            keys.iter().position(|k| *k == selected),
+        ) else {
+            return vec![selected];
+        };
+        keys[from.min(to)..=from.max(to)]
+            .iter()
+            .filter(|key| **key == selected || self.is_selectable(key))
+            .cloned()
+            .collect()
+    }
+
+    /// Get the key the range selection started from, if a range is selected (reactive)
+    pub fn selection_anchor(&self) -> Option<C::Key> {
+        self.inner.selection_anchor().read().clone()
+    }
+
+    /// Drop the range selection anchor, leaving only the selected key
+    pub(crate) fn clear_selection_anchor(&self) {
+        if self.inner.selection_anchor().peek().is_some() {
+            self.inner.selection_anchor().set(None);
+        }
+    }
+
+    /// Move the range selection anchor along with its item after a structural change
+    pub(crate) fn remap_anchor(
+        &self,
+        old_keys: &[C::Key],
+        new_position: &impl Fn(usize) -> Option<usize>,
+    ) {
+        let Some(anchor) = self.inner.selection_anchor().peek().clone() else {
+            return;
+        };
+        let new_anchor = {
+            let items = self.inner.items();
+            let items = items.peek();
+            follow_key(&*items, old_keys, &items.keys(), &anchor, new_position)
+        };
+        if new_anchor.as_ref() != Some(&anchor) {
+            self.inner.selection_anchor().set(new_anchor);
+        }
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Select every item between `from` and `to`, both included
+    ///
+    /// `to` becomes the selected key and `from` the anchor used by `extend_selection_to`.
+    /// Fails with `CollectionError::NotSelectable` if `to` is rejected by the `set_selectable` predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+    /// store.select_range(&1, &3).unwrap();
+    /// assert_eq!(store.selected_keys(), vec![1, 2, 3]);
+    /// assert_eq!(store.selected_key(), Some(3));
+    /// ```
+    pub fn select_range(&self, from: &C::Key, to: &C::Key) -> CollectionResult<()> {
+        if !self.peek_contains_key(from) || !self.peek_contains_key(to) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        if !self.peek_is_selectable(to) {
+            return Err(CollectionError::NotSelectable);
+        }
+        self.record(|_| CollectionOperation::SelectRange {
+            from: from.clone(),
+            to: to.clone(),
+        });
+        self.change_selected_key(Some(to.clone()));
+        self.inner.selection_anchor().set(Some(from.clone()));
+        self.finish_mutation("select_range");
+        Ok(())
+    }
+
+    /// Extend the selection from its anchor to `key`, like a shift-click
+    ///
+    /// The anchor is the start of the current range, or the selected key if there is no range.
+    /// Without any selection, only `key` is selected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+    /// store.select(&2).unwrap();
+    /// store.extend_selection_to(&0).unwrap();
+    /// assert_eq!(store.selected_keys(), vec![0, 1, 2]);
+    /// store.extend_selection_to(&3).unwrap();
+    /// assert_eq!(store.selected_keys(), vec![2, 3]);
+    /// ```
+    pub fn extend_selection_to(&self, key: &C::Key) -> CollectionResult<()> {
+        let anchor = self
+            .inner
+            .selection_anchor()
+            .peek()
+            .clone()
+            .or_else(|| self.inner.selected_key().peek().clone())
+            .unwrap_or_else(|| key.clone());
+        self.select_range(&anchor, key)
+    }
+}
@@ -0,0 +1,168 @@
+//! Items ranked by a score
+//!
+//! Leaderboards and relevance-sorted results show the items by decreasing score, and reorder them live as
+//! scores change. `store.ranked_by(score)` keeps the ranking next to the store: when the collection changes,
+//! only the items whose score changed are moved, and readers are only notified when the order changes.
+//!
+//! ```rust,ignore
+//! let leaderboard = use_hook(|| players.ranked_by(|player| player.points));
+//! rsx! {
+//!     for (rank, player) in leaderboard.top(10).into_iter().enumerate() {
+//!         PlayerRow { rank: rank + 1, player }
+//!     }
+//! }
+//! ```
+
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus_signals::*;
+use std::cmp::Ordering;
+
+/// Keys of a store ordered by decreasing score
+///
+/// Created with `CollectionStore::ranked_by`. The handle is `Copy`, and its state is owned by the component
+/// that created it.
+pub struct RankedView<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    order: Memo<Vec<C::Key>>,
+}
+
+impl<C> Copy for RankedView<C> where C: Collection + 'static {}
+
+impl<C> Clone for RankedView<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> PartialEq for RankedView<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner && self.order == other.order
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Rank the items by decreasing `score`
+    ///
+    /// Items with equal scores keep the order in which they reached that score. Scores that can't be compared,
+    /// like `NaN`, count as equal. Create the view once, in `use_hook` for instance.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
+    ///
+    /// let players = CollectionStore::new(HashMap::from([("ada", 30), ("alan", 50)]));
+    /// let leaderboard = players.ranked_by(|points| *points);
+    /// assert_eq!(leaderboard.keys(), vec!["alan", "ada"]);
+    /// players.set("ada", 80);
+    /// assert_eq!(leaderboard.rank_of(&"ada"), Some(0));
+    /// ```
+    pub fn ranked_by<S>(&self, score: impl Fn(&C::Value) -> S + 'static) -> RankedView<C>
+    where
+        S: PartialOrd + 'static,
+    {
+        let store = *self;
+        // Keys and scores by decreasing score, as of the last update
+        let ranking = CopyValue::new(Vec::new());
+        let order = Memo::new(move || {
+            let items = store.items();
+            let items = items.read();
+            let mut ranking = ranking.write_unchecked();
+            update_ranking(&mut ranking, &*items, &score);
+            ranking.iter().map(|(key, _)| key.clone()).collect()
+        });
+        RankedView { store, order }
+    }
+}
+
+/// Move the items whose score changed, drop the removed ones and insert the new ones
+fn update_ranking<C, S>(ranking: &mut Vec<(C::Key, S)>, items: &C, score: &impl Fn(&C::Value) -> S)
+where
+    C: Collection,
+    C::Key: PartialEq,
+    S: PartialOrd,
+{
+    ranking.retain(|(key, _)| items.contains_key(key));
+    for key in items.keys() {
+        let Some(value) = items.get(&key) else {
+            continue;
+        };
+        let new_score = score(value);
+        let current = ranking.iter().position(|(k, _)| *k == key);
+        if let Some(position) = current {
+            if compare(&ranking[position].1, &new_score) == Ordering::Equal {
+                continue;
+            }
+            ranking.remove(position);
+        }
+        // After the items with a higher or equal score
+        let position =
+            ranking.partition_point(|(_, other)| compare(other, &new_score) != Ordering::Less);
+        ranking.insert(position, (key, new_score));
+    }
+}
+
+/// Compare two scores, counting incomparable ones as equal
+fn compare<S: PartialOrd>(a: &S, b: &S) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+impl<C> RankedView<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the store this view ranks
+    pub fn store(&self) -> CollectionStore<C> {
+        self.store
+    }
+
+    /// Get the keys by decreasing score (reactive)
+    pub fn keys(&self) -> Vec<C::Key> {
+        self.order.read().clone()
+    }
+
+    /// Get the items by decreasing score (reactive)
+    pub fn items(&self) -> Vec<CollectionItem<C>> {
+        self.top(usize::MAX)
+    }
+
+    /// Get the `n` items with the highest scores (reactive)
+    pub fn top(&self, n: usize) -> Vec<CollectionItem<C>> {
+        self.order
+            .read()
+            .iter()
+            .take(n)
+            .map(|key| CollectionItem::new(self.store, key.clone()))
+            .collect()
+    }
+
+    /// Get the rank of an item, 0 for the highest score (reactive)
+    pub fn rank_of(&self, key: &C::Key) -> Option<usize> {
+        self.order.read().iter().position(|k| k == key)
+    }
+
+    /// Get the number of ranked items (reactive)
+    pub fn len(&self) -> usize {
+        self.order.read().len()
+    }
+
+    /// Check if no item is ranked (reactive)
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
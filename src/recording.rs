@@ -0,0 +1,443 @@
+//! Deterministic operation recording and replay
+//!
+//! A store can record every mutation made through its API into a `RecordingBundle`,
+//! together with a snapshot of the collection taken when the recording started.
+//! With the `serde` feature the bundle can be serialized and attached to a bug report,
+//! then loaded back with `CollectionStore::replay_bundle` to reproduce the session.
+//! `store.command_log()` returns the operations recorded so far without stopping, and `store.replay(commands)`
+//! applies them to another store, to mirror a store across windows or record macros.
+//!
+//! Mutations made through `CollectionItem::write()` (or any other raw `Writable` access)
+//! bypass the store API and are not recorded.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionResult, CollectionStore, SequentialCollection};
+use dioxus_signals::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::Hash;
+
+/// A single mutation made through the `CollectionStore` API
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CollectionOperation<K, V> {
+    /// `store.insert(key, value)`
+    Insert { key: K, value: V },
+    /// `store.set(key, value)` or `item.set(value)`
+    Set { key: K, value: V },
+    /// `store.remove(&key)` or `item.remove()`
+    Remove { key: K },
+    /// `store.rename_key(&from, to)`
+    RenameKey { from: K, to: K },
+    /// `store.remove_many(keys)`
+    RemoveMany { keys: Vec<K> },
+    /// `store.extend(items)`
+    Extend { items: Vec<(K, V)> },
+    /// `store.append(&other)` or `store.extend_from_store(&other, filter)`, with the entries received
+    AppendEntries { items: Vec<(K, V)> },
+    /// `store.clear()`
+    Clear,
+    /// `store.select(&key)` and the other selection helpers
+    Select { key: K },
+    /// `store.clear_selection()`
+    ClearSelection,
+    /// `store.push(value)`
+    Push { value: V },
+    /// `store.pop()`
+    Pop,
+    /// `store.swap(&key1, &key2)`
+    Swap { key1: K, key2: K },
+    /// `store.swap_remove(&key)`
+    SwapRemove { key: K },
+    /// `store.insert_before(&key, value)`
+    InsertBefore { key: K, value: V },
+    /// `store.insert_after(&key, value)` or `store.duplicate(&key)`
+    InsertAfter { key: K, value: V },
+    /// `store.move_item(&from, &to)`
+    MoveItem { from: K, to: K },
+    /// `store.sort_by(compare)`, with the keys of the items in their sorted order
+    Reorder { order: Vec<K> },
+    /// `store.select_range(&from, &to)` or `store.extend_selection_to(&to)`
+    SelectRange { from: K, to: K },
+}
+
+impl<K, V> CollectionOperation<K, V> {
+    /// Check if this operation only exists for sequential collections
+    pub fn is_sequential(&self) -> bool {
+        matches!(
+            self,
+            Self::Push { .. }
+                | Self::Pop
+                | Self::Swap { .. }
+                | Self::SwapRemove { .. }
+                | Self::InsertBefore { .. }
+                | Self::InsertAfter { .. }
+                | Self::MoveItem { .. }
+                | Self::Reorder { .. }
+                | Self::SelectRange { .. }
+        )
+    }
+
+    /// Check if this operation only changes the selection
+    pub fn is_selection(&self) -> bool {
+        matches!(
+            self,
+            Self::Select { .. } | Self::ClearSelection | Self::SelectRange { .. }
+        )
+    }
+
+    /// Apply this operation to a store
+    ///
+    /// Sequential operations are rejected, use `apply_sequential` for sequential stores.
+    pub fn apply_keyed<C>(self, store: &CollectionStore<C>) -> CollectionResult<()>
+    where
+        C: Collection<Key = K, Value = V> + 'static,
+        K: Clone + PartialEq,
+        V: Clone,
+    {
+        match self {
+            Self::Insert { key, value } => {
+                store.insert(key, value);
+            }
+            Self::Set { key, value } => store.set(key, value),
+            Self::Remove { key } => {
+                store.remove(&key);
+            }
+            Self::RenameKey { from, to } => store.rename_key(&from, to)?,
+            Self::RemoveMany { keys } => {
+                store.remove_many(keys);
+            }
+            Self::Extend { items } => store.extend(items),
+            Self::AppendEntries { items } => store.append_entries(items),
+            Self::Clear => store.clear(),
+            Self::Select { key } => store.select(&key)?,
+            Self::ClearSelection => store.clear_selection(),
+            _ => {
+                return Err(CollectionError::InvalidAccess {
+                    reason: "sequential operation replayed on a keyed collection".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply this operation to a sequential store
+    pub fn apply_sequential<C>(self, store: &CollectionStore<C>) -> CollectionResult<()>
+    where
+        C: SequentialCollection<Key = K, Value = V> + 'static,
+        K: Clone + PartialEq,
+        V: Clone,
+    {
+        match self {
+            Self::Push { value } => store.push(value),
+            Self::Pop => {
+                store.pop();
+            }
+            Self::Swap { key1, key2 } => store.swap(&key1, &key2)?,
+            Self::SwapRemove { key } => {
+                store.swap_remove(&key);
+            }
+            Self::InsertBefore { key, value } => {
+                store.insert_before(&key, value)?;
+            }
+            Self::InsertAfter { key, value } => {
+                store.insert_after(&key, value)?;
+            }
+            Self::MoveItem { from, to } => store.move_item(&from, &to)?,
+            Self::Reorder { order } => store.reorder(&order)?,
+            Self::SelectRange { from, to } => store.select_range(&from, &to)?,
+            operation => operation.apply_keyed(store)?,
+        }
+        Ok(())
+    }
+}
+
+/// Mutations captured by `CollectionStore::command_log`, in order
+///
+/// Serializable with the `serde` feature, to attach to a bug report or send to another window,
+/// and applied to a store with `CollectionStore::replay`.
+pub type CollectionCommands<K, V> = Vec<CollectionOperation<K, V>>;
+
+/// A recorded store session: the initial state and every operation applied since
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "C: serde::Serialize, C::Key: serde::Serialize, C::Value: serde::Serialize",
+        deserialize = "C: serde::Deserialize<'de>, C::Key: serde::Deserialize<'de>, C::Value: serde::Deserialize<'de>"
+    ))
+)]
+pub struct RecordingBundle<C>
+where
+    C: Collection,
+{
+    /// The collection when the recording started
+    pub initial: C,
+    /// The selected key when the recording started
+    pub initial_selection: Option<C::Key>,
+    /// The operations applied since, in order
+    pub operations: Vec<CollectionOperation<C::Key, C::Value>>,
+}
+
+/// Recording state kept in the store while a recording is in progress
+pub struct Recorder<C>
+where
+    C: Collection,
+{
+    pub(crate) bundle: RecordingBundle<C>,
+    /// Captured when the recording starts, so mutation methods don't need a `Clone` bound
+    pub(crate) clone_value: fn(&C::Value) -> C::Value,
+    /// When the recording started
+    #[cfg(feature = "devtools")]
+    pub(crate) started: std::time::Instant,
+    /// Time elapsed since `started` for each recorded operation
+    #[cfg(feature = "devtools")]
+    pub(crate) timestamps: Vec<std::time::Duration>,
+}
+
+/// Collections that can replay a `RecordingBundle`
+///
+/// The provided implementation replays keyed operations only.
+/// Sequential collections should forward to `CollectionOperation::apply_sequential`:
+///
+/// ```rust,ignore
+/// impl<T: Clone> Replay for CircularBuffer<T> {
+///     fn apply(store: &CollectionStore<Self>, operation: CollectionOperation<usize, T>) -> CollectionResult<()> {
+///         operation.apply_sequential(store)
+///     }
+/// }
+/// ```
+pub trait Replay: Collection + Sized + 'static
+where
+    Self::Key: PartialEq,
+    Self::Value: Clone,
+{
+    /// Apply a recorded operation to the store
+    fn apply(
+        store: &CollectionStore<Self>,
+        operation: CollectionOperation<Self::Key, Self::Value>,
+    ) -> CollectionResult<()> {
+        operation.apply_keyed(store)
+    }
+}
+
+impl<T: Clone + 'static> Replay for Vec<T> {
+    fn apply(
+        store: &CollectionStore<Self>,
+        operation: CollectionOperation<usize, T>,
+    ) -> CollectionResult<()> {
+        operation.apply_sequential(store)
+    }
+}
+
+impl<K, V> Replay for HashMap<K, V>
+where
+    K: Eq + Hash + Clone + 'static,
+    V: Clone + 'static,
+{
+}
+
+impl<K, V> Replay for BTreeMap<K, V>
+where
+    K: Ord + Clone + 'static,
+    V: Clone + 'static,
+{
+}
+
+impl<T> Replay for BTreeSet<T> where T: Ord + Clone + 'static {}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Start recording the mutations made through this store
+    ///
+    /// The current collection and selection are snapshotted as the starting point.
+    /// Starting a new recording discards the one in progress.
+    /// With the `devtools` feature, each operation is also timestamped for `debug_timeline`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.start_recording();
+    /// store.push(4);
+    /// store.select(&0).ok();
+    /// let bundle = store.stop_recording().unwrap();
+    /// assert_eq!(bundle.operations.len(), 2);
+    /// ```
+    pub fn start_recording(&self)
+    where
+        C: Clone,
+        C::Value: Clone,
+    {
+        let bundle = RecordingBundle {
+            initial: self.items().peek().clone(),
+            initial_selection: self.inner.selected_key().peek().clone(),
+            operations: Vec::new(),
+        };
+        self.inner.recorder().set(Some(Recorder {
+            bundle,
+            clone_value: C::Value::clone,
+            #[cfg(feature = "devtools")]
+            started: std::time::Instant::now(),
+            #[cfg(feature = "devtools")]
+            timestamps: Vec::new(),
+        }));
+    }
+
+    /// Stop recording and return the recorded session
+    ///
+    /// Returns `None` if no recording was in progress.
+    pub fn stop_recording(&self) -> Option<RecordingBundle<C>> {
+        self.inner
+            .recorder()
+            .write()
+            .take()
+            .map(|recorder| recorder.bundle)
+    }
+
+    /// Check if a recording is in progress
+    pub fn is_recording(&self) -> bool {
+        self.inner.recorder().read().is_some()
+    }
+
+    /// Create a new store from a recorded session, replaying every operation
+    ///
+    /// Fails if an operation can't be applied, which means the replay diverged from the recording.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.start_recording();
+    /// store.push(4);
+    /// let bundle = store.stop_recording().unwrap();
+    ///
+    /// let replayed = CollectionStore::replay_bundle(bundle).unwrap();
+    /// assert_eq!(replayed.len(), 4);
+    /// ```
+    pub fn replay_bundle(bundle: RecordingBundle<C>) -> CollectionResult<Self>
+    where
+        C: Replay,
+        C::Value: Clone,
+    {
+        let store = Self::new(bundle.initial);
+        if let Some(key) = bundle.initial_selection {
+            store.select(&key)?;
+        }
+        store.replay(bundle.operations)?;
+        Ok(store)
+    }
+
+    /// Get the mutations recorded since `start_recording`, without stopping the recording
+    ///
+    /// Returns an empty log if no recording is in progress.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.start_recording();
+    /// store.push(4);
+    /// let commands = store.command_log();
+    ///
+    /// // Apply the same mutations to a store in another window
+    /// let mirror = CollectionStore::new(vec![1, 2, 3]);
+    /// mirror.replay(commands).unwrap();
+    /// assert_eq!(mirror.len(), 4);
+    /// ```
+    pub fn command_log(&self) -> CollectionCommands<C::Key, C::Value>
+    where
+        C::Value: Clone,
+    {
+        self.inner
+            .recorder()
+            .peek()
+            .as_ref()
+            .map(|recorder| recorder.bundle.operations.clone())
+            .unwrap_or_default()
+    }
+
+    /// Apply commands to this store, in order
+    ///
+    /// The store should be in the state the commands were captured from, for instance the `initial`
+    /// state of a recording, or a mirror kept in sync with every command. Stops at the first command
+    /// that can't be applied, which means the states diverged.
+    pub fn replay(&self, commands: CollectionCommands<C::Key, C::Value>) -> CollectionResult<()>
+    where
+        C: Replay,
+        C::Value: Clone,
+    {
+        for command in commands {
+            C::apply(self, command)?;
+        }
+        Ok(())
+    }
+
+    /// Record an operation about to be applied, if a recording is in progress, announcements are enabled,
+    /// the store is offline with an offline queue or the store is traced
+    ///
+    /// The operation is only built when needed, using the value cloner captured by `start_recording`,
+    /// `enable_announcements`, `enable_offline_queue` or `enable_tracing`.
+    pub(crate) fn record(
+        &self,
+        operation: impl FnOnce(fn(&C::Value) -> C::Value) -> CollectionOperation<C::Key, C::Value>,
+    ) {
+        let mut recorder = self.inner.recorder();
+        let clone_value = recorder
+            .peek()
+            .as_ref()
+            .map(|recorder| recorder.clone_value);
+        #[cfg(feature = "announcer")]
+        let clone_value = clone_value.or_else(|| {
+            let announcer = self.inner.announcer();
+            let announcer = announcer.peek();
+            announcer.as_ref().map(|announcer| announcer.clone_value)
+        });
+        #[cfg(feature = "offline")]
+        let clone_value = clone_value.or_else(|| self.offline_clone_value());
+        #[cfg(feature = "tracing")]
+        let clone_value = clone_value.or_else(|| {
+            let tracer = self.inner.tracer();
+            let tracer = tracer.peek();
+            tracer.as_ref().map(|tracer| tracer.clone_value)
+        });
+        #[cfg(feature = "index")]
+        let clone_value = clone_value.or_else(|| self.index_clone_value());
+        let Some(clone_value) = clone_value else {
+            return;
+        };
+        let operation = operation(clone_value);
+        #[cfg(feature = "index")]
+        self.note_index_changes(&operation);
+        #[cfg(feature = "tracing")]
+        self.start_trace(&operation);
+        #[cfg(feature = "announcer")]
+        if self.is_announcing() {
+            self.announce(&operation);
+        }
+        #[cfg(feature = "offline")]
+        self.queue_offline(&operation);
+        if let Some(recorder) = recorder.write().as_mut() {
+            #[cfg(feature = "devtools")]
+            recorder.timestamps.push(recorder.started.elapsed());
+            recorder.bundle.operations.push(operation);
+        }
+    }
+
+    /// Finish a mutation once it is applied: emit the trace event of the operation passed to `record`,
+    /// if the store is traced, then check the store invariants in strict mode
+    pub(crate) fn finish_mutation(&self, operation: &str) {
+        #[cfg(feature = "tracing")]
+        self.finish_trace(operation);
+        self.check_invariants(operation);
+    }
+}
@@ -0,0 +1,220 @@
+//! Refreshing a store from fetched contents
+//!
+//! Replacing the collection with a freshly fetched one clears the selection, the focus and the item metadata,
+//! and notifies every reader even if nothing changed. `store.reconcile(items, key)` and
+//! `store.refresh_from(fresh)` diff the fetched contents against the current ones by key instead, in a single
+//! write: unchanged items keep their state, changed ones are updated, new ones inserted and missing ones
+//! removed. With the `polling` feature, `use_polling_collection(interval, fetch)` refreshes a store on an
+//! interval.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionOperation, CollectionResult, CollectionStore};
+#[cfg(feature = "polling")]
+use dioxus_core::{spawn, use_hook};
+use dioxus_signals::*;
+use std::collections::HashSet;
+#[cfg(feature = "polling")]
+use std::future::Future;
+use std::hash::Hash;
+#[cfg(feature = "polling")]
+use std::time::Duration;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Clone + PartialEq,
+{
+    /// Update the collection to match `fresh`, only writing the items that changed
+    ///
+    /// Like `reconcile`, with the keys of `fresh`. Sequential collections are compared position by position.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let prices = CollectionStore::new(BTreeMap::from([("btc", 60_000), ("eth", 3_000)]));
+    /// prices.select(&"eth").unwrap();
+    /// prices.refresh_from(BTreeMap::from([("eth", 3_100), ("sol", 150)])).unwrap();
+    /// assert_eq!(prices.peek_value(&"eth"), Some(3_100));
+    /// assert_eq!(prices.selected_key(), Some("eth"));
+    /// ```
+    pub fn refresh_from(&self, mut fresh: C) -> CollectionResult<()> {
+        let removed = self.missing_positions(|key| fresh.contains_key(key));
+        self.reconcile_entries(removed, fresh.drain())
+    }
+
+    /// Update the collection to match `new_items`, keyed by `key`, in a single write
+    ///
+    /// New keys are inserted, missing ones removed and changed values updated, while identical items aren't
+    /// written and keep their selection, focus and metadata. The changes are recorded as one `RemoveMany`,
+    /// then a `Set` or an `Insert` per changed item. If `key` gives the same key to several items, the last
+    /// one wins. The missing keys are found with a hash set of the new keys, in one pass over the collection.
+    /// Fails with `Locked`, or with `InvalidAccess` for positional collections like `Vec`, whose
+    /// keys can't be chosen (see `refresh_from`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
+    ///
+    /// #[derive(Clone, PartialEq)]
+    /// struct User { id: u32, name: &'static str }
+    ///
+    /// let users = CollectionStore::new(HashMap::from([(1, User { id: 1, name: "Ada" })]));
+    /// users.select(&1).unwrap();
+    /// let fetched = vec![User { id: 1, name: "Ada" }, User { id: 2, name: "Alan" }];
+    /// users.reconcile(fetched, |user| user.id).unwrap();
+    /// assert_eq!(users.len(), 2);
+    /// assert_eq!(users.selected_key(), Some(1));
+    /// ```
+    pub fn reconcile(
+        &self,
+        new_items: impl IntoIterator<Item = C::Value>,
+        key: impl Fn(&C::Value) -> C::Key,
+    ) -> CollectionResult<()>
+    where
+        C::Key: Hash + Eq,
+    {
+        if C::POSITIONAL_KEYS {
+            return Err(CollectionError::InvalidAccess {
+                reason: "the keys of a positional collection can't be chosen, use `refresh_from`"
+                    .to_string(),
+            });
+        }
+        let entries: Vec<(C::Key, C::Value)> = new_items
+            .into_iter()
+            .map(|value| (key(&value), value))
+            .collect();
+        let removed = {
+            let incoming: HashSet<&C::Key> = entries.iter().map(|(key, _)| key).collect();
+            self.missing_positions(|key| incoming.contains(key))
+        };
+        self.reconcile_entries(removed, entries)
+    }
+
+    /// Get the positions of the items whose key isn't `incoming`, in collection order
+    fn missing_positions(&self, incoming: impl Fn(&C::Key) -> bool) -> Vec<usize> {
+        let items = self.inner.items();
+        let items = items.peek();
+        items
+            .key_iter()
+            .enumerate()
+            .filter(|(_, key)| !incoming(key))
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// Remove the items at the `removed` positions, then insert or update `entries`, in a single write
+    fn reconcile_entries(
+        &self,
+        removed: Vec<usize>,
+        entries: impl IntoIterator<Item = (C::Key, C::Value)>,
+    ) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        let mut entries: Vec<(C::Key, C::Value)> = entries.into_iter().collect();
+        let (old_keys, changes) = {
+            let items = self.inner.items();
+            let items = items.peek();
+            let old_keys = items.keys();
+            entries.retain(|(key, value)| items.get(key) != Some(value));
+            let changes: Vec<(C::Key, C::Value, bool)> = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let exists = items.contains_key(&key);
+                    (key, value, exists)
+                })
+                .collect();
+            (old_keys, changes)
+        };
+        if removed.is_empty() && changes.is_empty() {
+            return Ok(());
+        }
+
+        if !removed.is_empty() {
+            self.record(|_| CollectionOperation::RemoveMany {
+                keys: removed
+                    .iter()
+                    .map(|position| old_keys[*position].clone())
+                    .collect(),
+            });
+        }
+        for (key, value, exists) in &changes {
+            self.record(|clone| {
+                let (key, value) = (key.clone(), clone(value));
+                if *exists {
+                    CollectionOperation::Set { key, value }
+                } else {
+                    CollectionOperation::Insert { key, value }
+                }
+            });
+        }
+
+        let tracked_keys = self.tracked_keys();
+        {
+            let mut items = self.inner.items();
+            let mut items = items.write();
+            let mut position = 0;
+            items.retain(|_, _| {
+                let kept = removed.binary_search(&position).is_err();
+                position += 1;
+                kept
+            });
+            for (key, value, _) in changes {
+                items.insert(key, value);
+            }
+        }
+        let new_position = |position: usize| {
+            if removed.binary_search(&position).is_ok() {
+                return None;
+            }
+            Some(position - removed.partition_point(|removed| *removed < position))
+        };
+        self.remap_selection(&old_keys, &new_position);
+        self.remap_tracked(tracked_keys, new_position);
+        self.finish_mutation("reconcile");
+        Ok(())
+    }
+}
+
+/// Hook creating a store refreshed with `fetch` every `interval`
+///
+/// The store starts empty and is refreshed with `refresh_from` right away, then `interval` after each fetch
+/// completes. `fetch` returns `None` when it fails, to keep the current contents until the next attempt,
+/// and refreshes are skipped while the store is locked.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let orders = use_polling_collection(Duration::from_secs(10), || async {
+///     api.orders().await.ok().map(|orders| orders.into_iter().map(|o| (o.id, o)).collect::<HashMap<_, _>>())
+/// });
+/// ```
+#[cfg(feature = "polling")]
+#[cfg_attr(docsrs, doc(cfg(feature = "polling")))]
+pub fn use_polling_collection<C, F>(
+    interval: Duration,
+    mut fetch: impl FnMut() -> F + 'static,
+) -> CollectionStore<C>
+where
+    C: Collection + Default + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Clone + PartialEq,
+    F: Future<Output = Option<C>> + 'static,
+{
+    use_hook(move || {
+        let store = CollectionStore::new(C::default());
+        spawn(async move {
+            loop {
+                if let Some(fresh) = fetch().await {
+                    store.refresh_from(fresh).ok();
+                }
+                futures_timer::Delay::new(interval).await;
+            }
+        });
+        store
+    })
+}
@@ -0,0 +1,83 @@
+//! Renaming the key of an item
+//!
+//! Maps keyed by a user-editable identifier (a name, a slug...) need to change the key of an item in place.
+//! Removing and inserting it again would drop its selection, focus and metadata; `store.rename_key(&old, new)`
+//! moves the value and all the per-item state to the new key, as a single `RenameKey` operation.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionOperation, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Move the item under `from` to the key `to`, with its selection, focus and metadata
+    ///
+    /// Handles to `from` are stale afterwards when generations are tracked. Fails with `KeyNotFound` if `from`
+    /// doesn't exist, with `DuplicateKey` if `to` is already used, and with `InvalidAccess` for positional
+    /// collections like `Vec`, whose keys can't be chosen.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
+    ///
+    /// let files = CollectionStore::new(HashMap::from([("draft.txt", 120)]));
+    /// files.select(&"draft.txt").unwrap();
+    /// files.rename_key(&"draft.txt", "notes.txt").unwrap();
+    /// assert_eq!(files.peek_value(&"notes.txt"), Some(120));
+    /// assert_eq!(files.selected_key(), Some("notes.txt"));
+    /// ```
+    pub fn rename_key(&self, from: &C::Key, to: C::Key) -> CollectionResult<()> {
+        if C::POSITIONAL_KEYS {
+            return Err(CollectionError::InvalidAccess {
+                reason: "the keys of a positional collection can't be renamed".to_string(),
+            });
+        }
+        self.ensure_unlocked()?;
+        if !self.peek_contains_key(from) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        if *from == to {
+            return Ok(());
+        }
+        if self.peek_contains_key(&to) {
+            return Err(CollectionError::DuplicateKey);
+        }
+        self.record(|_| CollectionOperation::RenameKey {
+            from: from.clone(),
+            to: to.clone(),
+        });
+        {
+            let mut items = self.inner.items();
+            let mut items = items.write();
+            if let Some(value) = items.remove(from) {
+                items.insert(to.clone(), value);
+            }
+        }
+        self.rename_tracked(from, &to);
+        self.finish_mutation("rename_key");
+        Ok(())
+    }
+
+    /// Move the selection, the focus and the other per-item state from `from` to `to`
+    fn rename_tracked(&self, from: &C::Key, to: &C::Key) {
+        let renamed = |key: &Option<C::Key>| key.as_ref() == Some(from);
+        if renamed(&self.inner.selected_key().peek()) {
+            self.inner.selected_key().set(Some(to.clone()));
+        }
+        if renamed(&self.inner.selection_anchor().peek()) {
+            self.inner.selection_anchor().set(Some(to.clone()));
+        }
+        if renamed(&self.inner.focused_key().peek()) {
+            self.inner.focused_key().set(Some(to.clone()));
+        }
+        self.rename_meta(from, to);
+        if self.is_tracking_generations() {
+            self.expire_generations(vec![from.clone()]);
+        }
+    }
+}
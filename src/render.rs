@@ -0,0 +1,163 @@
+//! Keyed rendering of the collection items
+//!
+//! A plain `for item in store.iter()` loop in `rsx!` renders unkeyed nodes: when an item is added or removed,
+//! the renderer diffs the following items by position and re-renders them. `store.render_items(render)` renders
+//! each item in its own component, keyed by the item key, so unchanged items keep their state and are skipped
+//! when the list re-renders.
+
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus_core::{DynamicNode, Element, Properties, Template, TemplateNode, VComponent, VNode};
+use std::fmt::Display;
+use std::rc::Rc;
+
+/// Renders the node of an item
+type RenderItem<C> = Rc<dyn Fn(CollectionItem<C>) -> Element>;
+
+/// A template with a single dynamic root
+static DYNAMIC_ROOT: Template = Template {
+    roots: &[TemplateNode::Dynamic { id: 0 }],
+    node_paths: &[&[0]],
+    attr_paths: &[],
+};
+
+/// Props of the component rendering an item
+struct ItemProps<C>
+where
+    C: Collection + 'static,
+{
+    item: CollectionItem<C>,
+    render: RenderItem<C>,
+}
+
+impl<C> Clone for ItemProps<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            item: CollectionItem {
+                store: self.item.store,
+                key: self.item.key.clone(),
+                generation: self.item.generation,
+            },
+            render: self.render.clone(),
+        }
+    }
+}
+
+impl<C> Properties for ItemProps<C>
+where
+    C: Collection + 'static,
+    C::Key: PartialEq,
+{
+    type Builder = ();
+
+    fn builder() -> Self::Builder {}
+
+    /// The item is only re-rendered if it's another item, or when the values it reads change
+    fn memoize(&mut self, other: &Self) -> bool {
+        let unchanged = self.item.store == other.item.store
+            && self.item.key == other.item.key
+            && self.item.generation == other.item.generation;
+        self.render = other.render.clone();
+        unchanged
+    }
+}
+
+fn render_item<C>(props: ItemProps<C>) -> Element
+where
+    C: Collection + 'static,
+{
+    (props.render)(props.item)
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Display,
+{
+    /// Render every item in its own component, keyed by the item key
+    ///
+    /// An item's component only re-renders when the values it reads change, or when another item takes its key:
+    /// a parent re-rendering doesn't re-render the items, and `render` shouldn't depend on other parent state.
+    /// Positional collections like `Vec` key items by position, so an insertion re-renders the items after it.
+    /// This subscribes to the collection like `iter()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus::prelude::*;
+    /// use dioxus_collection_store::use_collection;
+    /// use std::collections::BTreeMap;
+    ///
+    /// fn Users() -> Element {
+    ///     let users = use_collection(|| BTreeMap::from([(1, "Ada"), (2, "Alan")]));
+    ///     rsx! {
+    ///         ul {
+    ///             {users.render_items(|user| rsx! { li { "{user.read()}" } })}
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn render_items(&self, render: impl Fn(CollectionItem<C>) -> Element + 'static) -> Element
+    where
+        C::Key: 'static,
+    {
+        self.render_items_keyed(|key| key.to_string(), render)
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Render every item in its own component, keyed by `node_key(key)`, for keys that don't implement `Display`
+    ///
+    /// `node_key` must give a distinct string for each key, see `render_items`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// #[derive(Clone, PartialEq, Eq, Hash)]
+    /// struct TaskId(Uuid);
+    ///
+    /// tasks.render_items_keyed(|id| id.0.to_string(), |task| rsx! { li { "{task.read().title}" } })
+    /// ```
+    pub fn render_items_keyed(
+        &self,
+        node_key: impl Fn(&C::Key) -> String,
+        render: impl Fn(CollectionItem<C>) -> Element + 'static,
+    ) -> Element
+    where
+        C::Key: 'static,
+    {
+        let render: RenderItem<C> = Rc::new(render);
+        let children: Vec<VNode> = self
+            .iter()
+            .map(|item| {
+                let key = node_key(&item.key);
+                let props = ItemProps {
+                    item,
+                    render: render.clone(),
+                };
+                let component = VComponent::new(render_item::<C>, props, "CollectionItem");
+                VNode::new(
+                    Some(key),
+                    DYNAMIC_ROOT,
+                    Box::new([DynamicNode::Component(component)]),
+                    Box::new([]),
+                )
+            })
+            .collect();
+        if children.is_empty() {
+            return VNode::empty();
+        }
+        Ok(VNode::new(
+            None,
+            DYNAMIC_ROOT,
+            Box::new([DynamicNode::Fragment(children)]),
+            Box::new([]),
+        ))
+    }
+}
@@ -0,0 +1,103 @@
+//! `<select>` integration with key-encoded option values
+//!
+//! `select_by_display` can't tell apart items that render identically. Instead, each `<option>` can carry
+//! its item's key, encoded with `Display` by `option_value` and decoded with `FromStr` by
+//! `select_by_option_value`. The empty value stands for "no selection":
+//!
+//! ```rust,ignore
+//! let onchange = use_collection_select(store);
+//! rsx! {
+//!     select {
+//!         value: store.selected_option_value(),
+//!         onchange: move |event| onchange.call(event.value()),
+//!         option { value: "", "-- Choose --" }
+//!         for item in store.iter() {
+//!             option { value: store.option_value(&item.key()), "{item}" }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::{Collection, CollectionError, CollectionResult, CollectionStore};
+use dioxus_core::{Callback, use_hook};
+use std::fmt::Display;
+use std::str::FromStr;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Encode a key as an `<option>` value
+    pub fn option_value(&self, key: &C::Key) -> String
+    where
+        C::Key: Display,
+    {
+        key.to_string()
+    }
+
+    /// Get the option value of the selected item, empty if nothing is selected (reactive)
+    pub fn selected_option_value(&self) -> String
+    where
+        C::Key: Display,
+    {
+        self.selected_key()
+            .map(|key| self.option_value(&key))
+            .unwrap_or_default()
+    }
+
+    /// Select the item whose key is encoded in an `<option>` value
+    ///
+    /// The empty value clears the selection. Fails with `CollectionError::KeyNotFound`
+    /// if the value doesn't decode to a key of the collection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["same", "same"]);
+    /// let value = store.option_value(&1);
+    /// store.select_by_option_value(&value).unwrap();
+    /// assert_eq!(store.selected_key(), Some(1));
+    /// ```
+    pub fn select_by_option_value(&self, value: &str) -> CollectionResult<()>
+    where
+        C::Key: FromStr,
+    {
+        if value.is_empty() {
+            self.clear_selection();
+            return Ok(());
+        }
+        let key = value
+            .parse::<C::Key>()
+            .map_err(|_| CollectionError::KeyNotFound)?;
+        self.select(&key)
+    }
+}
+
+/// Hook returning the `onchange` handler of a `<select>` bound to a store
+///
+/// The handler takes the selected option value, see `CollectionStore::select_by_option_value`.
+/// Values that don't decode to a key are ignored.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use dioxus_collection_store::{use_collection, use_collection_select};
+///
+/// let store = use_collection(|| vec!["milk", "eggs"]);
+/// let onchange = use_collection_select(store);
+/// // rsx! { select { onchange: move |event| onchange.call(event.value()), ... } }
+/// ```
+pub fn use_collection_select<C>(store: CollectionStore<C>) -> Callback<String>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + FromStr,
+{
+    use_hook(move || {
+        Callback::new(move |value: String| {
+            store.select_by_option_value(&value).ok();
+        })
+    })
+}
@@ -0,0 +1,179 @@
+//! Selection constrained by a predicate
+//!
+//! Disabled items stay in the collection but can't be selected: `select()` rejects them,
+//! and the other selection helpers skip them.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+use std::rc::Rc;
+
+/// Decides which items can be selected
+pub(crate) type SelectablePredicate<C> =
+    Rc<dyn Fn(&<C as Collection>::Key, &<C as Collection>::Value) -> bool>;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Restrict the selection to the items matching `predicate`
+    ///
+    /// The current selection is cleared if it no longer matches.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionError, CollectionStore};
+    ///
+    /// let store = CollectionStore::new(vec![("draft", false), ("published", true)]);
+    /// store.set_selectable(|_, (_, published)| *published);
+    /// assert_eq!(store.select(&0), Err(CollectionError::NotSelectable));
+    /// assert_eq!(store.select_next(), Some(1));
+    /// ```
+    pub fn set_selectable(&self, predicate: impl Fn(&C::Key, &C::Value) -> bool + 'static) {
+        self.inner.selectable().set(Some(Rc::new(predicate)));
+        let selected = self.inner.selected_key().peek().clone();
+        if selected.is_some_and(|selected| !self.peek_is_selectable(&selected)) {
+            self.clear_selection();
+        }
+    }
+
+    /// Allow every item to be selected again
+    pub fn clear_selectable(&self) {
+        self.inner.selectable().set(None);
+    }
+
+    /// Check if an item can be selected (reactive)
+    ///
+    /// Returns `false` if the key is not in the collection.
+    pub fn is_selectable(&self, key: &C::Key) -> bool {
+        let selectable = self.inner.selectable();
+        let items = self.inner.items();
+        selectable_in::<C>(&items.read(), selectable.read().as_ref(), key)
+    }
+
+    /// Check if an item can be selected, without subscribing to the predicate or the items
+    pub(crate) fn peek_is_selectable(&self, key: &C::Key) -> bool {
+        let selectable = self.inner.selectable();
+        let items = self.inner.items();
+        selectable_in::<C>(&items.peek(), selectable.peek().as_ref(), key)
+    }
+
+    /// Select the next selectable item, or the first one if nothing is selected
+    ///
+    /// The selection stays on the last selectable item. Returns the selected key.
+    pub fn select_next(&self) -> Option<C::Key> {
+        self.select_step(false, false)
+    }
+
+    /// Select the previous selectable item, or the last one if nothing is selected
+    ///
+    /// The selection stays on the first selectable item. Returns the selected key.
+    pub fn select_prev(&self) -> Option<C::Key> {
+        self.select_step(true, false)
+    }
+
+    /// Select the next selectable item, going back to the first one after the last one
+    ///
+    /// Selects the first selectable item if nothing is selected, for carousels and slideshows.
+    /// Returns the selected key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let slides = CollectionStore::new(vec!["intro", "demo", "outro"]);
+    /// slides.select(&2).unwrap();
+    /// assert_eq!(slides.select_next_wrapping(), Some(0));
+    /// assert_eq!(slides.select_prev_wrapping(), Some(2));
+    /// ```
+    pub fn select_next_wrapping(&self) -> Option<C::Key> {
+        self.select_step(false, true)
+    }
+
+    /// Select the previous selectable item, going back to the last one before the first one
+    pub fn select_prev_wrapping(&self) -> Option<C::Key> {
+        self.select_step(true, true)
+    }
+
+    /// Select the closest selectable item after (or before) the selection, wrapping around if `wrapping`
+    fn select_step(&self, backwards: bool, wrapping: bool) -> Option<C::Key> {
+        let mut keys = self.inner.items().peek().keys();
+        if backwards {
+            keys.reverse();
+        }
+        let selected = self.inner.selected_key().peek().clone();
+        let start = match &selected {
+            Some(selected) => keys.iter().position(|k| k == selected)? + 1,
+            None => 0,
+        };
+        let (after, before) = (&keys[start..], &keys[..start]);
+        let wrapped = if wrapping { before } else { &[] };
+        match after
+            .iter()
+            .chain(wrapped)
+            .find(|key| self.peek_is_selectable(key))
+        {
+            Some(key) => {
+                self.select(key).ok()?;
+                Some(key.clone())
+            }
+            None => selected,
+        }
+    }
+
+    /// Find the first selectable item whose value matches `predicate`
+    ///
+    /// Fails with `NotSelectable` if only items that can't be selected match.
+    pub(crate) fn find_selectable(
+        &self,
+        mut predicate: impl FnMut(&C::Value) -> bool,
+    ) -> CollectionResult<C::Key> {
+        let keys = self.inner.items().peek().keys();
+        let mut found = false;
+        for key in keys {
+            let matches = self
+                .inner
+                .items()
+                .peek()
+                .get(&key)
+                .is_some_and(&mut predicate);
+            if matches {
+                if self.peek_is_selectable(&key) {
+                    return Ok(key);
+                }
+                found = true;
+            }
+        }
+        Err(if found {
+            CollectionError::NotSelectable
+        } else {
+            CollectionError::KeyNotFound
+        })
+    }
+}
+
+/// Check `key` against the selectable predicate, if any
+fn selectable_in<C>(items: &C, selectable: Option<&SelectablePredicate<C>>, key: &C::Key) -> bool
+where
+    C: Collection,
+{
+    match (items.get(key), selectable) {
+        (Some(value), Some(predicate)) => predicate(key, value),
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Check if this item can be selected
+    pub fn is_selectable(&self) -> bool {
+        self.store.is_selectable(&self.key)
+    }
+}
@@ -0,0 +1,75 @@
+//! Automatic selection of added items
+//!
+//! "Add" buttons usually select the new item right away. With a `SelectionBehavior` configured on the store,
+//! adding an item selects it in the same call, instead of a second step computing its key.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionStore};
+use dioxus_signals::*;
+
+/// What happens to the selection when an item is added
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelectionBehavior {
+    /// Adding an item doesn't change the selection
+    #[default]
+    Manual,
+    /// `push` selects the new item
+    SelectOnPush,
+    /// Every single-item addition (`insert` of a new key, `push`, `insert_before`, `insert_after`, `duplicate`)
+    /// selects the new item
+    SelectOnInsert,
+}
+
+/// How an item was added, to match against the `SelectionBehavior`
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Addition {
+    Push,
+    Insert,
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Configure what happens to the selection when an item is added
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionStore, SelectionBehavior};
+    ///
+    /// let store = CollectionStore::new(vec!["milk"])
+    ///     .with_selection_behavior(SelectionBehavior::SelectOnPush);
+    /// store.push("eggs");
+    /// assert_eq!(store.selected_key(), Some(1));
+    /// ```
+    pub fn with_selection_behavior(self, behavior: SelectionBehavior) -> Self {
+        self.set_selection_behavior(behavior);
+        self
+    }
+
+    /// Change what happens to the selection when an item is added
+    pub fn set_selection_behavior(&self, behavior: SelectionBehavior) {
+        self.inner.selection_behavior().set(behavior);
+    }
+
+    /// Get what happens to the selection when an item is added
+    pub fn selection_behavior(&self) -> SelectionBehavior {
+        *self.inner.selection_behavior().peek()
+    }
+
+    /// Select an added item if the `SelectionBehavior` asks for it
+    ///
+    /// Items rejected by the `set_selectable` predicate are left unselected.
+    pub(crate) fn select_added(&self, key: &C::Key, addition: Addition) {
+        let select = match self.selection_behavior() {
+            SelectionBehavior::Manual => false,
+            SelectionBehavior::SelectOnPush => addition == Addition::Push,
+            SelectionBehavior::SelectOnInsert => true,
+        };
+        if select {
+            self.select(key).ok();
+        }
+    }
+}
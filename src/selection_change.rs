@@ -0,0 +1,184 @@
+//! Reacting to selection changes with the previous selection
+//!
+//! `selected_key_signal()` only tells which item is selected now. Editors keeping a draft of the selected
+//! item need the previous one too, to save its draft when the user moves on. `store.on_selection_change`
+//! calls a handler with both, without a "previous key" signal next to the store:
+//!
+//! ```rust,ignore
+//! let mut draft = use_signal(String::new);
+//! use_hook(|| {
+//!     notes.on_selection_change(move |old, new| {
+//!         if let Some(old) = old {
+//!             old.set(draft());
+//!         }
+//!         draft.set(new.map(|new| new.read().clone()).unwrap_or_default());
+//!     })
+//! });
+//! ```
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus_signals::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Handler registered with `on_selection_change`
+type SelectionHandler<C> =
+    Rc<RefCell<dyn FnMut(Option<CollectionItem<C>>, Option<CollectionItem<C>>)>>;
+
+/// A handler of a store, kept until `alive` is dropped with its scope or the subscription is cancelled
+pub(crate) struct SelectionListener<C>
+where
+    C: Collection + 'static,
+{
+    handler: SelectionHandler<C>,
+    alive: CopyValue<()>,
+}
+
+impl<C> SelectionListener<C>
+where
+    C: Collection + 'static,
+{
+    fn is_alive(&self) -> bool {
+        self.alive.try_peek_unchecked().is_ok()
+    }
+}
+
+/// A handler registered with `on_selection_change`, see `cancel`
+pub struct SelectionSubscription<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    alive: CopyValue<()>,
+}
+
+impl<C> Clone for SelectionSubscription<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for SelectionSubscription<C> where C: Collection + 'static {}
+
+impl<C> PartialEq for SelectionSubscription<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.alive == other.alive
+    }
+}
+
+impl<C> SelectionSubscription<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Stop calling the handler
+    pub fn cancel(&self) {
+        self.store
+            .inner
+            .selection_listeners()
+            .write()
+            .retain(|listener| listener.alive != self.alive);
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Call `handler` with the previously and newly selected items each time the selected item changes
+    ///
+    /// The handler is called synchronously by the method changing the selection (`select`, `clear_selection`,
+    /// `select_range`...), so the previous item is still the one that was selected: saving a draft into it
+    /// can't overwrite another row. Removing the selected item clears the selection first, calling the
+    /// handler with the item before it is removed. An item only moving to another key, like a `Vec` index
+    /// shifting after a removal, isn't a change. Writes to `selected_key_signal()` bypass the handlers.
+    ///
+    /// The handler belongs to the current component and stops being called once it is dropped, or once the
+    /// returned subscription is cancelled. Call it once per component (in `use_hook` for instance).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus::prelude::*;
+    /// use dioxus_collection_store::use_collection;
+    ///
+    /// fn Notes() -> Element {
+    ///     let notes = use_collection(|| vec!["groceries".to_string(), "ideas".to_string()]);
+    ///     use_hook(|| {
+    ///         notes.on_selection_change(|old, new| {
+    ///             let old = old.map(|item| item.key());
+    ///             let new = new.map(|item| item.key());
+    ///             println!("selection moved from {old:?} to {new:?}");
+    ///         })
+    ///     });
+    ///     rsx! {}
+    /// }
+    /// ```
+    pub fn on_selection_change(
+        &self,
+        handler: impl FnMut(Option<CollectionItem<C>>, Option<CollectionItem<C>>) + 'static,
+    ) -> SelectionSubscription<C> {
+        let alive = CopyValue::new(());
+        self.inner
+            .selection_listeners()
+            .write()
+            .push(SelectionListener {
+                handler: Rc::new(RefCell::new(handler)),
+                alive,
+            });
+        SelectionSubscription {
+            store: *self,
+            alive,
+        }
+    }
+
+    /// Select `key` and call the `on_selection_change` handlers if the selected key changed
+    pub(crate) fn change_selected_key(&self, key: Option<C::Key>) {
+        let old = self.inner.selected_key().peek().clone();
+        if old == key {
+            return;
+        }
+        self.inner.selected_key().set(key.clone());
+
+        let handlers: Vec<SelectionHandler<C>> = {
+            let mut listeners = self.inner.selection_listeners();
+            if listeners.peek().iter().any(|listener| !listener.is_alive()) {
+                listeners.write().retain(SelectionListener::is_alive);
+            }
+            let listeners = listeners.peek();
+            listeners
+                .iter()
+                .map(|listener| listener.handler.clone())
+                .collect()
+        };
+        for handler in handlers {
+            // A handler changing the selection again isn't called back recursively
+            if let Ok(mut handler) = handler.try_borrow_mut() {
+                handler(
+                    old.clone().map(|key| CollectionItem::new(*self, key)),
+                    key.clone().map(|key| CollectionItem::new(*self, key)),
+                );
+            }
+        }
+    }
+
+    /// Clear the selection before removing the items for which `removed` returns `true`
+    ///
+    /// The `on_selection_change` handlers still see the selected item, before its key is reused.
+    pub(crate) fn deselect_removed(&self, removed: impl FnOnce(&C::Key) -> bool) {
+        if self
+            .peek_selected_key()
+            .is_some_and(|selected| removed(&selected))
+        {
+            self.change_selected_key(None);
+        }
+    }
+}
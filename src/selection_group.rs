@@ -0,0 +1,229 @@
+//! One selection across several stores
+//!
+//! Kanban boards and file managers show several lists side by side, but only one item is selected at a time.
+//! A `SelectionGroup` watches the selection of its member stores: when an item is selected in one of them, the
+//! selection of the others is cleared, whether the item was selected through the group or through its store.
+//!
+//! ```rust,ignore
+//! let board = use_hook(|| SelectionGroup::new([todo.selection(), doing.selection(), done.selection()]));
+//! rsx! {
+//!     if let Some(card) = board.active_item() { CardDetail { card } }
+//! }
+//! ```
+
+use crate::waker::ChangeWaker;
+use crate::{
+    Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore, SelectionModel,
+};
+use dioxus_core::{Task, spawn};
+use dioxus_signals::*;
+
+/// Selections of several stores, of which only one holds a selected item
+///
+/// The handle is `Copy`, and its state is owned by the component that created it.
+pub struct SelectionGroup<C>
+where
+    C: Collection + 'static,
+{
+    members: CopyValue<Vec<SelectionModel<C>>>,
+    active: Signal<Option<usize>>,
+    /// Selected key of each member when the watcher last ran, or after the group changed the selection
+    seen: CopyValue<Vec<Option<C::Key>>>,
+    watcher: Task,
+}
+
+impl<C> Copy for SelectionGroup<C> where C: Collection + 'static {}
+
+impl<C> Clone for SelectionGroup<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> PartialEq for SelectionGroup<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.members == other.members
+    }
+}
+
+impl<C> SelectionGroup<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Group the selections of several stores
+    ///
+    /// If more than one member has a selected item, the first one keeps it. Must be called from a Dioxus
+    /// runtime, once per component (in `use_hook` for instance): selections made through the stores are
+    /// enforced by a task of the current component.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionStore, SelectionGroup};
+    ///
+    /// let todo = CollectionStore::new(vec!["write docs"]);
+    /// let done = CollectionStore::new(vec!["release"]);
+    /// let board = SelectionGroup::new([todo.selection(), done.selection()]);
+    /// board.select(1, &0).unwrap();
+    /// todo.select(&0).unwrap();
+    /// // Once the group's task has run, `done` has no selection anymore
+    /// ```
+    pub fn new(members: impl IntoIterator<Item = SelectionModel<C>>) -> Self {
+        let members: Vec<SelectionModel<C>> = members.into_iter().collect();
+        let active = members
+            .iter()
+            .position(|member| member.peek_key().is_some());
+        let members = CopyValue::new(members);
+        let mut active = Signal::new(active);
+        if let Some(index) = *active.peek() {
+            clear_others(members, index);
+        }
+        let mut seen = CopyValue::new(peek_keys(members));
+
+        let changed = ChangeWaker::default();
+        let context = changed.reactive_context();
+        let watcher = spawn(async move {
+            loop {
+                let keys: Vec<Option<C::Key>> = context.reset_and_run_in(|| {
+                    members.read().iter().map(|member| member.key()).collect()
+                });
+                let newly_selected = {
+                    let seen = seen.peek();
+                    keys.iter().enumerate().rposition(|(index, key)| {
+                        key.is_some()
+                            && seen
+                                .as_slice()
+                                .get(index)
+                                .is_none_or(|previous| previous != key)
+                    })
+                };
+                let current = *active.peek();
+                let next = match newly_selected {
+                    Some(index) => Some(index),
+                    None => current.filter(|index| keys[*index].is_some()),
+                };
+                if let Some(index) = next {
+                    clear_others(members, index);
+                }
+                if next != current {
+                    active.set(next);
+                }
+                seen.set(peek_keys(members));
+                changed.changed().await;
+            }
+        });
+
+        Self {
+            members,
+            active,
+            seen,
+            watcher,
+        }
+    }
+
+    /// Get the selections of the member stores
+    pub fn members(&self) -> Vec<SelectionModel<C>> {
+        self.members.peek().clone()
+    }
+
+    /// Select an item in a member store, clearing the selection of the others
+    ///
+    /// Fails with `OutOfBounds` for a missing member, or with the error of `CollectionStore::select`.
+    pub fn select(&self, member: usize, key: &C::Key) -> CollectionResult<()> {
+        let selection = self.member(member)?;
+        selection.select(key)?;
+        clear_others(self.members, member);
+        let mut active = self.active;
+        if *active.peek() != Some(member) {
+            active.set(Some(member));
+        }
+        self.remember_selection();
+        Ok(())
+    }
+
+    /// Clear the selection of every member store
+    pub fn clear(&self) {
+        for member in self.members.peek().iter() {
+            if member.peek_key().is_some() {
+                member.clear();
+            }
+        }
+        let mut active = self.active;
+        if active.peek().is_some() {
+            active.set(None);
+        }
+        self.remember_selection();
+    }
+
+    /// Get the index of the member store holding the selection (reactive)
+    pub fn active_index(&self) -> Option<usize> {
+        *self.active.read()
+    }
+
+    /// Get the member store holding the selection (reactive)
+    pub fn active_store(&self) -> Option<CollectionStore<C>> {
+        let index = self.active_index()?;
+        Some(self.members.peek()[index].store())
+    }
+
+    /// Get the selected item, in whichever member store it is (reactive)
+    pub fn active_item(&self) -> Option<CollectionItem<C>> {
+        self.active_store()?.selected()
+    }
+
+    /// Stop enforcing the single selection for selections made through the stores
+    pub fn stop(&self) {
+        self.watcher.cancel();
+    }
+
+    /// Record the selection changed by the group, so the watcher doesn't take it for a selection made in a store
+    fn remember_selection(&self) {
+        let mut seen = self.seen;
+        seen.set(peek_keys(self.members));
+    }
+
+    fn member(&self, member: usize) -> CollectionResult<SelectionModel<C>> {
+        let members = self.members.peek();
+        members
+            .as_slice()
+            .get(member)
+            .copied()
+            .ok_or(CollectionError::OutOfBounds {
+                index: member,
+                len: members.len(),
+            })
+    }
+}
+
+/// Clear the selection of every member but `active`
+fn clear_others<C>(members: CopyValue<Vec<SelectionModel<C>>>, active: usize)
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    for (index, member) in members.peek().iter().enumerate() {
+        if index != active && member.peek_key().is_some() {
+            member.clear();
+        }
+    }
+}
+
+/// Get the selected key of every member, without subscribing
+fn peek_keys<C>(members: CopyValue<Vec<SelectionModel<C>>>) -> Vec<Option<C::Key>>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    members
+        .peek()
+        .iter()
+        .map(|member| member.peek_key())
+        .collect()
+}
@@ -0,0 +1,193 @@
+//! Confirming selection changes
+//!
+//! Editors with unsaved changes need to ask before the selection moves to another item. With
+//! `store.set_selection_guard(guard)`, `select()` first asks `guard(current, next)`, which allows the change,
+//! blocks it, redirects it to another item, or answers later with `SelectionDecision::confirm`, to wait for a
+//! confirmation dialog for instance. Meanwhile `select()` returns `CollectionError::SelectionPending` and
+//! `store.pending_selection()` holds the requested key:
+//!
+//! ```rust,ignore
+//! notes.set_selection_guard(move |_, _| {
+//!     if !draft.read().is_dirty() {
+//!         return SelectionDecision::Allow;
+//!     }
+//!     SelectionDecision::confirm(async move {
+//!         match dialog.ask("Discard your changes?").await {
+//!             true => SelectionDecision::Allow,
+//!             false => SelectionDecision::Block,
+//!         }
+//!     })
+//! });
+//! ```
+//!
+//! The guard covers `select()` and the helpers built on it (`select_next`, `select_by_value`,
+//! `item.select()`...), not clearing the selection or the selection changes caused by removing items.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_core::{Task, spawn};
+use dioxus_signals::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+/// Answer of a selection guard to a selection change
+pub enum SelectionDecision<K> {
+    /// Select the requested item
+    Allow,
+    /// Keep the current selection
+    Block,
+    /// Select another item instead, without asking the guard again
+    Redirect(K),
+    /// Decide once the future completes, see `SelectionDecision::confirm`
+    Pending(Pin<Box<dyn Future<Output = SelectionDecision<K>>>>),
+}
+
+impl<K> SelectionDecision<K> {
+    /// Decide once `decision` completes, like after a confirmation dialog
+    pub fn confirm(decision: impl Future<Output = SelectionDecision<K>> + 'static) -> Self {
+        Self::Pending(Box::pin(decision))
+    }
+}
+
+/// Decides whether the selection can move from the current item to the next one
+type GuardFn<C> = Rc<
+    dyn Fn(
+        Option<CollectionItem<C>>,
+        CollectionItem<C>,
+    ) -> SelectionDecision<<C as Collection>::Key>,
+>;
+
+/// Selection guard kept in the store while it is set
+pub(crate) struct SelectionGuard<C>
+where
+    C: Collection + 'static,
+{
+    decide: GuardFn<C>,
+    /// Requested key and the task waiting for its decision
+    pending: Option<(C::Key, Task)>,
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Ask `guard` before each selection change made with `select()`
+    ///
+    /// `guard` receives the selected item, if any, and the requested one. Selecting the item that is already
+    /// selected doesn't ask it. Pending decisions run in a task of the current component, so an async guard
+    /// must be set from a Dioxus runtime. A new selection request cancels the pending one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionError, CollectionStore, SelectionDecision};
+    /// use dioxus_signals::*;
+    ///
+    /// let notes = CollectionStore::new(vec!["draft", "archived", "todo"]);
+    /// notes.set_selection_guard(|_, next| match *next.read() {
+    ///     "archived" => SelectionDecision::Block,
+    ///     "draft" => SelectionDecision::Redirect(2),
+    ///     _ => SelectionDecision::Allow,
+    /// });
+    /// assert_eq!(notes.select(&1), Err(CollectionError::SelectionBlocked));
+    /// notes.select(&0).unwrap();
+    /// assert_eq!(notes.selected_key(), Some(2));
+    /// ```
+    pub fn set_selection_guard(
+        &self,
+        guard: impl Fn(Option<CollectionItem<C>>, CollectionItem<C>) -> SelectionDecision<C::Key>
+        + 'static,
+    ) {
+        self.cancel_pending_selection();
+        self.inner.selection_guard().set(Some(SelectionGuard {
+            decide: Rc::new(guard),
+            pending: None,
+        }));
+    }
+
+    /// Let the selection change freely again, cancelling the pending selection change
+    pub fn clear_selection_guard(&self) {
+        self.cancel_pending_selection();
+        self.inner.selection_guard().set(None);
+    }
+
+    /// Get the key waiting for the decision of the selection guard (reactive)
+    pub fn pending_selection(&self) -> Option<C::Key> {
+        self.inner
+            .selection_guard()
+            .read()
+            .as_ref()?
+            .pending
+            .as_ref()
+            .map(|(key, _)| key.clone())
+    }
+
+    /// Drop the pending selection change, keeping the current selection
+    pub fn cancel_pending_selection(&self) {
+        let pending = self
+            .inner
+            .selection_guard()
+            .peek()
+            .as_ref()
+            .and_then(|guard| guard.pending.as_ref().map(|(_, task)| *task));
+        if let Some(task) = pending {
+            task.cancel();
+            self.set_pending_selection(None);
+        }
+    }
+
+    /// Ask the selection guard about selecting `key`, returning the key to select
+    ///
+    /// Fails with `SelectionBlocked`, or with `SelectionPending` while an async decision is awaited.
+    pub(crate) fn guard_selection(&self, key: &C::Key) -> CollectionResult<C::Key> {
+        let decide = self
+            .inner
+            .selection_guard()
+            .peek()
+            .as_ref()
+            .map(|guard| guard.decide.clone());
+        let Some(decide) = decide else {
+            return Ok(key.clone());
+        };
+        self.cancel_pending_selection();
+        let current = self.peek_selected_key();
+        if current.as_ref() == Some(key) {
+            return Ok(key.clone());
+        }
+        let current = current.map(|current| CollectionItem::new(*self, current));
+        match decide(current, CollectionItem::new(*self, key.clone())) {
+            SelectionDecision::Allow => Ok(key.clone()),
+            SelectionDecision::Block => Err(CollectionError::SelectionBlocked),
+            SelectionDecision::Redirect(target) => Ok(target),
+            SelectionDecision::Pending(mut decision) => {
+                let store = *self;
+                let requested = key.clone();
+                let task = spawn(async move {
+                    let target = loop {
+                        match decision.await {
+                            SelectionDecision::Allow => break Some(requested),
+                            SelectionDecision::Block => break None,
+                            SelectionDecision::Redirect(target) => break Some(target),
+                            SelectionDecision::Pending(next) => decision = next,
+                        }
+                    };
+                    store.set_pending_selection(None);
+                    if let Some(target) = target {
+                        store.select_unguarded(&target).ok();
+                    }
+                });
+                self.set_pending_selection(Some((key.clone(), task)));
+                Err(CollectionError::SelectionPending)
+            }
+        }
+    }
+
+    /// Record the selection change waiting for a decision
+    fn set_pending_selection(&self, pending: Option<(C::Key, Task)>) {
+        if let Some(guard) = self.inner.selection_guard().write().as_mut() {
+            guard.pending = pending;
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! The selection as its own reactive object
+//!
+//! `store.selection()` gathers the single selection, the range selection and the focus cursor behind one handle.
+//! Its reads only subscribe to the selection state: a toolbar showing "3 selected" or a detail pane following
+//! the selected key doesn't re-render when the values of the items change. Ranges are the exception, they
+//! need the order of the keys and subscribe to the collection while one is selected.
+//!
+//! ```rust,ignore
+//! let selection = tasks.selection();
+//! rsx! {
+//!     button { disabled: selection.is_empty(), onclick: move |_| delete(selection.keys()),
+//!         "Delete {selection.len()} tasks"
+//!     }
+//! }
+//! ```
+
+use crate::{Collection, CollectionItem, CollectionResult, CollectionStore, SequentialCollection};
+use dioxus_signals::*;
+
+/// The selection and the focus of a store
+///
+/// Created with `CollectionStore::selection`. It holds no state of its own, so it can be created anywhere.
+pub struct SelectionModel<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+}
+
+impl<C> Copy for SelectionModel<C> where C: Collection + 'static {}
+
+impl<C> Clone for SelectionModel<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> PartialEq for SelectionModel<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the selection of the store as its own object
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec!["a", "b", "c"]);
+    /// let selection = store.selection();
+    /// selection.select(&1).unwrap();
+    /// assert_eq!(selection.key(), Some(1));
+    /// assert_eq!(selection.len(), 1);
+    /// ```
+    pub fn selection(&self) -> SelectionModel<C> {
+        SelectionModel { store: *self }
+    }
+}
+
+impl<C> SelectionModel<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the store of the selection
+    pub fn store(&self) -> CollectionStore<C> {
+        self.store
+    }
+
+    /// Get the selected key (reactive)
+    ///
+    /// With a range, this is the end of the range, where the selection was extended to.
+    pub fn key(&self) -> Option<C::Key> {
+        self.store.selected_key()
+    }
+
+    /// Get the selected key, without subscribing
+    pub fn peek_key(&self) -> Option<C::Key> {
+        self.store.peek_selected_key()
+    }
+
+    /// Get the selected item (reactive)
+    pub fn item(&self) -> Option<CollectionItem<C>> {
+        self.store.selected()
+    }
+
+    /// Get the selected keys, in collection order (reactive)
+    pub fn keys(&self) -> Vec<C::Key> {
+        self.store.selected_keys()
+    }
+
+    /// Iterate over the selected items, in collection order (reactive)
+    pub fn iter(&self) -> impl Iterator<Item = CollectionItem<C>> + '_ {
+        let store = self.store;
+        self.keys()
+            .into_iter()
+            .map(move |key| CollectionItem::new(store, key))
+    }
+
+    /// Get the number of selected items (reactive)
+    pub fn len(&self) -> usize {
+        match self.store.selection_anchor() {
+            Some(_) => self.keys().len(),
+            None => usize::from(self.key().is_some()),
+        }
+    }
+
+    /// Check if nothing is selected (reactive)
+    pub fn is_empty(&self) -> bool {
+        self.key().is_none()
+    }
+
+    /// Get a memo of the number of selected items, which only notifies its readers when the count changes
+    ///
+    /// Create it once, in `use_hook` for instance.
+    pub fn len_memo(&self) -> Memo<usize> {
+        let selection = *self;
+        Memo::new(move || selection.len())
+    }
+
+    /// Check if an item is selected, including as part of a range (reactive)
+    pub fn contains(&self, key: &C::Key) -> bool {
+        if self.key().as_ref() == Some(key) {
+            return true;
+        }
+        self.store.selection_anchor().is_some() && self.keys().contains(key)
+    }
+
+    /// Get the key the range selection started from, if a range is selected (reactive)
+    pub fn anchor(&self) -> Option<C::Key> {
+        self.store.selection_anchor()
+    }
+
+    /// Select a single item
+    pub fn select(&self, key: &C::Key) -> CollectionResult<()> {
+        self.store.select(key)
+    }
+
+    /// Select the next selectable item, see `CollectionStore::select_next`
+    pub fn select_next(&self) -> Option<C::Key> {
+        self.store.select_next()
+    }
+
+    /// Select the previous selectable item, see `CollectionStore::select_prev`
+    pub fn select_prev(&self) -> Option<C::Key> {
+        self.store.select_prev()
+    }
+
+    /// Clear the selection, range included
+    pub fn clear(&self) {
+        self.store.clear_selection();
+    }
+
+    /// Get the focused key (reactive)
+    pub fn focused_key(&self) -> Option<C::Key> {
+        self.store.focused_key()
+    }
+
+    /// Focus an item without selecting it
+    pub fn focus(&self, key: &C::Key) -> CollectionResult<()> {
+        self.store.focus(key)
+    }
+
+    /// Move the focus to the next item, see `CollectionStore::focus_next`
+    pub fn focus_next(&self) -> Option<C::Key> {
+        self.store.focus_next()
+    }
+
+    /// Move the focus to the previous item, see `CollectionStore::focus_prev`
+    pub fn focus_prev(&self) -> Option<C::Key> {
+        self.store.focus_prev()
+    }
+
+    /// Select the focused item
+    pub fn commit_focus(&self) -> CollectionResult<()> {
+        self.store.commit_focus()
+    }
+
+    /// Clear the focus
+    pub fn clear_focus(&self) {
+        self.store.clear_focus();
+    }
+}
+
+impl<C> SelectionModel<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Select every item between `from` and `to`, both included
+    pub fn select_range(&self, from: &C::Key, to: &C::Key) -> CollectionResult<()> {
+        self.store.select_range(from, to)
+    }
+
+    /// Extend the selection from its anchor to `key`, like a shift-click
+    pub fn extend_to(&self, key: &C::Key) -> CollectionResult<()> {
+        self.store.extend_selection_to(key)
+    }
+}
@@ -0,0 +1,81 @@
+//! Random order and random picks
+//!
+//! Quizzes, games and "discover" lists show the items in a random order, or a few random items.
+//! `store.shuffle(&mut rng)` reorders a sequential store in a single write, with the selection following
+//! the selected item, and `store.sample(n)` picks random items without changing the collection.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionItem, CollectionStore, SequentialCollection};
+use dioxus_signals::*;
+use rand::Rng;
+use rand::seq::{IndexedRandom, SliceRandom};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+impl<C> CollectionStore<C>
+where
+    C: SequentialCollection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Put the items in a random order, in a single write
+    ///
+    /// Recorded as a `Reorder`, so replaying it gives the same order. The selection, focus and item
+    /// metadata follow their items to their new keys.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use rand::SeedableRng;
+    ///
+    /// let cards = CollectionStore::new(vec!["ace", "king", "queen", "jack"]);
+    /// cards.select(&0).unwrap();
+    /// cards.shuffle(&mut rand::rngs::StdRng::seed_from_u64(7));
+    /// let ace = cards.selected_key().unwrap();
+    /// assert_eq!(cards.peek_value(&ace), Some("ace"));
+    /// ```
+    pub fn shuffle<R: Rng + ?Sized>(&self, rng: &mut R) {
+        if self.ensure_unlocked().is_err() {
+            return;
+        }
+        let old_keys = self.inner.items().peek().keys();
+        if old_keys.len() < 2 {
+            return;
+        }
+        let mut order: Vec<usize> = (0..old_keys.len()).collect();
+        order.shuffle(rng);
+        self.permute(&old_keys, &order);
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Pick `n` distinct random items, or all of them in a random order if there are fewer (reactive)
+    ///
+    /// Uses the thread-local random generator, see `sample_with` to pass one. A component calling it
+    /// picks new items each time it renders: pick them in an event handler or a hook to keep them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let questions = CollectionStore::new(vec!["q1", "q2", "q3", "q4"]);
+    /// let quiz = questions.sample(2);
+    /// assert_eq!(quiz.len(), 2);
+    /// ```
+    pub fn sample(&self, n: usize) -> Vec<CollectionItem<C>> {
+        self.sample_with(n, &mut rand::rng())
+    }
+
+    /// Pick `n` distinct random items with `rng`, like `sample` (reactive)
+    pub fn sample_with<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Vec<CollectionItem<C>> {
+        let keys = self.inner.items().read().keys();
+        keys.choose_multiple(rng, n)
+            .map(|key| CollectionItem::new(*self, key.clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,108 @@
+//! Stable iteration order for unordered maps
+//!
+//! A `HashMap` iterates in a different order from one run to the next, and may reorder its entries when it grows,
+//! so rows rendered with `iter()` jump around. `store.iter_sorted_by_key()` and `store.iter_sorted_by(compare)`
+//! iterate in a stable order without changing the collection type. Both sort on every call: a list rendering
+//! many items should keep the order in `store.sorted_keys_memo(compare)`, which only notifies its readers
+//! when the order changes.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus_signals::*;
+use std::cmp::Ordering;
+
+/// Sort the keys of `items` by comparing their values
+fn sorted_keys<C>(
+    items: &C,
+    mut compare: impl FnMut(&C::Value, &C::Value) -> Ordering,
+) -> Vec<C::Key>
+where
+    C: Collection,
+{
+    let mut keys = items.keys();
+    keys.sort_by(|a, b| match (items.get(a), items.get(b)) {
+        (Some(a), Some(b)) => compare(a, b),
+        _ => Ordering::Equal,
+    });
+    keys
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Iterate over the items in key order (reactive)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
+    ///
+    /// let store = CollectionStore::new(HashMap::from([("b", 2), ("c", 3), ("a", 1)]));
+    /// let keys: Vec<&str> = store.iter_sorted_by_key().map(|item| item.key()).collect();
+    /// assert_eq!(keys, vec!["a", "b", "c"]);
+    /// ```
+    pub fn iter_sorted_by_key(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = CollectionItem<C>> + ExactSizeIterator + '_
+    where
+        C::Key: Ord,
+    {
+        let mut keys = self.inner.items().read().keys();
+        keys.sort();
+        let store = *self;
+        keys.into_iter()
+            .map(move |key| CollectionItem::new(store, key))
+    }
+
+    /// Iterate over the items sorted by comparing their values (reactive)
+    ///
+    /// The sort is stable, but items comparing equal keep the collection's iteration order,
+    /// so `compare` should only find distinct items equal if their order doesn't matter.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
+    ///
+    /// let store = CollectionStore::new(HashMap::from([(1, "carol"), (2, "ada"), (3, "bob")]));
+    /// let keys: Vec<i32> = store.iter_sorted_by(|a, b| a.cmp(b)).map(|item| item.key()).collect();
+    /// assert_eq!(keys, vec![2, 3, 1]);
+    /// ```
+    pub fn iter_sorted_by(
+        &self,
+        compare: impl FnMut(&C::Value, &C::Value) -> Ordering,
+    ) -> impl DoubleEndedIterator<Item = CollectionItem<C>> + ExactSizeIterator + '_ {
+        let keys = sorted_keys(&*self.inner.items().read(), compare);
+        let store = *self;
+        keys.into_iter()
+            .map(move |key| CollectionItem::new(store, key))
+    }
+
+    /// Get a memo of the keys sorted by comparing their values
+    ///
+    /// The keys are sorted again when the collection changes, but readers are only notified when the order
+    /// changes: editing a field that `compare` ignores doesn't re-render the list. Create it once,
+    /// in `use_hook` for instance.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let order = use_hook(|| users.sorted_keys_memo(|a, b| a.name.cmp(&b.name)));
+    /// rsx! {
+    ///     for key in order.read().iter().cloned() {
+    ///         UserRow { key: "{key}", user: users.get(&key) }
+    ///     }
+    /// }
+    /// ```
+    pub fn sorted_keys_memo(
+        &self,
+        compare: impl Fn(&C::Value, &C::Value) -> Ordering + 'static,
+    ) -> Memo<Vec<C::Key>> {
+        let items = self.inner.items();
+        Memo::new(move || sorted_keys(&*items.read(), &compare))
+    }
+}
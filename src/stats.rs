@@ -0,0 +1,111 @@
+//! Rate-limited statistics for status bars and debug overlays
+//!
+//! `store.stats()` returns a signal with the number of items, how many were added and removed since it was
+//! created, and when the collection last changed. It's refreshed at most once per animation frame, however
+//! many mutations happen in between, so a component displaying it doesn't re-render on every write.
+//! Timestamps use `std::time::Instant`, which is not available on `wasm32-unknown-unknown`.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionStore};
+use dioxus_core::{ReactiveContext, current_scope_id, spawn};
+use dioxus_signals::*;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Minimum delay between two refreshes of the statistics, one frame at 60 fps
+const FRAME: Duration = Duration::from_millis(16);
+
+/// Statistics of a collection, see `CollectionStore::stats`
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CollectionStats {
+    /// Number of items
+    pub len: usize,
+    /// Number of items added since the statistics were created
+    pub added: usize,
+    /// Number of items removed since the statistics were created
+    pub removed: usize,
+    /// When the collection last changed, values included, if it changed since the statistics were created
+    pub last_mutation: Option<Instant>,
+}
+
+impl CollectionStats {
+    /// Account for the collection going from `old_keys` to `new_keys`
+    ///
+    /// Positional keys only tell how the length changed. Other keys are compared,
+    /// so replacing an entry with a new key counts as one removal and one addition.
+    pub(crate) fn record_change<K: PartialEq>(
+        &mut self,
+        old_keys: &[K],
+        new_keys: &[K],
+        positional: bool,
+        at: Instant,
+    ) {
+        if positional {
+            self.added += new_keys.len().saturating_sub(old_keys.len());
+            self.removed += old_keys.len().saturating_sub(new_keys.len());
+        } else {
+            self.added += new_keys.iter().filter(|k| !old_keys.contains(k)).count();
+            self.removed += old_keys.iter().filter(|k| !new_keys.contains(k)).count();
+        }
+        self.len = new_keys.len();
+        self.last_mutation = Some(at);
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get a signal with the statistics of the collection, refreshed at most once per animation frame
+    ///
+    /// Must be called from a Dioxus runtime: the refresh runs in a task of the current component,
+    /// so create the statistics once, in `use_hook` for instance. Counting the added and removed
+    /// keys of maps compares the keys before and after the frame's changes, which is meant for
+    /// overlays over moderate collections rather than for hot paths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let stats = use_hook(|| store.stats());
+    /// let CollectionStats { len, added, removed, .. } = stats();
+    /// rsx! { footer { "{len} items (+{added} / -{removed})" } }
+    /// ```
+    pub fn stats(&self) -> ReadSignal<CollectionStats> {
+        let items = self.inner.items();
+        let mut keys = items.peek().keys();
+        let mut stats = Signal::new(CollectionStats {
+            len: keys.len(),
+            ..Default::default()
+        });
+
+        let changed = Arc::new(AtomicBool::new(false));
+        let context = ReactiveContext::new_with_callback(
+            {
+                let changed = changed.clone();
+                move || changed.store(true, Ordering::Relaxed)
+            },
+            current_scope_id(),
+            std::panic::Location::caller(),
+        );
+        context.run_in(|| items.read().len());
+
+        spawn(async move {
+            loop {
+                futures_timer::Delay::new(FRAME).await;
+                if !changed.swap(false, Ordering::Relaxed) {
+                    continue;
+                }
+                let new_keys = context.reset_and_run_in(|| items.read().keys());
+                let mut updated = *stats.peek();
+                updated.record_change(&keys, &new_keys, C::POSITIONAL_KEYS, Instant::now());
+                stats.set(updated);
+                keys = new_keys;
+            }
+        });
+
+        stats.into()
+    }
+}
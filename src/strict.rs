@@ -0,0 +1,116 @@
+//! Strict mode: runtime invariant checks for development builds
+//!
+//! When enabled on a store, every mutation made through the `CollectionStore` API is followed by
+//! a check of the store invariants, and a violation panics with a message explaining how to fix it.
+//! The checks are compiled out of release builds (`debug_assertions` off), so strict mode can be
+//! left on unconditionally.
+//!
+//! Checked invariants:
+//! - the selected and focused keys and the range selection anchor, if any, exist in the collection
+//! - the collection doesn't yield the same key twice (not checked for positional keys, unique by construction)
+//! - the store isn't written to while a component is re-rendering (the runtime doesn't flag the first build)
+//!
+//! Mutations made through `CollectionItem::write()` or `selected_key_signal()` bypass the store API
+//! and are only checked by the next store operation.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionStore};
+use dioxus_signals::*;
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Enable or disable strict mode for this store
+    ///
+    /// Strict mode only has an effect in debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use dioxus_signals::*;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.set_strict(true);
+    /// // Writing the signal directly bypasses the store API
+    /// store.selected_key_signal().set(Some(5));
+    /// // Panics in debug builds: the selected index 5 is out of bounds
+    /// store.push(4);
+    /// ```
+    pub fn set_strict(&self, strict: bool) {
+        self.inner.strict().set(strict);
+    }
+
+    /// Check if strict mode is enabled for this store
+    pub fn is_strict(&self) -> bool {
+        *self.inner.strict().peek()
+    }
+
+    /// Check the store invariants after `operation`, panicking on violation
+    ///
+    /// No-op unless strict mode is enabled in a debug build. Mutators call it through `finish_mutation`.
+    pub(crate) fn check_invariants(&self, operation: &str) {
+        if !cfg!(debug_assertions) || !self.is_strict() {
+            return;
+        }
+
+        if dioxus_core::Runtime::try_current().is_some_and(|runtime| runtime.vdom_is_rendering()) {
+            panic!(
+                "[strict] `{operation}` was called while a component was rendering. \
+                 Writing to the store during render re-runs the components reading it and can loop forever; \
+                 move the mutation into an event handler, `use_effect` or a spawned task."
+            );
+        }
+
+        let items = self.inner.items();
+        let items = items.peek();
+        let keys = items.keys();
+
+        // Positional keys are unique by construction, other keys are only `PartialEq` so this check is quadratic
+        if !C::POSITIONAL_KEYS {
+            for (position, key) in keys.iter().enumerate() {
+                if let Some(first) = keys[..position].iter().position(|other| other == key) {
+                    panic!(
+                        "[strict] after `{operation}`, the collection yields the same key twice (at positions {first} and {position}). \
+                         The `Collection` implementation must return unique keys from `keys()`, \
+                         otherwise items can't be addressed individually."
+                    );
+                }
+            }
+        }
+
+        if let Some(selected) = self.inner.selected_key().peek().as_ref()
+            && items.get(selected).is_none()
+        {
+            panic!(
+                "[strict] after `{operation}`, the selected key is no longer in the collection \
+                 (collection length: {}). Call `clear_selection()` or `select()` a valid key \
+                 when removing or moving the selected item.",
+                keys.len(),
+            );
+        }
+
+        if let Some(focused) = self.inner.focused_key().peek().as_ref()
+            && items.get(focused).is_none()
+        {
+            panic!(
+                "[strict] after `{operation}`, the focused key is no longer in the collection \
+                 (collection length: {}). Use `focus()` or `clear_focus()` instead of writing \
+                 `focused_key_signal()` directly.",
+                keys.len(),
+            );
+        }
+
+        if let Some(anchor) = self.inner.selection_anchor().peek().as_ref()
+            && items.get(anchor).is_none()
+        {
+            panic!(
+                "[strict] after `{operation}`, the range selection anchor is no longer in the collection \
+                 (collection length: {}). Use `select_range()` or `select()` to start a new range.",
+                keys.len(),
+            );
+        }
+    }
+}
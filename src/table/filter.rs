@@ -0,0 +1,228 @@
+//! Column filters of a table view
+//!
+//! A `FilterSet` holds conditions on the cell text of columns, all of which (`FilterMode::All`) or any of which
+//! (`FilterMode::Any`) a row must match. It can be built in code with `set_filter`, or parsed from a search box
+//! query like `status:done priority>2`: `column:text` matches cells containing the text (ignoring case),
+//! `column=text` equal cells, and `>`, `<`, `>=`, `<=` compare numeric cells. Joining terms with `OR`
+//! makes the whole query match any term.
+
+use super::TableView;
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult};
+use dioxus_signals::*;
+
+/// A condition on the text of a cell
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FilterOp {
+    /// The cell contains the text, ignoring case
+    Contains(String),
+    /// The cell is exactly the text
+    Equals(String),
+    /// The cell is a number greater than the value
+    GreaterThan(f64),
+    /// The cell is a number less than the value
+    LessThan(f64),
+    /// The cell is a number greater than or equal to the value
+    AtLeast(f64),
+    /// The cell is a number less than or equal to the value
+    AtMost(f64),
+}
+
+impl FilterOp {
+    /// Check if a cell matches the condition
+    pub fn matches(&self, cell: &str) -> bool {
+        let number = || cell.trim().parse::<f64>().ok();
+        match self {
+            FilterOp::Contains(text) => cell.to_lowercase().contains(&text.to_lowercase()),
+            FilterOp::Equals(text) => cell == text,
+            FilterOp::GreaterThan(value) => number().is_some_and(|cell| cell > *value),
+            FilterOp::LessThan(value) => number().is_some_and(|cell| cell < *value),
+            FilterOp::AtLeast(value) => number().is_some_and(|cell| cell >= *value),
+            FilterOp::AtMost(value) => number().is_some_and(|cell| cell <= *value),
+        }
+    }
+}
+
+/// How the conditions of a `FilterSet` combine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FilterMode {
+    /// Rows must match every condition (AND)
+    #[default]
+    All,
+    /// Rows must match at least one condition (OR)
+    Any,
+}
+
+/// Conditions on the columns of a table, see `TableView::set_filter`
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FilterSet {
+    pub(super) filters: Vec<(usize, FilterOp)>,
+    pub(super) mode: FilterMode,
+}
+
+impl FilterSet {
+    /// Create an empty set, matching every row
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a query, resolving column names against `headers` (ignoring case)
+    ///
+    /// Fails with `InvalidAccess` for a term without an operator, an unknown column
+    /// or a comparison with something else than a number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use dioxus_collection_store::{FilterOp, FilterSet};
+    ///
+    /// let headers = ["Status".to_string(), "Priority".to_string()];
+    /// let filters = FilterSet::parse("status:done priority>2", &headers).unwrap();
+    /// assert_eq!(
+    ///     filters.filters(),
+    ///     &[(0, FilterOp::Contains("done".to_string())), (1, FilterOp::GreaterThan(2.0))]
+    /// );
+    /// ```
+    pub fn parse(query: &str, headers: &[String]) -> CollectionResult<Self> {
+        let mut set = Self::new();
+        for term in query.split_whitespace() {
+            if term == "OR" {
+                set.mode = FilterMode::Any;
+                continue;
+            }
+            let invalid = |reason: &str| CollectionError::InvalidAccess {
+                reason: format!("invalid filter \"{term}\": {reason}"),
+            };
+            let split = term
+                .find([':', '=', '<', '>'])
+                .ok_or_else(|| invalid("expected column:text, column=text or a comparison"))?;
+            let (name, rest) = term.split_at(split);
+            let column = headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(name))
+                .ok_or_else(|| invalid("unknown column"))?;
+            let number = |value: &str| {
+                value
+                    .parse::<f64>()
+                    .map_err(|_| invalid("expected a number"))
+            };
+            let op = if let Some(text) = rest.strip_prefix(':') {
+                FilterOp::Contains(text.to_string())
+            } else if let Some(value) = rest.strip_prefix(">=") {
+                FilterOp::AtLeast(number(value)?)
+            } else if let Some(value) = rest.strip_prefix("<=") {
+                FilterOp::AtMost(number(value)?)
+            } else if let Some(value) = rest.strip_prefix('>') {
+                FilterOp::GreaterThan(number(value)?)
+            } else if let Some(value) = rest.strip_prefix('<') {
+                FilterOp::LessThan(number(value)?)
+            } else {
+                FilterOp::Equals(rest[1..].to_string())
+            };
+            set.filters.push((column, op));
+        }
+        Ok(set)
+    }
+
+    /// Get the conditions, with the column they apply to
+    pub fn filters(&self) -> &[(usize, FilterOp)] {
+        &self.filters
+    }
+
+    /// Get how the conditions combine
+    pub fn mode(&self) -> FilterMode {
+        self.mode
+    }
+
+    /// Check if there is no condition
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Check if a row matches, reading the text of its cells with `cell`
+    pub fn matches(&self, mut cell: impl FnMut(usize) -> Option<String>) -> bool {
+        if self.filters.is_empty() {
+            return true;
+        }
+        let mut results = self
+            .filters
+            .iter()
+            .map(|(column, op)| cell(*column).is_some_and(|text| op.matches(&text)));
+        match self.mode {
+            FilterMode::All => results.all(|matched| matched),
+            FilterMode::Any => results.any(|matched| matched),
+        }
+    }
+}
+
+impl<C> TableView<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Replace the conditions on a column with `op`
+    ///
+    /// Fails with `OutOfBounds` for a missing column.
+    pub fn set_filter(&self, column: usize, op: FilterOp) -> CollectionResult<()> {
+        let len = self.column_count();
+        if column >= len {
+            return Err(CollectionError::OutOfBounds { index: column, len });
+        }
+        let mut filters = self.filters;
+        let mut filters = filters.write();
+        filters.filters.retain(|(filtered, _)| *filtered != column);
+        filters.filters.push((column, op));
+        Ok(())
+    }
+
+    /// Remove the conditions on a column
+    pub fn remove_filter(&self, column: usize) {
+        let mut filters = self.filters;
+        filters
+            .write()
+            .filters
+            .retain(|(filtered, _)| *filtered != column);
+    }
+
+    /// Set how the conditions combine
+    pub fn set_filter_mode(&self, mode: FilterMode) {
+        let mut filters = self.filters;
+        filters.write().mode = mode;
+    }
+
+    /// Replace every condition with the ones of a query, see `FilterSet::parse`
+    ///
+    /// The conditions are left untouched if the query is invalid.
+    pub fn set_query(&self, query: &str) -> CollectionResult<()> {
+        let headers: Vec<String> = (0..self.column_count())
+            .filter_map(|column| self.header(column))
+            .collect();
+        let parsed = FilterSet::parse(query, &headers)?;
+        let mut filters = self.filters;
+        filters.set(parsed);
+        Ok(())
+    }
+
+    /// Remove every condition
+    pub fn clear_filters(&self) {
+        let mut filters = self.filters;
+        filters.set(FilterSet::new());
+    }
+
+    /// Get the conditions (reactive)
+    pub fn filters(&self) -> FilterSet {
+        self.filters.read().clone()
+    }
+
+    /// Get the rows matching the conditions, in the order of `rows()` (reactive)
+    ///
+    /// Page through them with `skip` and `take`, or slice the returned `Vec`.
+    pub fn filtered_rows(&self) -> Vec<CollectionItem<C>> {
+        let filters = self.filters.read();
+        self.rows()
+            .filter(|row| filters.matches(|column| self.cell(row, column)))
+            .collect()
+    }
+}
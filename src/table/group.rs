@@ -0,0 +1,164 @@
+//! Row grouping of a table view
+//!
+//! `table.group_by_column(column)` groups the filtered rows by the text of a column, in order of first appearance.
+//! Each `RowGroup` gives aggregates for its header (row count, sum of a numeric column), and groups can be
+//! collapsed: the expanded state is kept per group label in the table view, so it survives re-renders and
+//! changes of the rows.
+
+use super::TableView;
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult};
+use dioxus_signals::*;
+
+/// The rows of a table sharing the same text in the grouped column
+///
+/// Returned by `TableView::groups`.
+pub struct RowGroup<C>
+where
+    C: Collection + 'static,
+{
+    table: TableView<C>,
+    label: String,
+    rows: Vec<CollectionItem<C>>,
+}
+
+impl<C> RowGroup<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the text of the grouped column shared by the rows
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the rows of the group, in the order of `TableView::rows`
+    pub fn rows(&self) -> &[CollectionItem<C>] {
+        &self.rows
+    }
+
+    /// Get the number of rows
+    pub fn count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Sum the numeric cells of a column, ignoring the cells that aren't numbers (reactive)
+    pub fn sum(&self, column: usize) -> f64 {
+        self.rows
+            .iter()
+            .filter_map(|row| self.table.cell(row, column)?.trim().parse::<f64>().ok())
+            .sum()
+    }
+
+    /// Check if the group is expanded (reactive)
+    pub fn is_expanded(&self) -> bool {
+        self.table.is_group_expanded(&self.label)
+    }
+
+    /// Get the rows to display: all of them if the group is expanded, none otherwise (reactive)
+    pub fn visible_rows(&self) -> &[CollectionItem<C>] {
+        if self.is_expanded() { &self.rows } else { &[] }
+    }
+
+    /// Collapse the group if it's expanded, expand it otherwise
+    pub fn toggle(&self) {
+        self.table.toggle_group(&self.label);
+    }
+}
+
+impl<C> TableView<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Group the rows by the text of a column
+    ///
+    /// Fails with `OutOfBounds` for a missing column.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// table.group_by_column(0)?;
+    /// rsx! {
+    ///     for group in table.groups() {
+    ///         tr { onclick: move |_| group.toggle(), "{group.label()} ({group.count()}, total {group.sum(1)})" }
+    ///         for row in group.visible_rows() { /* cells */ }
+    ///     }
+    /// }
+    /// ```
+    pub fn group_by_column(&self, column: usize) -> CollectionResult<()> {
+        let len = self.column_count();
+        if column >= len {
+            return Err(CollectionError::OutOfBounds { index: column, len });
+        }
+        let mut grouping = self.grouping;
+        grouping.set(Some(column));
+        Ok(())
+    }
+
+    /// Stop grouping the rows
+    pub fn ungroup(&self) {
+        let mut grouping = self.grouping;
+        grouping.set(None);
+    }
+
+    /// Get the grouped column, if any (reactive)
+    pub fn grouped_by(&self) -> Option<usize> {
+        *self.grouping.read()
+    }
+
+    /// Get the groups of the filtered rows, in order of first appearance (reactive)
+    ///
+    /// Without grouping, all the filtered rows are in a single group with an empty label.
+    pub fn groups(&self) -> Vec<RowGroup<C>> {
+        let rows = self.filtered_rows();
+        let Some(column) = self.grouped_by() else {
+            return vec![RowGroup {
+                table: *self,
+                label: String::new(),
+                rows,
+            }];
+        };
+        let mut groups: Vec<RowGroup<C>> = Vec::new();
+        for row in rows {
+            let label = self.cell(&row, column).unwrap_or_default();
+            match groups.iter_mut().find(|group| group.label == label) {
+                Some(group) => group.rows.push(row),
+                None => groups.push(RowGroup {
+                    table: *self,
+                    label,
+                    rows: vec![row],
+                }),
+            }
+        }
+        groups
+    }
+
+    /// Check if the group with this label is expanded, which groups are by default (reactive)
+    pub fn is_group_expanded(&self, label: &str) -> bool {
+        !self
+            .collapsed_groups
+            .read()
+            .iter()
+            .any(|collapsed| collapsed == label)
+    }
+
+    /// Expand or collapse the group with this label
+    pub fn set_group_expanded(&self, label: &str, expanded: bool) {
+        let mut collapsed_groups = self.collapsed_groups;
+        let mut collapsed = collapsed_groups.write();
+        collapsed.retain(|collapsed| collapsed != label);
+        if !expanded {
+            collapsed.push(label.to_string());
+        }
+    }
+
+    /// Collapse the group with this label if it's expanded, expand it otherwise
+    pub fn toggle_group(&self, label: &str) {
+        let expanded = self
+            .collapsed_groups
+            .peek()
+            .iter()
+            .any(|collapsed| collapsed == label);
+        self.set_group_expanded(label, expanded);
+    }
+}
@@ -0,0 +1,377 @@
+//! Column model for data tables
+//!
+//! `store.as_table(columns)` describes how a store is displayed as a table: each `ColumnModel` reads a cell from
+//! an item value, and may be sortable and sized. The `TableView` keeps the sort state and the column widths in
+//! signals: a header click sorts the rows of the view, leaving the store in its own order (so keyed stores like
+//! a `HashMap` can be sorted too), and every cell reads its own item: editing a value only re-renders the cells
+//! of its row. Column filters narrow the rows down with `filtered_rows()`,
+//! and `groups()` splits them by the text of a column under collapsible group headers. `page_rows()` shows them
+//! one page at a time, and `save_view(name)` keeps the whole state as a named preset.
+//!
+//! ```rust,ignore
+//! let table = use_hook(|| users.as_table(vec![
+//!     ColumnModel::new("Name", |user: &User| user.name.clone()).sort_by(|a, b| a.name.cmp(&b.name)),
+//!     ColumnModel::new("Age", |user: &User| user.age.to_string()).width(80),
+//! ]));
+//! rsx! {
+//!     table {
+//!         tr {
+//!             for column in 0..table.column_count() {
+//!                 th { onclick: move |_| { table.toggle_sort(column).ok(); }, {table.header(column)} }
+//!             }
+//!         }
+//!         for row in table.rows() {
+//!             tr { for column in 0..table.column_count() { td { {table.cell(&row, column)} } } }
+//!         }
+//!     }
+//! }
+//! ```
+
+mod filter;
+mod group;
+mod page;
+mod preset;
+#[cfg(feature = "router")]
+mod url_sync;
+
+pub use filter::{FilterMode, FilterOp, FilterSet};
+pub use group::RowGroup;
+pub use preset::ViewPreset;
+#[cfg(feature = "router")]
+pub use url_sync::use_collection_url_sync;
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_signals::*;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+/// Reads the text of a cell from an item value
+type Accessor<V> = Rc<dyn Fn(&V) -> String>;
+
+/// Compares two item values to sort a column
+type Compare<V> = Rc<dyn Fn(&V, &V) -> Ordering>;
+
+/// Direction of a sorted column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// A column of a `TableView`: its header, how to read its cells, and whether it's sortable
+pub struct ColumnModel<V> {
+    header: String,
+    accessor: Accessor<V>,
+    compare: Option<Compare<V>>,
+    width: Option<u32>,
+}
+
+impl<V> Clone for ColumnModel<V> {
+    fn clone(&self) -> Self {
+        Self {
+            header: self.header.clone(),
+            accessor: self.accessor.clone(),
+            compare: self.compare.clone(),
+            width: self.width,
+        }
+    }
+}
+
+impl<V> ColumnModel<V> {
+    /// Create a column reading its cells with `accessor`, not sortable and sized automatically
+    pub fn new(header: impl Into<String>, accessor: impl Fn(&V) -> String + 'static) -> Self {
+        Self {
+            header: header.into(),
+            accessor: Rc::new(accessor),
+            compare: None,
+            width: None,
+        }
+    }
+
+    /// Make the column sortable, ordering the item values with `compare` when sorted ascending
+    pub fn sort_by(mut self, compare: impl Fn(&V, &V) -> Ordering + 'static) -> Self {
+        self.compare = Some(Rc::new(compare));
+        self
+    }
+
+    /// Set the initial width of the column, in pixels
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+}
+
+/// A store displayed as a table, with a sort state and column widths
+///
+/// Created with `CollectionStore::as_table`.
+pub struct TableView<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    columns: CopyValue<Vec<ColumnModel<C::Value>>>,
+    widths: CopyValue<Vec<Signal<Option<u32>>>>,
+    /// Sort keys, primary first
+    sort: Signal<Vec<(usize, SortDirection)>>,
+    /// Keys of the rows, sorted by the sort keys
+    order: Memo<Vec<C::Key>>,
+    filters: Signal<FilterSet>,
+    grouping: Signal<Option<usize>>,
+    /// Labels of the collapsed groups
+    collapsed_groups: Signal<Vec<String>>,
+    page_size: Signal<Option<usize>>,
+    page: Signal<usize>,
+    presets: Signal<Vec<ViewPreset<C::Key>>>,
+}
+
+impl<C> Copy for TableView<C> where C: Collection + 'static {}
+
+impl<C> Clone for TableView<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> PartialEq for TableView<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner && self.columns == other.columns
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Display the store as a table with the given columns
+    ///
+    /// Create it once (in `use_hook` for instance), it owns the sort state, the filters, the grouping, the pagination, the saved views and the column widths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionStore, ColumnModel, SortDirection};
+    ///
+    /// let users = CollectionStore::new(vec![("alan", 41), ("ada", 36)]);
+    /// let table = users.as_table(vec![
+    ///     ColumnModel::new("Name", |(name, _): &(&str, u32)| name.to_string()),
+    ///     ColumnModel::new("Age", |(_, age): &(&str, u32)| age.to_string()).sort_by(|a, b| a.1.cmp(&b.1)),
+    /// ]);
+    /// table.toggle_sort(1).unwrap();
+    /// assert_eq!(table.sort_state(), Some((1, SortDirection::Ascending)));
+    /// let first = table.rows().next().unwrap();
+    /// assert_eq!(table.cell(&first, 0), Some("ada".to_string()));
+    /// ```
+    pub fn as_table(&self, columns: Vec<ColumnModel<C::Value>>) -> TableView<C> {
+        let widths = columns
+            .iter()
+            .map(|column| Signal::new(column.width))
+            .collect();
+        let store = *self;
+        let columns = CopyValue::new(columns);
+        let sort = Signal::new(Vec::new());
+        TableView {
+            store,
+            columns,
+            widths: CopyValue::new(widths),
+            sort,
+            order: Memo::new(move || sorted_keys(store, columns, &sort.read())),
+            filters: Signal::new(FilterSet::new()),
+            grouping: Signal::new(None),
+            collapsed_groups: Signal::new(Vec::new()),
+            page_size: Signal::new(None),
+            page: Signal::new(0),
+            presets: Signal::new(Vec::new()),
+        }
+    }
+}
+
+impl<C> TableView<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the displayed store
+    pub fn store(&self) -> CollectionStore<C> {
+        self.store
+    }
+
+    /// Get the number of columns
+    pub fn column_count(&self) -> usize {
+        self.columns.peek().len()
+    }
+
+    /// Get the header of a column
+    pub fn header(&self, column: usize) -> Option<String> {
+        self.columns
+            .peek()
+            .as_slice()
+            .get(column)
+            .map(|column| column.header.clone())
+    }
+
+    /// Check if a column can be sorted
+    pub fn is_sortable(&self, column: usize) -> bool {
+        self.columns
+            .peek()
+            .as_slice()
+            .get(column)
+            .is_some_and(|column| column.compare.is_some())
+    }
+
+    /// Get the width signal of a column, `None` inside meaning sized automatically
+    ///
+    /// Write to it to resize the column, for instance while dragging the header border.
+    pub fn width(&self, column: usize) -> Option<Signal<Option<u32>>> {
+        self.widths.peek().as_slice().get(column).copied()
+    }
+
+    /// Get the primary sorted column and its direction, if the table was sorted (reactive)
+    pub fn sort_state(&self) -> Option<(usize, SortDirection)> {
+        self.sort.read().first().copied()
+    }
+
+    /// Get every sort key, primary first (reactive)
+    pub fn sort_keys(&self) -> Vec<(usize, SortDirection)> {
+        self.sort.read().clone()
+    }
+
+    /// Get an iterator over the rows, sorted by the sort keys or in collection order (reactive)
+    ///
+    /// The order is kept up to date: an added or edited item takes its sorted place.
+    pub fn rows(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = CollectionItem<C>> + ExactSizeIterator + '_ {
+        let store = self.store;
+        self.order
+            .read()
+            .clone()
+            .into_iter()
+            .map(move |key| CollectionItem::new(store, key))
+    }
+
+    /// Get the text of a cell, reading the item of its row
+    ///
+    /// This subscribes to the item like `item.read()`: only the cells of a changed row re-render.
+    pub fn cell(&self, row: &CollectionItem<C>, column: usize) -> Option<String> {
+        let accessor = self.columns.peek().as_slice().get(column)?.accessor.clone();
+        let value = row.try_read_value().ok()?;
+        Some(accessor(&value))
+    }
+}
+
+impl<C> TableView<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Sort the rows by a column, keeping the previous sort keys as tie-breakers
+    ///
+    /// The column becomes the primary sort key, and the columns sorted before it still order the items
+    /// it considers equal, like sorting a spreadsheet by one column then another. The sort is stable.
+    /// Only the rows of the view are sorted: the store, its keys and its selection are left untouched.
+    /// Fails with `OutOfBounds` for a missing column, and `InvalidAccess` if the column isn't sortable.
+    pub fn sort(&self, column: usize, direction: SortDirection) -> CollectionResult<()> {
+        let keys: Vec<_> = std::iter::once((column, direction))
+            .chain(
+                self.sort
+                    .peek()
+                    .iter()
+                    .copied()
+                    .filter(|(sorted, _)| *sorted != column),
+            )
+            .collect();
+        self.set_sort_keys(keys)
+    }
+
+    /// Sort by a column, ascending first, then toggling the direction, like a header click
+    pub fn toggle_sort(&self, column: usize) -> CollectionResult<()> {
+        let direction = match self.sort_state() {
+            Some((sorted, SortDirection::Ascending)) if sorted == column => {
+                SortDirection::Descending
+            }
+            _ => SortDirection::Ascending,
+        };
+        self.sort(column, direction)
+    }
+
+    /// Forget the sort keys, showing the rows in collection order
+    pub fn clear_sort(&self) {
+        let mut sort = self.sort;
+        sort.set(Vec::new());
+    }
+
+    /// Replace the sort keys, the first one being the primary key, if every column can be sorted
+    fn set_sort_keys(&self, keys: Vec<(usize, SortDirection)>) -> CollectionResult<()> {
+        comparators(&self.columns.peek(), &keys)?;
+        let mut sort = self.sort;
+        sort.set(keys);
+        Ok(())
+    }
+}
+
+/// Get the comparators of the sort keys
+///
+/// Fails with `OutOfBounds` for a missing column, and `InvalidAccess` if a column isn't sortable.
+fn comparators<V>(
+    columns: &[ColumnModel<V>],
+    keys: &[(usize, SortDirection)],
+) -> CollectionResult<Vec<(Compare<V>, SortDirection)>> {
+    keys.iter()
+        .map(|(column, direction)| {
+            let model = columns.get(*column).ok_or(CollectionError::OutOfBounds {
+                index: *column,
+                len: columns.len(),
+            })?;
+            let compare = model
+                .compare
+                .clone()
+                .ok_or_else(|| CollectionError::InvalidAccess {
+                    reason: format!("column \"{}\" isn't sortable", model.header),
+                })?;
+            Ok((compare, *direction))
+        })
+        .collect()
+}
+
+/// Get the keys of the store sorted by `keys` (reactive)
+fn sorted_keys<C>(
+    store: CollectionStore<C>,
+    columns: CopyValue<Vec<ColumnModel<C::Value>>>,
+    keys: &[(usize, SortDirection)],
+) -> Vec<C::Key>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    let items = store.inner.items();
+    let items = items.read();
+    let mut order = items.keys();
+    // The sort keys are checked when they are set
+    let comparators = comparators(&columns.peek(), keys).unwrap_or_default();
+    if comparators.is_empty() {
+        return order;
+    }
+    order.sort_by(|a, b| {
+        let (Some(a), Some(b)) = (items.get(a), items.get(b)) else {
+            return Ordering::Equal;
+        };
+        comparators
+            .iter()
+            .map(|(compare, direction)| match direction {
+                SortDirection::Ascending => compare(a, b),
+                SortDirection::Descending => compare(b, a),
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+    order
+}
@@ -0,0 +1,73 @@
+//! Pagination of a table view
+//!
+//! With `table.set_page_size(Some(n))`, `page_rows()` returns one page of the filtered rows at a time. The page
+//! index is kept in the table view and clamped to the last page, so filtering down to fewer rows shows the
+//! last page left instead of an empty one.
+
+use super::TableView;
+use crate::{Collection, CollectionItem};
+use dioxus_signals::*;
+
+impl<C> TableView<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Show `size` rows per page, or every row with `None`, going back to the first page
+    pub fn set_page_size(&self, size: Option<usize>) {
+        let mut page_size = self.page_size;
+        page_size.set(size.filter(|size| *size > 0));
+        let mut page = self.page;
+        page.set(0);
+    }
+
+    /// Get the number of rows per page, `None` if the rows aren't paginated (reactive)
+    pub fn page_size(&self) -> Option<usize> {
+        *self.page_size.read()
+    }
+
+    /// Show the page with this index, starting at 0
+    pub fn set_page(&self, index: usize) {
+        let mut page = self.page;
+        page.set(index);
+    }
+
+    /// Get the index of the shown page, clamped to the last page (reactive)
+    pub fn page(&self) -> usize {
+        let page = *self.page.read();
+        page.min(self.page_count().saturating_sub(1))
+    }
+
+    /// Get the number of pages of the filtered rows, at least 1 (reactive)
+    pub fn page_count(&self) -> usize {
+        match self.page_size() {
+            Some(size) => self.filtered_rows().len().div_ceil(size).max(1),
+            None => 1,
+        }
+    }
+
+    /// Get the filtered rows of the shown page, in the order of `rows()` (reactive)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionStore, ColumnModel};
+    ///
+    /// let numbers = CollectionStore::new((1..=25).collect::<Vec<u32>>());
+    /// let table = numbers.as_table(vec![ColumnModel::new("N", |n: &u32| n.to_string())]);
+    /// table.set_page_size(Some(10));
+    /// table.set_page(2);
+    /// assert_eq!(table.page_rows().len(), 5);
+    /// assert_eq!(table.page_count(), 3);
+    /// ```
+    pub fn page_rows(&self) -> Vec<CollectionItem<C>> {
+        let rows = self.filtered_rows();
+        let Some(size) = self.page_size() else {
+            return rows;
+        };
+        rows.into_iter()
+            .skip(self.page() * size)
+            .take(size)
+            .collect()
+    }
+}
@@ -0,0 +1,134 @@
+//! Named presets of a table view
+//!
+//! "My open tasks"-style saved views restore a sort, filters, grouping, page and selection at once.
+//! `table.save_view(name)` captures them in a `ViewPreset` kept by the table view, and `table.load_view(name)`
+//! applies it again. With the `serde` feature, presets can be serialized to persist them, and applied back with
+//! `apply_view`.
+
+use super::{FilterSet, SortDirection, TableView};
+use crate::{Collection, CollectionError, CollectionResult};
+use dioxus_signals::*;
+
+/// Sort, filters, grouping, page and selection of a table view, see `TableView::save_view`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewPreset<K> {
+    pub name: String,
+    /// Sort keys, primary first
+    pub sort: Vec<(usize, SortDirection)>,
+    pub filters: FilterSet,
+    pub grouping: Option<usize>,
+    /// Labels of the collapsed groups
+    pub collapsed_groups: Vec<String>,
+    pub page_size: Option<usize>,
+    pub page: usize,
+    pub selected_key: Option<K>,
+}
+
+impl<C> TableView<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Capture the current state of the table under `name`, replacing the preset with the same name
+    ///
+    /// Returns the preset, to persist it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionStore, ColumnModel, FilterOp};
+    ///
+    /// let tasks = CollectionStore::new(vec![("docs", "open"), ("bug", "done")]);
+    /// let table = tasks.as_table(vec![
+    ///     ColumnModel::new("Task", |(task, _): &(&str, &str)| task.to_string()),
+    ///     ColumnModel::new("Status", |(_, status): &(&str, &str)| status.to_string()),
+    /// ]);
+    /// table.set_filter(1, FilterOp::Equals("open".to_string())).unwrap();
+    /// table.save_view("My open tasks");
+    /// table.clear_filters();
+    /// table.load_view("My open tasks").unwrap();
+    /// assert_eq!(table.filtered_rows().len(), 1);
+    /// ```
+    pub fn save_view(&self, name: &str) -> ViewPreset<C::Key> {
+        let preset = self.capture_view(name);
+        let mut presets = self.presets;
+        let mut presets = presets.write();
+        presets.retain(|saved| saved.name != name);
+        presets.push(preset.clone());
+        preset
+    }
+
+    /// Capture the current state of the table without saving it
+    pub(super) fn capture_view(&self, name: &str) -> ViewPreset<C::Key> {
+        ViewPreset {
+            name: name.to_string(),
+            sort: self.sort.peek().clone(),
+            filters: self.filters.peek().clone(),
+            grouping: *self.grouping.peek(),
+            collapsed_groups: self.collapsed_groups.peek().clone(),
+            page_size: *self.page_size.peek(),
+            page: *self.page.peek(),
+            selected_key: self.store.peek_selected_key(),
+        }
+    }
+
+    /// Get the saved presets, in the order they were first saved (reactive)
+    pub fn views(&self) -> Vec<ViewPreset<C::Key>> {
+        self.presets.read().clone()
+    }
+
+    /// Forget the preset with this name
+    pub fn remove_view(&self, name: &str) {
+        let mut presets = self.presets;
+        presets.write().retain(|saved| saved.name != name);
+    }
+
+    /// Apply the preset saved under `name`
+    ///
+    /// Fails with `KeyNotFound` if no preset has this name, or like `apply_view`.
+    pub fn load_view(&self, name: &str) -> CollectionResult<()> {
+        let preset = self
+            .presets
+            .peek()
+            .iter()
+            .find(|saved| saved.name == name)
+            .cloned()
+            .ok_or(CollectionError::KeyNotFound)?;
+        self.apply_view(&preset)
+    }
+
+    /// Apply a preset, sorting the rows by its sort keys and selecting its selected item
+    ///
+    /// The preset doesn't need to be saved in this table, so a persisted preset can be applied after a reload.
+    /// The selection is cleared if the preset's item is missing or can't be selected. Fails like `sort`,
+    /// leaving the table untouched, if the preset refers to columns the table doesn't have or can't sort.
+    pub fn apply_view(&self, preset: &ViewPreset<C::Key>) -> CollectionResult<()> {
+        let columns = self.column_count();
+        let missing = preset
+            .grouping
+            .into_iter()
+            .chain(preset.filters.filters().iter().map(|(column, _)| *column))
+            .find(|column| *column >= columns);
+        if let Some(column) = missing {
+            return Err(CollectionError::OutOfBounds {
+                index: column,
+                len: columns,
+            });
+        }
+        self.set_sort_keys(preset.sort.clone())?;
+        let mut filters = self.filters;
+        filters.set(preset.filters.clone());
+        let mut grouping = self.grouping;
+        grouping.set(preset.grouping);
+        let mut collapsed_groups = self.collapsed_groups;
+        collapsed_groups.set(preset.collapsed_groups.clone());
+        self.set_page_size(preset.page_size);
+        self.set_page(preset.page);
+        match &preset.selected_key {
+            Some(key) if self.store.select(key).is_ok() => {}
+            _ => self.store.clear_selection(),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+//! Table view state in the URL
+//!
+//! `use_collection_url_sync(table, "tasks.")` keeps the sort, the filters, the page and the selected key of a
+//! table view in the query string, through the history provider `dioxus-router` navigates with. A reload or a
+//! shared link shows the same rows, and going back restores the previous state:
+//!
+//! ```text
+//! /tasks?tasks.sort=-1,0&tasks.filter=0%3Dtodo&tasks.page=2&tasks.selected=4
+//! ```
+//!
+//! Each sort key is a column index, negative for a descending sort, and each filter a column index followed by
+//! the operator of a filter query (`:` contains, `=`, `>`, `<`, `>=`, `<=`) and its value. `match=any` makes
+//! the filters match any condition, and pages start at 1. Other parameters of the query string are kept.
+
+use super::{FilterMode, FilterOp, FilterSet, SortDirection, TableView, ViewPreset};
+use crate::Collection;
+use crate::waker::ChangeWaker;
+use dioxus_core::{queue_effect, spawn, use_hook};
+use dioxus_history::{History, history};
+use dioxus_signals::*;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Parameters of the query string, decoded
+type Params = Vec<(String, String)>;
+
+/// Hook keeping the sort, filters, page and selected key of a table view in the query string
+///
+/// Parameter names start with `param_prefix`, to sync several tables in one page. On the first render, the
+/// parameters of the URL are applied to the view, or the view state is written to the URL if it has none. Then
+/// every change of the view replaces the URL (without adding history entries), and the parameters are applied
+/// again when the component renders after a navigation changed them. A URL whose parameters can't be applied
+/// (a missing column, a key that doesn't parse) is replaced with the view state. The page size stays the one
+/// set in code. Call it in the component rendered for the route, inside a router or any history provider.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use dioxus::prelude::*;
+/// use dioxus_collection_store::{ColumnModel, use_collection, use_collection_url_sync};
+///
+/// fn Tasks() -> Element {
+///     let tasks = use_collection(|| vec![("docs", 2), ("bug", 1)]);
+///     let table = use_hook(|| {
+///         tasks.as_table(vec![
+///             ColumnModel::new("Task", |(task, _): &(&str, u32)| task.to_string()),
+///             ColumnModel::new("Priority", |(_, priority): &(&str, u32)| priority.to_string())
+///                 .sort_by(|a, b| a.1.cmp(&b.1)),
+///         ])
+///     });
+///     use_collection_url_sync(table, "tasks.");
+///     rsx! {}
+/// }
+/// ```
+pub fn use_collection_url_sync<C>(view: TableView<C>, param_prefix: &str)
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Display + FromStr,
+{
+    // Parameters both in the URL and applied to the view
+    let synced = use_hook(|| {
+        let mut synced = CopyValue::new(None);
+        let changed = ChangeWaker::default();
+        let context = changed.reactive_context();
+        let prefix = param_prefix.to_string();
+        let history = history();
+        spawn(async move {
+            restore(&*history, view, &prefix, synced);
+            loop {
+                let params = context.reset_and_run_in(|| view_params(view, &prefix));
+                if synced.peek().as_ref() != Some(&params) {
+                    replace_params(&*history, &prefix, &params);
+                    synced.set(Some(params));
+                }
+                changed.changed().await;
+            }
+        });
+        synced
+    });
+    // Effects run outside of the component, get its history provider now
+    let history = history();
+    let prefix = param_prefix.to_string();
+    queue_effect(move || restore(&*history, view, &prefix, synced));
+}
+
+/// Apply the parameters of the URL to the view if a navigation changed them
+fn restore<C>(
+    history: &dyn History,
+    view: TableView<C>,
+    prefix: &str,
+    mut synced: CopyValue<Option<Params>>,
+) where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Display + FromStr,
+{
+    let params = route_params(&history.current_route(), prefix);
+    if synced.peek().as_ref() == Some(&params) || (synced.peek().is_none() && params.is_empty()) {
+        return;
+    }
+    let applied = parse_params(view, prefix, &params)
+        .and_then(|preset| view.apply_view(&preset).ok())
+        .is_some();
+    if applied {
+        synced.set(Some(params));
+    } else {
+        let params = view_params(view, prefix);
+        replace_params(history, prefix, &params);
+        synced.set(Some(params));
+    }
+}
+
+/// Get the parameters describing the state of the view (reactive)
+fn view_params<C>(view: TableView<C>, prefix: &str) -> Params
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Display,
+{
+    let mut params = Vec::new();
+    let sort = view.sort.read();
+    if !sort.is_empty() {
+        let keys: Vec<String> = sort
+            .iter()
+            .map(|(column, direction)| match direction {
+                SortDirection::Ascending => column.to_string(),
+                SortDirection::Descending => format!("-{column}"),
+            })
+            .collect();
+        params.push((format!("{prefix}sort"), keys.join(",")));
+    }
+    let filters = view.filters.read();
+    for (column, op) in filters.filters() {
+        let condition = match op {
+            FilterOp::Contains(text) => format!("{column}:{text}"),
+            FilterOp::Equals(text) => format!("{column}={text}"),
+            FilterOp::GreaterThan(value) => format!("{column}>{value}"),
+            FilterOp::LessThan(value) => format!("{column}<{value}"),
+            FilterOp::AtLeast(value) => format!("{column}>={value}"),
+            FilterOp::AtMost(value) => format!("{column}<={value}"),
+        };
+        params.push((format!("{prefix}filter"), condition));
+    }
+    if filters.mode() == FilterMode::Any {
+        params.push((format!("{prefix}match"), "any".to_string()));
+    }
+    let page = *view.page.read();
+    if page > 0 {
+        params.push((format!("{prefix}page"), (page + 1).to_string()));
+    }
+    if let Some(key) = view.store.selected_key() {
+        params.push((format!("{prefix}selected"), key.to_string()));
+    }
+    params
+}
+
+/// Build the preset described by the parameters, `None` if one of them doesn't parse
+fn parse_params<C>(view: TableView<C>, prefix: &str, params: &Params) -> Option<ViewPreset<C::Key>>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + FromStr,
+{
+    let mut preset = view.capture_view("");
+    preset.sort = Vec::new();
+    preset.filters = FilterSet::new();
+    preset.page = 0;
+    preset.selected_key = None;
+    for (name, value) in params {
+        match name.strip_prefix(prefix)? {
+            "sort" => {
+                for key in value.split(',') {
+                    preset.sort.push(match key.strip_prefix('-') {
+                        Some(column) => (column.parse().ok()?, SortDirection::Descending),
+                        None => (key.parse().ok()?, SortDirection::Ascending),
+                    });
+                }
+            }
+            "filter" => {
+                let split = value.find(|c: char| !c.is_ascii_digit())?;
+                let (column, rest) = value.split_at(split);
+                let number = |value: &str| value.parse::<f64>().ok();
+                let op = if let Some(text) = rest.strip_prefix(':') {
+                    FilterOp::Contains(text.to_string())
+                } else if let Some(text) = rest.strip_prefix('=') {
+                    FilterOp::Equals(text.to_string())
+                } else if let Some(value) = rest.strip_prefix(">=") {
+                    FilterOp::AtLeast(number(value)?)
+                } else if let Some(value) = rest.strip_prefix("<=") {
+                    FilterOp::AtMost(number(value)?)
+                } else if let Some(value) = rest.strip_prefix('>') {
+                    FilterOp::GreaterThan(number(value)?)
+                } else {
+                    FilterOp::LessThan(number(rest.strip_prefix('<')?)?)
+                };
+                preset.filters.filters.push((column.parse().ok()?, op));
+            }
+            "match" if value == "any" => preset.filters.mode = FilterMode::Any,
+            "page" => preset.page = value.parse::<usize>().ok()?.checked_sub(1)?,
+            "selected" => preset.selected_key = Some(value.parse().ok()?),
+            _ => return None,
+        }
+    }
+    Some(preset)
+}
+
+/// Check if a parameter name is one of the view parameters
+fn is_view_param(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix)
+        .is_some_and(|name| matches!(name, "sort" | "filter" | "match" | "page" | "selected"))
+}
+
+/// Get the view parameters of a route
+fn route_params(route: &str, prefix: &str) -> Params {
+    let (_, query, _) = split_route(route);
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(name), decode(value))
+        })
+        .filter(|(name, _)| is_view_param(name, prefix))
+        .collect()
+}
+
+/// Replace the view parameters of the current route, keeping the others
+fn replace_params(history: &dyn History, prefix: &str, params: &Params) {
+    let route = history.current_route();
+    let (path, query, fragment) = split_route(&route);
+    let pairs: Vec<String> = query
+        .split('&')
+        .filter(|pair| {
+            let name = pair.split_once('=').map_or(*pair, |(name, _)| name);
+            !pair.is_empty() && !is_view_param(&decode(name), prefix)
+        })
+        .map(str::to_string)
+        .chain(
+            params
+                .iter()
+                .map(|(name, value)| format!("{}={}", encode(name), encode(value))),
+        )
+        .collect();
+    let mut replaced = path.to_string();
+    if !pairs.is_empty() {
+        replaced.push('?');
+        replaced.push_str(&pairs.join("&"));
+    }
+    replaced.push_str(fragment);
+    if replaced != route {
+        history.replace(replaced);
+    }
+}
+
+/// Split a route into its path, query string (without `?`) and fragment (with `#`)
+fn split_route(route: &str) -> (&str, &str, &str) {
+    let (route, fragment) = route.find('#').map_or((route, ""), |at| route.split_at(at));
+    let (path, query) = route.split_once('?').unwrap_or((route, ""));
+    (path, query, fragment)
+}
+
+/// Percent-encode a query string component
+fn encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+/// Decode a percent-encoded query string component, `+` being a space
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', _) => decoded.push(b' '),
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
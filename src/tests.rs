@@ -1,6 +1,7 @@
 use crate::*;
 use dioxus::prelude::*;
 use std::collections::HashMap;
+use std::task::{Context, Poll, Waker};
 
 // Helper macro to run tests within a Dioxus runtime context
 macro_rules! test_with_runtime {
@@ -362,6 +363,8 @@ fn test_set_with_hashmap() {
 }
 
 #[test]
+#[cfg(feature = "compat")]
+#[allow(deprecated)]
 fn test_peek_api() {
     test_with_runtime!(|| {
         let store = CollectionStore::new(vec![100, 200, 300]);
@@ -382,6 +385,20 @@ fn test_peek_api() {
 }
 
 #[test]
+fn test_peek_value() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec![100, 200, 300]);
+        assert_eq!(store.peek_value(&1), Some(200));
+        assert_eq!(store.peek_value(&3), None);
+
+        store.set(1, 999);
+        assert_eq!(store.peek_value(&1), Some(999));
+    });
+}
+
+#[test]
+#[cfg(feature = "compat")]
+#[allow(deprecated)]
 fn test_peek_with_hashmap() {
     test_with_runtime!(|| {
         let mut map = std::collections::HashMap::new();
@@ -583,6 +600,30 @@ fn test_sequential_first_last() {
     });
 }
 
+#[test]
+fn test_last_key() {
+    use std::collections::BTreeMap;
+
+    assert_eq!(Vec::<i32>::new().last_key(), None);
+    assert_eq!(vec![10, 20].last_key(), Some(1));
+    assert_eq!(BTreeMap::from([(3, "c"), (1, "a")]).last_key(), Some(3));
+    assert_eq!(HashMap::from([(1, "a")]).last_key(), Some(1));
+}
+
+#[test]
+fn test_key_position() {
+    use std::collections::BTreeMap;
+
+    assert_eq!(vec![10, 20].key_position(&1), Some(1));
+    assert_eq!(vec![10, 20].key_position(&2), None);
+    let map = BTreeMap::from([(3, "c"), (1, "a"), (2, "b")]);
+    assert_eq!(map.key_position(&3), Some(2));
+    assert_eq!(map.key_position(&4), None);
+    let map = HashMap::from([(1, "a"), (2, "b")]);
+    let keys = Collection::keys(&map);
+    assert_eq!(map.key_position(&keys[1]), Some(1));
+}
+
 #[test]
 fn test_sequential_swap() {
     test_with_runtime!(|| {
@@ -669,3 +710,3585 @@ fn test_item_remove_clears_selection() {
         assert_eq!(*store.get(&1).read(), 20);
     });
 }
+
+#[test]
+fn test_value_lookups() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec![10, 20, 30, 20]);
+
+        assert!(store.contains_value(&30));
+        assert!(!store.contains_value(&99));
+        assert_eq!(store.position_of(&20), Some(1));
+        assert_eq!(store.position_of(&99), None);
+        assert_eq!(store.find_key(|value| *value > 15), Some(1));
+        assert_eq!(store.find_key(|value| *value > 100), None);
+
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), 1);
+        map.insert("b".to_string(), 2);
+        let store = CollectionStore::new(map);
+
+        assert!(store.contains_value(&2));
+        assert_eq!(store.position_of(&2), Some("b".to_string()));
+        assert_eq!(store.find_key(|value| *value == 1), Some("a".to_string()));
+    });
+}
+
+#[test]
+fn test_sequential_insert_before_after() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec![10, 30]);
+
+        // Insert in the middle
+        assert_eq!(store.insert_after(&0, 20), Ok(1));
+        assert_eq!(store.items().read().clone(), vec![10, 20, 30]);
+
+        // Insert before the first item
+        assert_eq!(store.insert_before(&0, 5), Ok(0));
+        assert_eq!(store.items().read().clone(), vec![5, 10, 20, 30]);
+
+        // Insert after the last item appends
+        assert_eq!(store.insert_after(&3, 40), Ok(4));
+        assert_eq!(store.items().read().clone(), vec![5, 10, 20, 30, 40]);
+
+        // Unknown key
+        assert_eq!(
+            store.insert_after(&99, 0),
+            Err(CollectionError::KeyNotFound)
+        );
+        assert_eq!(store.len(), 5);
+    });
+}
+
+#[test]
+fn test_sequential_duplicate_keeps_selection() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec!["a".to_string(), "b".to_string()]);
+
+        // Select "b", then duplicate "a": the selection must follow "b"
+        store.select(&1).unwrap();
+        assert_eq!(store.duplicate(&0), Ok(1));
+        assert_eq!(
+            store.items().read().clone(),
+            vec!["a".to_string(), "a".to_string(), "b".to_string()]
+        );
+        assert_eq!(store.selected_key(), Some(2));
+        assert_eq!(&*store.selected().unwrap().read(), "b");
+
+        // Duplicating after the selected item leaves the selection untouched
+        assert_eq!(store.duplicate(&2), Ok(3));
+        assert_eq!(store.selected_key(), Some(2));
+
+        assert_eq!(store.duplicate(&10), Err(CollectionError::KeyNotFound));
+    });
+}
+
+#[test]
+#[cfg(feature = "fake-data")]
+fn test_populate_fake() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(Vec::new());
+        store.populate_fake(5, |i| i * 2);
+        assert_eq!(store.items().read().clone(), vec![0, 2, 4, 6, 8]);
+
+        let store = CollectionStore::new(HashMap::new());
+        store.populate_fake_entries(3, |i| (format!("key{i}"), i));
+        assert_eq!(store.len(), 3);
+        assert_eq!(*store.get(&"key2".to_string()).read(), 2);
+    });
+}
+
+#[test]
+#[cfg(feature = "fake-data")]
+fn test_mutation_simulator() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec![0; 5]);
+
+        // Only removals enabled: drains the collection then stops
+        let mut simulator = MutationSimulator::new(store).seed(7).removals();
+        for _ in 0..5 {
+            assert!(matches!(
+                simulator.step(),
+                Some(SimulatedMutation::Removed(_))
+            ));
+        }
+        assert!(store.is_empty());
+        assert_eq!(simulator.step(), None);
+
+        // Inserts respect max_len
+        let mut simulator = MutationSimulator::new(store)
+            .seed(7)
+            .inserts(|_| 1)
+            .max_len(3);
+        for _ in 0..10 {
+            simulator.step();
+        }
+        assert_eq!(store.len(), 3);
+
+        // Edits mutate in place
+        let mut simulator = MutationSimulator::new(store)
+            .seed(7)
+            .edits(|_, value| *value += 10);
+        assert!(matches!(
+            simulator.step(),
+            Some(SimulatedMutation::Edited(_))
+        ));
+        assert_eq!(store.iter().filter(|item| *item.read() == 11).count(), 1);
+    });
+}
+
+#[test]
+fn test_remove_many() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec![10, 20, 30, 40, 50]);
+
+        // Selected item survives and follows its shifted index
+        store.select(&3).unwrap();
+        let removed = store.remove_many([2, 0, 2, 99]);
+        assert_eq!(removed, vec![10, 30]);
+        assert_eq!(store.items().read().clone(), vec![20, 40, 50]);
+        assert_eq!(store.selected_key(), Some(1));
+        assert_eq!(*store.selected().unwrap().read(), 40);
+
+        // Removing the selected item clears the selection
+        let removed = store.remove_many([1, 2]);
+        assert_eq!(removed, vec![40, 50]);
+        assert_eq!(store.selected_key(), None);
+        assert_eq!(store.items().read().clone(), vec![20]);
+
+        // Maps keep their keys stable
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), 1);
+        map.insert("b".to_string(), 2);
+        map.insert("c".to_string(), 3);
+        let store = CollectionStore::new(map);
+        store.select(&"c".to_string()).unwrap();
+        let mut removed = store.remove_many(["a".to_string(), "b".to_string()]);
+        removed.sort();
+        assert_eq!(removed, vec![1, 2]);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.selected_key(), Some("c".to_string()));
+    });
+}
+
+#[test]
+fn test_recording_and_replay() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        store.select(&2).unwrap();
+        assert!(!store.is_recording());
+
+        store.start_recording();
+        assert!(store.is_recording());
+        store.push(4);
+        store.insert_before(&0, 0).unwrap();
+        store.duplicate(&4).unwrap();
+        store.get(&1).set(10);
+        store.swap(&0, &1).unwrap();
+        store.remove_many([2, 3]);
+        store.select(&0).unwrap();
+        store.pop();
+        // Failed operations are not recorded
+        assert!(store.select(&99).is_err());
+
+        let bundle = store.stop_recording().unwrap();
+        assert!(!store.is_recording());
+        assert_eq!(bundle.initial, vec![1, 2, 3]);
+        assert_eq!(bundle.initial_selection, Some(2));
+        assert_eq!(bundle.operations.len(), 8);
+        assert_eq!(bundle.operations[0], CollectionOperation::Push { value: 4 });
+
+        let replayed = CollectionStore::replay_bundle(bundle).unwrap();
+        assert_eq!(
+            replayed.items().read().clone(),
+            store.items().read().clone()
+        );
+        assert_eq!(replayed.selected_key(), store.selected_key());
+
+        // Nothing is recorded anymore
+        store.push(5);
+        assert!(store.stop_recording().is_none());
+    });
+}
+
+#[test]
+fn test_recording_replay_hashmap() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(HashMap::from([("a".to_string(), 1)]));
+
+        store.start_recording();
+        store.insert("b".to_string(), 2);
+        store.extend(vec![("c".to_string(), 3)]);
+        store.select(&"b".to_string()).unwrap();
+        store.remove(&"a".to_string());
+        let bundle = store.stop_recording().unwrap();
+
+        let replayed = CollectionStore::replay_bundle(bundle).unwrap();
+        assert_eq!(
+            replayed.items().read().clone(),
+            store.items().read().clone()
+        );
+        assert_eq!(replayed.selected_key(), Some("b".to_string()));
+
+        // Sequential operations can't be replayed on maps
+        let bundle = RecordingBundle {
+            initial: HashMap::<String, i32>::new(),
+            initial_selection: None,
+            operations: vec![CollectionOperation::Pop],
+        };
+        assert!(CollectionStore::replay_bundle(bundle).is_err());
+    });
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_recording_bundle_serde() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec!["a".to_string()]);
+        store.start_recording();
+        store.push("b".to_string());
+        store.select(&1).unwrap();
+        let bundle = store.stop_recording().unwrap();
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        let decoded: RecordingBundle<Vec<String>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, bundle);
+
+        let replayed = CollectionStore::replay_bundle(decoded).unwrap();
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(replayed.selected_key(), Some(1));
+    });
+}
+
+// Run a test in the root scope after the initial render, so panics aren't caught by the renderer
+macro_rules! test_outside_render {
+    ($test_fn:expr) => {{
+        let mut dom = VirtualDom::new(|| rsx! { div {} });
+        dom.rebuild_in_place();
+        dom.in_scope(ScopeId::ROOT, $test_fn);
+    }};
+}
+
+// Poll a future once, returning its output if it is already ready
+fn poll_once<T>(future: impl Future<Output = T>) -> Option<T> {
+    match std::pin::pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => Some(output),
+        Poll::Pending => None,
+    }
+}
+
+// Let the spawned tasks of `dom` run without a real executor
+fn run_tasks(dom: &mut VirtualDom) {
+    for _ in 0..4 {
+        let _ = poll_once(dom.wait_for_work());
+    }
+}
+
+#[test]
+fn test_strict_mode_valid_operations() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        assert!(!store.is_strict());
+        store.set_strict(true);
+        assert!(store.is_strict());
+
+        store.select(&2).unwrap();
+        store.push(4);
+        store.insert_before(&0, 0).unwrap();
+        store.swap(&0, &1).unwrap();
+        store.remove_many([0, 1]);
+        store.select(&2).unwrap();
+        store.pop();
+        assert_eq!(store.selected_key(), None);
+        store.clear();
+    });
+}
+
+#[test]
+#[should_panic(expected = "the selected key is no longer in the collection")]
+fn test_strict_mode_dangling_selection() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        store.set_strict(true);
+        store.selected_key_signal().set(Some(5));
+        store.push(4);
+    });
+}
+
+#[test]
+fn test_strict_mode_disabled() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        store.selected_key_signal().set(Some(5));
+        store.push(4);
+        assert_eq!(store.selected_key(), Some(5));
+    });
+}
+
+#[test]
+fn test_remove_shifts_positional_selection() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c"]);
+        store.set_strict(true);
+        store.select(&2).unwrap();
+        assert_eq!(store.remove(&0), Some("a"));
+        assert_eq!(store.selected_key(), Some(1));
+        assert_eq!(store.selected().map(|item| *item.read()), Some("c"));
+
+        // A selection before the removed item doesn't move
+        store.select(&0).unwrap();
+        store.remove(&1);
+        assert_eq!(store.selected_key(), Some(0));
+        store.remove(&0);
+        assert_eq!(store.selected_key(), None);
+
+        // Keyed collections keep the selected key
+        let users = CollectionStore::new(HashMap::from([("ada", 36), ("alan", 41)]));
+        users.select(&"alan").unwrap();
+        users.remove(&"ada");
+        assert_eq!(users.selected_key(), Some("alan"));
+    });
+}
+
+#[test]
+fn test_strict_mode_write_during_render() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    static PANICKED: AtomicBool = AtomicBool::new(false);
+
+    fn app() -> Element {
+        let store = use_hook(|| {
+            let store = CollectionStore::new(vec![1, 2, 3]);
+            store.set_strict(true);
+            store
+        });
+        // The runtime only flags re-renders, so write on the second render
+        if dioxus_core::generation() > 0 {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| store.push(4)));
+            PANICKED.store(result.is_err(), Ordering::SeqCst);
+        }
+        rsx! { div {} }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert!(PANICKED.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_swap_remove() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+        store.set_strict(true);
+
+        // The last item moves into the removed slot and keeps its selection
+        store.select(&3).unwrap();
+        assert_eq!(store.swap_remove(&1), Some("b"));
+        assert_eq!(*store.items().read(), vec!["a", "d", "c"]);
+        assert_eq!(store.selected_key(), Some(1));
+
+        // Removing the selected item clears the selection
+        assert_eq!(store.swap_remove(&1), Some("d"));
+        assert_eq!(*store.items().read(), vec!["a", "c"]);
+        assert_eq!(store.selected_key(), None);
+
+        // Removing the last item doesn't move anything
+        store.select(&0).unwrap();
+        assert_eq!(store.swap_remove(&1), Some("c"));
+        assert_eq!(store.selected_key(), Some(0));
+
+        assert_eq!(store.swap_remove(&5), None);
+        assert_eq!(store.len(), 1);
+    });
+}
+
+#[test]
+fn test_append_between_stores() {
+    test_outside_render!(|| {
+        let todo = CollectionStore::new(vec!["a", "b"]);
+        let done = CollectionStore::new(vec!["c"]);
+        done.set_strict(true);
+        todo.select(&1).unwrap();
+        done.select(&0).unwrap();
+
+        done.append(&todo);
+        assert_eq!(*done.items().read(), vec!["c", "a", "b"]);
+        assert_eq!(done.selected_key(), Some(0));
+        assert!(todo.is_empty());
+        assert_eq!(todo.selected_key(), None);
+
+        // Appending a store to itself is a no-op
+        done.append(&done);
+        assert_eq!(done.len(), 3);
+
+        let scores = CollectionStore::new(HashMap::from([("a", 1), ("b", 2)]));
+        let other = CollectionStore::new(HashMap::from([("b", 20), ("c", 30)]));
+        scores.append(&other);
+        assert_eq!(
+            *scores.items().read(),
+            HashMap::from([("a", 1), ("b", 20), ("c", 30)])
+        );
+    });
+}
+
+#[test]
+fn test_extend_from_store() {
+    test_outside_render!(|| {
+        let backlog = CollectionStore::new(vec![1, 2, 3, 4]);
+        let sprint = CollectionStore::new(vec![10]);
+        sprint.select(&0).unwrap();
+        sprint.start_recording();
+
+        sprint.extend_from_store(&backlog, |_, value| value % 2 == 0);
+        assert_eq!(*sprint.items().read(), vec![10, 2, 4]);
+        assert_eq!(sprint.selected_key(), Some(0));
+        assert_eq!(backlog.len(), 4);
+
+        let bundle = sprint.stop_recording().unwrap();
+        let replayed = CollectionStore::replay_bundle(bundle).unwrap();
+        assert_eq!(*replayed.items().read(), vec![10, 2, 4]);
+    });
+}
+
+#[test]
+fn test_item_meta_follows_items() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+        store.meta(&1).set_flag("expanded", true);
+        store.get(&3).meta().insert("tag", "urgent");
+        assert!(store.meta(&1).flag("expanded"));
+        assert!(!store.meta(&0).flag("expanded"));
+        assert!(store.meta(&0).read().is_empty());
+
+        // ["z", "a", "b", "c", "d"]
+        store.insert_before(&0, "z").unwrap();
+        assert!(store.meta(&2).flag("expanded"));
+        assert_eq!(store.meta(&4).get("tag"), Some("urgent".to_string()));
+
+        // ["z", "d", "c", "b"]
+        store.swap(&2, &4).unwrap();
+        store.remove(&1);
+        assert!(store.meta(&3).flag("expanded"));
+        assert_eq!(store.meta(&1).get("tag"), Some("urgent".to_string()));
+
+        // ["b", "d", "c"]
+        store.swap_remove(&0);
+        assert!(store.meta(&0).flag("expanded"));
+
+        // ["c"]
+        store.remove_many([0, 1]);
+        assert!(store.meta(&0).read().is_empty());
+
+        store.meta(&0).set_flag("pinned", true);
+        store.pop();
+        store.push("e");
+        assert!(!store.meta(&0).flag("pinned"));
+    });
+}
+
+#[test]
+fn test_item_meta_keyed() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(HashMap::from([("a", 1), ("b", 2)]));
+        store.meta(&"a").set_flag("editing", true);
+        store.meta(&"b").set_flag("editing", true);
+        store.meta(&"b").set_flag("editing", false);
+        assert!(store.meta(&"b").read().is_empty());
+
+        store.insert("c", 3);
+        assert!(store.meta(&"a").flag("editing"));
+        store.remove(&"a");
+        store.insert("a", 1);
+        assert!(!store.meta(&"a").flag("editing"));
+
+        store.meta(&"c").insert("note", "x");
+        store.clear();
+        assert!(store.meta(&"c").read().is_empty());
+    });
+}
+
+#[test]
+fn test_item_meta_typed() {
+    #[derive(Default, Debug, PartialEq)]
+    struct Draft(String);
+
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c"]);
+        // Reading absent metadata returns the default without storing it
+        assert_eq!(*store.meta_of::<Draft>(&1).read(), Draft::default());
+        assert!(!store.meta_of::<Draft>(&1).is_set());
+        assert!(!store.has_meta());
+
+        store.meta_of::<Draft>(&1).write().0.push_str("hello");
+        store.meta(&1).set_flag("editing", true);
+        assert!(store.meta_of::<Draft>(&1).is_set());
+
+        // Each type follows the items on its own
+        store.insert_before(&0, "z").unwrap();
+        assert_eq!(store.meta_of::<Draft>(&2).read().0, "hello");
+        assert!(store.meta(&2).flag("editing"));
+        store.remove(&2);
+        assert!(!store.meta_of::<Draft>(&2).is_set());
+        assert!(!store.has_meta());
+
+        store.meta_of::<Draft>(&0).write().0.push_str("draft");
+        assert_eq!(
+            store.meta_of::<Draft>(&0).take(),
+            Some(Draft("draft".into()))
+        );
+        assert_eq!(store.meta_of::<Draft>(&0).take(), None);
+    });
+}
+
+#[test]
+fn test_item_meta_subscriptions() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c"]);
+        let runs = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = runs.clone();
+        let first_expanded = Memo::new(move || {
+            counter.set(counter.get() + 1);
+            store.meta(&0).flag("expanded")
+        });
+        let last_set = Memo::new(move || store.meta(&2).is_set());
+        assert!(!first_expanded());
+        assert!(!last_set());
+
+        // Readers only subscribe to their own entry
+        store.meta(&1).set_flag("expanded", true);
+        assert!(!first_expanded());
+        assert_eq!(runs.get(), 1);
+
+        store.meta(&0).set_flag("expanded", true);
+        assert!(first_expanded());
+        assert_eq!(runs.get(), 2);
+
+        store.meta(&2).insert("note", "x");
+        assert!(last_set());
+
+        // Structural changes move the metadata, so they notify every entry
+        store.clear_meta();
+        assert!(!first_expanded());
+        assert!(!last_set());
+    });
+}
+
+#[test]
+#[cfg(feature = "announcer")]
+fn test_live_announcements() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["milk".to_string(), "bread".to_string()]);
+        store.push("ignored".to_string());
+        assert_eq!(store.announcement(), "");
+
+        let templates = AnnouncementTemplates {
+            added: "Added {item}".to_string(),
+            ..Default::default()
+        };
+        store.enable_announcements(templates, |item: &String| item.to_uppercase());
+
+        store.push("eggs".to_string());
+        assert_eq!(store.announcement(), "Added EGGS");
+        store.select(&1).unwrap();
+        assert_eq!(store.announcement(), "BREAD selected");
+        store.remove_many([0, 2]);
+        assert_eq!(store.announcement(), "MILK, IGNORED removed");
+        store.pop();
+        assert_eq!(store.announcement(), "EGGS removed");
+
+        // Replacing an existing value isn't an addition
+        store.insert(0, "butter".to_string());
+        assert_eq!(store.announcement(), "EGGS removed");
+
+        store.clear();
+        assert_eq!(store.announcement(), "All items removed");
+
+        store.disable_announcements();
+        store.push("jam".to_string());
+        assert_eq!(store.announcement(), "");
+    });
+}
+
+#[test]
+fn test_pinned_items() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+        assert!(store.pinned_keys().is_empty());
+        assert_eq!(store.pin(&9), Err(CollectionError::KeyNotFound));
+
+        store.pin(&3).unwrap();
+        store.pin(&1).unwrap();
+        store.pin(&1).unwrap();
+        assert!(store.get(&1).is_pinned());
+        assert_eq!(store.pinned_keys(), vec![1, 3]);
+        let values: Vec<&str> = store.iter_pinned_first().map(|item| *item.read()).collect();
+        assert_eq!(values, vec!["b", "d", "a", "c"]);
+
+        // Pins follow their item
+        store.remove(&0);
+        assert_eq!(store.pinned_keys(), vec![0, 2]);
+
+        store.unpin(&0);
+        assert!(!store.is_pinned(&0));
+        assert_eq!(store.pinned_keys(), vec![2]);
+    });
+}
+
+#[test]
+fn test_focus_cursor() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c"]);
+        store.set_strict(true);
+        assert_eq!(store.focused_key(), None);
+        assert!(store.commit_focus().is_err());
+
+        assert_eq!(store.focus_prev(), Some(2));
+        assert_eq!(store.focus_next(), Some(2));
+        assert_eq!(store.focus_prev(), Some(1));
+        assert!(store.get(&1).is_focused());
+        assert_eq!(store.selected_key(), None);
+
+        store.commit_focus().unwrap();
+        assert_eq!(store.selected_key(), Some(1));
+        assert!(store.focus(&7).is_err());
+
+        // The focus follows its item, and is cleared with it
+        store.insert_before(&0, "z").unwrap();
+        assert_eq!(store.focused_key(), Some(2));
+        store.remove(&2);
+        assert_eq!(store.focused_key(), None);
+
+        store.get(&0).focus().unwrap();
+        store.clear();
+        assert_eq!(store.focused_key(), None);
+        assert_eq!(store.focus_next(), None);
+
+        let keyed = CollectionStore::new(HashMap::from([("a", 1), ("b", 2)]));
+        keyed.focus(&"a").unwrap();
+        keyed.remove(&"b");
+        assert_eq!(keyed.focused_key(), Some("a"));
+        keyed.remove(&"a");
+        assert_eq!(keyed.focused_key(), None);
+    });
+}
+
+#[test]
+#[cfg(feature = "listbox")]
+fn test_listbox() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["Apple", "Banana", "Cherry"]);
+        let listbox = Listbox::new(store);
+        let other = Listbox::new(store);
+        assert_ne!(listbox.attributes().id, other.attributes().id);
+        assert_eq!(listbox.attributes().aria_activedescendant, None);
+
+        listbox.handle_focus();
+        assert_eq!(store.focused_key(), Some(0));
+        assert!(listbox.handle_key("End"));
+        assert!(listbox.handle_key("ArrowUp"));
+        assert_eq!(store.selected_key(), None);
+        assert!(listbox.handle_key(" "));
+        assert_eq!(store.selected_key(), Some(1));
+        assert!(!listbox.handle_key("a"));
+
+        let options = listbox.options();
+        assert_eq!(options.len(), 3);
+        assert!(options[1].aria_selected && options[1].focused);
+        assert!(!options[0].aria_selected);
+        assert_eq!(
+            listbox.attributes().aria_activedescendant,
+            Some(options[1].id.clone())
+        );
+
+        listbox.handle_click(&2);
+        assert_eq!(store.selected_key(), Some(2));
+        assert_eq!(store.focused_key(), Some(2));
+
+        let follows = Listbox::new(store).with_selection_follows_focus(true);
+        follows.handle_key("Home");
+        assert_eq!(store.selected_key(), Some(0));
+    });
+}
+
+#[test]
+fn test_selectable_predicate() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![
+            ("a", true),
+            ("b", false),
+            ("c", true),
+            ("b", true),
+            ("e", false),
+        ]);
+        store.select(&1).unwrap();
+        store.set_selectable(|_, (_, enabled)| *enabled);
+        assert_eq!(store.selected_key(), None);
+
+        assert_eq!(store.select(&1), Err(CollectionError::NotSelectable));
+        assert!(!store.get(&1).is_selectable());
+        assert!(store.get(&0).is_selectable());
+
+        assert_eq!(store.select_next(), Some(0));
+        assert_eq!(store.select_next(), Some(2));
+        assert_eq!(store.select_next(), Some(3));
+        assert_eq!(store.select_next(), Some(3));
+        assert_eq!(store.select_prev(), Some(2));
+        assert_eq!(store.select_prev(), Some(0));
+        assert_eq!(store.select_prev(), Some(0));
+
+        // Value lookups skip disabled items
+        store.select_by_value(&("b", true)).unwrap();
+        assert_eq!(store.selected_key(), Some(3));
+        assert_eq!(
+            store.select_by_value(&("e", false)),
+            Err(CollectionError::NotSelectable)
+        );
+
+        store.clear_selectable();
+        store.select(&1).unwrap();
+    });
+}
+
+#[test]
+fn test_composite_operations() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![5, 1, 4, 2, 3]);
+        store.set_strict(true);
+        store.start_recording();
+
+        // The selection follows its item through every operation
+        store.select(&2).unwrap();
+        store.sort_by(|a, b| a.cmp(b));
+        assert_eq!(*store.items().read(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(store.selected_key(), Some(3));
+
+        store.move_item(&3, &0).unwrap();
+        assert_eq!(*store.items().read(), vec![4, 1, 2, 3, 5]);
+        assert_eq!(store.selected_key(), Some(0));
+        store.move_item(&0, &4).unwrap();
+        assert_eq!(*store.items().read(), vec![1, 2, 3, 5, 4]);
+        assert_eq!(store.selected_key(), Some(4));
+        assert_eq!(store.move_item(&0, &9), Err(CollectionError::KeyNotFound));
+
+        store.retain(|_, value| *value > 2);
+        assert_eq!(*store.items().read(), vec![3, 5, 4]);
+        assert_eq!(store.selected_key(), Some(2));
+        store.retain(|_, value| *value != 4);
+        assert_eq!(store.selected_key(), None);
+
+        // Replaying the recording reproduces the same collection
+        let bundle = store.stop_recording().unwrap();
+        let replayed = CollectionStore::replay_bundle(bundle).unwrap();
+        assert_eq!(*replayed.items().read(), vec![3, 5]);
+
+        assert_eq!(store.drain(), vec![(0, 3), (1, 5)]);
+        assert!(store.is_empty());
+
+        let scores = CollectionStore::new(HashMap::from([("a", 1), ("b", 2), ("c", 3)]));
+        scores.select(&"c").unwrap();
+        scores.retain(|key, _| *key != "a");
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores.selected_key(), Some("c"));
+    });
+}
+
+#[test]
+fn test_range_selection() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c", "d", "e"]);
+        store.set_strict(true);
+        assert!(store.selected_keys().is_empty());
+
+        // Shift-click extends from the selected item, then from the same anchor
+        store.select(&1).unwrap();
+        assert_eq!(store.selected_keys(), vec![1]);
+        store.extend_selection_to(&3).unwrap();
+        assert_eq!(store.selected_keys(), vec![1, 2, 3]);
+        assert_eq!(store.selection_anchor(), Some(1));
+        store.extend_selection_to(&0).unwrap();
+        assert_eq!(store.selected_keys(), vec![0, 1]);
+        assert!(store.get(&1).is_selected());
+        assert!(!store.get(&2).is_selected());
+
+        // The range follows its items and skips disabled ones
+        store.select_range(&1, &4).unwrap();
+        store.remove_many([0]);
+        assert_eq!(store.selected_keys(), vec![0, 1, 2, 3]);
+        store.set_selectable(|_, value| *value != "c");
+        assert_eq!(store.selected_keys(), vec![0, 2, 3]);
+        assert_eq!(
+            store.select_range(&0, &1),
+            Err(CollectionError::NotSelectable)
+        );
+
+        // A plain selection drops the range
+        store.select(&2).unwrap();
+        assert_eq!(store.selection_anchor(), None);
+        assert_eq!(store.selected_keys(), vec![2]);
+        assert_eq!(
+            store.select_range(&0, &9),
+            Err(CollectionError::KeyNotFound)
+        );
+    });
+}
+
+#[test]
+fn test_checked_items() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c"]);
+        assert_eq!(store.check_state(), CheckState::None);
+
+        assert_eq!(store.get(&1).toggle_checked(), Ok(true));
+        store.check(&2).unwrap();
+        assert_eq!(store.checked_keys(), vec![1, 2]);
+        assert_eq!(store.check_state(), CheckState::Some);
+        assert_eq!(store.check(&5), Err(CollectionError::KeyNotFound));
+
+        // Checks follow their items and stay separate from the selection
+        store.remove_many([0]);
+        assert_eq!(store.checked_keys(), vec![0, 1]);
+        assert_eq!(store.check_state(), CheckState::All);
+        assert_eq!(store.selected_key(), None);
+
+        assert_eq!(store.toggle_checked(&0), Ok(false));
+        assert!(!store.get(&0).is_checked());
+        store.pin(&1).unwrap();
+        store.uncheck_all();
+        assert_eq!(store.check_state(), CheckState::None);
+        assert!(store.is_pinned(&1));
+
+        store.push("d");
+        store.check_all();
+        assert_eq!(store.checked_keys(), vec![0, 1, 2]);
+    });
+}
+
+#[test]
+fn test_as_store_lenses() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        store.select(&2).unwrap();
+
+        let data = store.as_store();
+        assert_eq!(data.read().items(), &vec![1, 2, 3]);
+        assert_eq!(data.read().selected_key(), Some(&2));
+
+        // Lens writes are visible through the collection store, and converting back is lossless
+        data.items().write().push(4);
+        assert_eq!(store.len(), 4);
+        assert_eq!(CollectionStore::from(data), store);
+    });
+}
+
+#[test]
+fn test_map_values() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let doubled = store.map_values(move |value| {
+            counter.set(counter.get() + 1);
+            value * 2
+        });
+
+        assert_eq!(doubled.values(), vec![2, 4, 6]);
+        assert_eq!(calls.get(), 3);
+
+        // Only the changed item is projected again
+        store.set(1, 10);
+        assert_eq!(doubled.entries(), vec![(0, 2), (1, 20), (2, 6)]);
+        assert_eq!(doubled.get(&1), Some(20));
+        assert_eq!(calls.get(), 4);
+
+        store.push(4);
+        assert_eq!(doubled.get(&3), Some(8));
+        assert_eq!(doubled.get(&9), None);
+        assert_eq!(doubled.len(), 4);
+        assert_eq!(calls.get(), 5);
+        assert!(doubled.source() == store);
+    });
+}
+
+#[test]
+fn test_join_stores() {
+    test_outside_render!(|| {
+        let users = CollectionStore::new(vec!["ada", "alan", "grace"]);
+        let presence = CollectionStore::new(HashMap::from([(0, "online"), (2, "away")]));
+        let rows = users.join(&presence);
+
+        let statuses: Vec<(usize, &str, Option<&str>)> = rows
+            .iter()
+            .map(|(key, user, status)| (key, *user.read(), status.map(|status| *status.read())))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (0, "ada", Some("online")),
+                (1, "alan", None),
+                (2, "grace", Some("away"))
+            ]
+        );
+
+        presence.insert(1, "online");
+        assert!(rows.get(&1).unwrap().1.is_some());
+        assert!(rows.get(&3).is_none());
+        assert_eq!(rows.len(), 3);
+    });
+}
+
+#[test]
+#[cfg(feature = "typeahead")]
+fn test_select_by_prefix() {
+    test_outside_render!(|| {
+        let fruits = CollectionStore::new(vec!["Banana", "cherry", "Blueberry", "Blackberry"]);
+        fruits.set_selectable(|_, fruit| *fruit != "Blackberry");
+
+        // Repeating a letter cycles through the selectable items starting with it
+        assert_eq!(fruits.select_by_prefix("b"), Some(0));
+        assert_eq!(fruits.select_by_prefix("bb"), Some(2));
+        assert_eq!(fruits.select_by_prefix("bbb"), Some(0));
+        // A longer prefix keeps the selection while it matches
+        assert_eq!(fruits.select_by_prefix("ba"), Some(0));
+        assert_eq!(fruits.select_by_prefix("C"), Some(1));
+        assert_eq!(fruits.select_by_prefix("bla"), None);
+        assert_eq!(fruits.select_by_prefix(""), None);
+        assert_eq!(fruits.selected_key(), Some(1));
+
+        let codes = CollectionStore::new(HashMap::from([("fr", 1), ("de", 2)]));
+        assert_eq!(codes.select_by_key_prefix("D"), Some("de"));
+
+        let typeahead = Typeahead::new(fruits);
+        assert!(typeahead.handle_key("b"));
+        assert!(typeahead.handle_key("l"));
+        assert_eq!(typeahead.buffer(), "bl");
+        assert_eq!(fruits.selected_key(), Some(2));
+        assert!(!typeahead.handle_key("ArrowDown"));
+        typeahead.reset();
+        assert!(!typeahead.handle_key(" "));
+
+        // The buffer restarts after a pause
+        let typeahead = typeahead.with_timeout(std::time::Duration::ZERO);
+        typeahead.handle_key("b");
+        typeahead.handle_key("c");
+        assert_eq!(typeahead.buffer(), "c");
+        assert_eq!(fruits.selected_key(), Some(1));
+    });
+}
+
+#[test]
+fn test_map_in_place_and_for_each_mut() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        let writes = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = writes.clone();
+        let sum = Memo::new(move || {
+            counter.set(counter.get() + 1);
+            store.items().read().iter().sum::<i32>()
+        });
+        assert_eq!(sum(), 6);
+
+        store.start_recording();
+        store.map_in_place(|key, value| value * 10 + *key as i32);
+        assert_eq!(sum(), 63);
+        store.for_each_mut(|_, value| *value += 1);
+        assert_eq!(sum(), 66);
+        // One recomputation per batch
+        assert_eq!(writes.get(), 3);
+        assert_eq!(*store.items().peek(), vec![11, 22, 33]);
+        let bundle = store.stop_recording().unwrap();
+        assert_eq!(bundle.operations.len(), 6);
+        // The operations carry the updated values
+        let replayed = CollectionStore::replay_bundle(bundle).unwrap();
+        assert_eq!(*replayed.items().peek(), vec![11, 22, 33]);
+
+        // Unchanged values are skipped with deduplicated writes
+        store.dedup_writes(true);
+        store.map_in_place(|_, value| *value);
+        assert_eq!(sum(), 66);
+        assert_eq!(writes.get(), 3);
+    });
+}
+
+#[test]
+fn test_partition() {
+    test_outside_render!(|| {
+        let active = CollectionStore::new(vec![("docs", true), ("bug", false), ("release", true)]);
+        active.select(&1).unwrap();
+        let archive = CollectionStore::new(vec![("old", true)]);
+
+        let (done, todo) = active.partition(|_, (_, done)| *done);
+        assert_eq!(done.len(), 2);
+        assert_eq!(todo.peek_value(&0), Some(("bug", false)));
+        assert_eq!(active.len(), 3);
+
+        assert_eq!(active.partition_into(&archive, |_, (_, done)| *done), 2);
+        assert_eq!(*active.items().peek(), vec![("bug", false)]);
+        assert_eq!(active.selected_key(), Some(0));
+        assert_eq!(archive.len(), 3);
+        assert_eq!(archive.peek_value(&2), Some(("release", true)));
+        assert_eq!(active.partition_into(&archive, |_, (_, done)| *done), 0);
+        assert_eq!(active.partition_into(&active, |_, _| true), 0);
+    });
+}
+
+#[test]
+fn test_dedup() {
+    test_outside_render!(|| {
+        let tags = CollectionStore::new(vec!["rust", "web", "rust", "ui", "web", "rust"]);
+        tags.select(&3).unwrap();
+        assert_eq!(tags.dedup(), vec![2, 4, 5]);
+        assert_eq!(*tags.items().peek(), vec!["rust", "web", "ui"]);
+        assert_eq!(tags.selected_key(), Some(2));
+        assert!(tags.dedup().is_empty());
+
+        let users = CollectionStore::new(HashMap::from([(1, "ada@x.org"), (2, "ADA@x.org")]));
+        let removed = users.dedup_by_key(|email| email.to_lowercase());
+        assert_eq!(removed.len(), 1);
+        assert_eq!(users.len(), 1);
+    });
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_shuffle_and_sample() {
+    use rand::SeedableRng;
+
+    test_outside_render!(|| {
+        let cards = CollectionStore::new((0..20).collect::<Vec<i32>>());
+        cards.select(&5).unwrap();
+        cards.start_recording();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        cards.shuffle(&mut rng);
+
+        let shuffled = cards.items().peek().clone();
+        assert_ne!(shuffled, (0..20).collect::<Vec<i32>>());
+        let mut sorted = shuffled.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<i32>>());
+        assert_eq!(cards.peek_value(&cards.selected_key().unwrap()), Some(5));
+        // The recorded order is the old key of each item in its new position
+        let operations = cards.stop_recording().unwrap().operations;
+        assert!(matches!(
+            &operations[..],
+            [CollectionOperation::Reorder { order: keys }]
+                if keys.iter().map(|key| *key as i32).collect::<Vec<_>>() == shuffled
+        ));
+
+        let picked = cards.sample_with(3, &mut rng);
+        assert_eq!(picked.len(), 3);
+        assert_ne!(picked[0].key(), picked[1].key());
+        assert_eq!(cards.sample(50).len(), 20);
+    });
+}
+
+#[test]
+fn test_rotate_and_wrapping_selection() {
+    test_outside_render!(|| {
+        let slides = CollectionStore::new(vec!["a", "b", "c", "d"]);
+        slides.select(&1).unwrap();
+        slides.rotate_left(5);
+        assert_eq!(*slides.items().peek(), vec!["b", "c", "d", "a"]);
+        assert_eq!(slides.selected_key(), Some(0));
+        slides.rotate_right(2);
+        assert_eq!(*slides.items().peek(), vec!["d", "a", "b", "c"]);
+        assert_eq!(slides.selected_key(), Some(2));
+        slides.rotate_left(4);
+        assert_eq!(*slides.items().peek(), vec!["d", "a", "b", "c"]);
+
+        slides.set_selectable(|_, slide| *slide != "d");
+        slides.select(&3).unwrap();
+        assert_eq!(slides.select_next_wrapping(), Some(1));
+        assert_eq!(slides.select_prev_wrapping(), Some(3));
+        assert_eq!(slides.select_next(), Some(3));
+
+        let empty = CollectionStore::new(Vec::<i32>::new());
+        empty.rotate_right(3);
+        assert_eq!(empty.select_next_wrapping(), None);
+    });
+}
+
+#[test]
+#[cfg(feature = "index")]
+fn test_index_by() {
+    test_outside_render!(|| {
+        let tasks = CollectionStore::new(vec![("docs", 1), ("bug", 2), ("docs", 3)]);
+        let derived = std::rc::Rc::new(std::cell::Cell::new(0));
+        let by_tag = tasks.index_by({
+            let derived = derived.clone();
+            move |(tag, _)| {
+                derived.set(derived.get() + 1);
+                *tag
+            }
+        });
+
+        assert_eq!(by_tag.get(&"docs"), vec![0, 2]);
+        assert_eq!(by_tag.count(&"bug"), 1);
+        assert!(!by_tag.contains(&"feature"));
+        assert_eq!(by_tag.items(&"docs")[1].read().1, 3);
+        assert_eq!(derived.get(), 3);
+
+        // Only the pushed and changed items are derived again, and join their group last
+        tasks.push(("feature", 4));
+        tasks.set(0, ("bug", 1));
+        assert_eq!(by_tag.get(&"docs"), vec![2]);
+        assert_eq!(by_tag.get(&"bug"), vec![1, 0]);
+        assert_eq!(by_tag.get(&"feature"), vec![3]);
+        assert_eq!(by_tag.groups().len(), 3);
+        assert_eq!(derived.get(), 5);
+
+        // Writes through an item are followed too
+        tasks.get(&3).write().0 = "docs";
+        assert_eq!(by_tag.get(&"docs"), vec![2, 3]);
+        assert!(!by_tag.contains(&"feature"));
+
+        // Removing before the end shifts the keys, which rebuilds the index
+        tasks.remove(&0);
+        assert_eq!(by_tag.get(&"bug"), vec![0]);
+        assert_eq!(by_tag.get(&"docs"), vec![1, 2]);
+    });
+}
+
+#[test]
+fn test_ranked_by() {
+    test_outside_render!(|| {
+        let players =
+            CollectionStore::new(HashMap::from([("ada", 30), ("alan", 50), ("grace", 40)]));
+        let leaderboard = players.ranked_by(|points| *points);
+
+        assert_eq!(leaderboard.keys(), vec!["alan", "grace", "ada"]);
+        assert_eq!(leaderboard.top(1)[0].key(), "alan");
+        assert_eq!(leaderboard.rank_of(&"ada"), Some(2));
+
+        // Only the changed item moves, behind the items with the same score
+        players.set("ada", 50);
+        assert_eq!(leaderboard.keys(), vec!["alan", "ada", "grace"]);
+        players.insert("linus", 45);
+        players.remove(&"alan");
+        assert_eq!(leaderboard.keys(), vec!["ada", "linus", "grace"]);
+        assert_eq!(leaderboard.len(), 3);
+
+        // Readers aren't notified when the order stays the same
+        let calls = std::rc::Rc::new(std::cell::Cell::new(0));
+        let counter = calls.clone();
+        let first = Memo::new(move || {
+            counter.set(counter.get() + 1);
+            leaderboard.keys()[0]
+        });
+        assert_eq!(first(), "ada");
+        players.set("grace", 41);
+        assert_eq!(first(), "ada");
+        assert_eq!(calls.get(), 1);
+        players.set("grace", 60);
+        assert_eq!(leaderboard.rank_of(&"grace"), Some(0));
+    });
+}
+
+#[test]
+fn test_default_rows() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2]);
+        assert_eq!(store.push_default(), Ok(2));
+        assert_eq!(*store.items().read(), vec![1, 2, 0]);
+        assert_eq!(store.selected_key(), Some(2));
+
+        let store = store.with_placeholder(|| -1);
+        assert_eq!(store.push_default(), Ok(3));
+        assert_eq!(store.get(&3).cloned(), -1);
+
+        let scores = CollectionStore::new(HashMap::from([("ada".to_string(), 3)]));
+        assert_eq!(scores.insert_default("alan".to_string()), None);
+        assert_eq!(scores.get(&"alan".to_string()).cloned(), 0);
+        assert_eq!(scores.selected_key(), Some("alan".to_string()));
+    });
+}
+
+#[test]
+fn test_push_and_insert_return_item() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2]);
+        let item = store.push_and_get(3).unwrap();
+        assert_eq!(item.key(), 2);
+        assert_eq!(item.cloned(), 3);
+
+        let (item, previous) = store.insert_and_get(0, 10).unwrap();
+        assert_eq!((item.key(), previous), (0, Some(1)));
+        assert_eq!(
+            store.insert_and_get(9, 10).map(|(item, _)| item.key()),
+            Err(CollectionError::KeyNotFound)
+        );
+    });
+}
+
+#[test]
+fn test_selection_behavior() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a"]);
+        store.push("b");
+        assert_eq!(store.selected_key(), None);
+
+        store.set_selection_behavior(SelectionBehavior::SelectOnPush);
+        store.push("c");
+        assert_eq!(store.selected_key(), Some(2));
+        store.insert_before(&0, "z").unwrap();
+        assert_eq!(store.selected_key(), Some(3));
+
+        store.set_selection_behavior(SelectionBehavior::SelectOnInsert);
+        assert_eq!(store.duplicate(&0), Ok(1));
+        assert_eq!(store.selected_key(), Some(1));
+
+        let scores = CollectionStore::new(HashMap::from([("ada", 1)]))
+            .with_selection_behavior(SelectionBehavior::SelectOnInsert);
+        scores.insert("alan", 2);
+        assert_eq!(scores.selected_key(), Some("alan"));
+        // Updating an existing key is not an addition
+        scores.insert("ada", 3);
+        assert_eq!(scores.selected_key(), Some("alan"));
+    });
+}
+
+#[cfg(feature = "animations")]
+#[test]
+fn test_remove_with_exit() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c"]);
+        store
+            .remove_with_exit(&1, std::time::Duration::from_secs(1))
+            .unwrap();
+        assert!(store.get(&1).leaving());
+        assert!(!store.is_leaving(&0));
+        assert_eq!(
+            store.remove_with_exit(&5, std::time::Duration::ZERO),
+            Err(CollectionError::KeyNotFound)
+        );
+
+        // The pending removal follows its item and only removes it
+        let token = store.start_exit(&2).unwrap();
+        store.remove_many([0]);
+        store.finish_exit(token);
+        assert_eq!(*store.items().read(), vec!["b"]);
+        assert!(store.is_leaving(&0));
+
+        // A cancelled exit keeps the item
+        let token = store.start_exit(&0).unwrap();
+        store.cancel_exit(&0);
+        store.finish_exit(token);
+        assert_eq!(store.len(), 1);
+        assert!(!store.is_leaving(&0));
+    });
+}
+
+#[test]
+fn test_previous_positions() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![3, 1, 2]);
+        store.swap(&0, &2).unwrap();
+        assert_eq!(store.previous_index(&0), None);
+
+        store.enable_position_tracking();
+        store.sort_by(|a, b| a.cmp(b));
+        assert_eq!(*store.items().read(), vec![1, 2, 3]);
+        assert_eq!(store.get(&0).previous_index(), Some(1));
+        assert_eq!(store.get(&1).previous_index(), Some(0));
+        assert_eq!(store.get(&2).previous_index(), None);
+
+        // The next structural change replaces the previous positions
+        store.swap(&0, &1).unwrap();
+        assert_eq!(store.previous_index(&0), Some(1));
+        assert_eq!(store.previous_index(&2), None);
+
+        store.clear_previous_positions();
+        assert_eq!(store.previous_index(&0), None);
+        store.disable_position_tracking();
+        store.move_item(&0, &2).unwrap();
+        assert_eq!(store.previous_index(&2), None);
+    });
+}
+
+#[cfg(feature = "devtools")]
+#[test]
+fn test_debug_timeline() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2]);
+        assert!(store.debug_timeline().is_none());
+
+        store.start_recording();
+        store.push(3);
+        store.swap(&0, &2).unwrap();
+        store.remove(&1);
+
+        let mut timeline = store.debug_timeline().unwrap();
+        assert_eq!(timeline.len(), 3);
+        assert_eq!(timeline.position(), 3);
+        let entries = timeline.entries();
+        assert_eq!(entries[0].operation, CollectionOperation::Push { value: 3 });
+        assert!(entries.windows(2).all(|pair| pair[0].at <= pair[1].at));
+        assert_eq!(*timeline.state().unwrap().items().read(), vec![3, 1]);
+
+        assert!(timeline.step_back());
+        assert_eq!(*timeline.state().unwrap().items().read(), vec![3, 2, 1]);
+        timeline.go_to(0).unwrap();
+        assert!(!timeline.step_back());
+        assert_eq!(*timeline.state().unwrap().items().read(), vec![1, 2]);
+        assert!(timeline.step_forward());
+        assert_eq!(*timeline.state().unwrap().items().read(), vec![1, 2, 3]);
+        assert!(timeline.go_to(4).is_err());
+
+        // The timeline is a snapshot, the store itself is untouched
+        assert_eq!(*store.items().read(), vec![3, 1]);
+    });
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn test_tracing_events() {
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+
+    /// Collects the fields of the store events
+    #[derive(Clone, Default)]
+    struct Events(Arc<Mutex<Vec<String>>>);
+
+    struct Fields(Vec<String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() != "message" {
+                self.0.push(format!("{}={value:?}", field.name()));
+            }
+        }
+    }
+
+    impl tracing::Subscriber for Events {
+        fn enabled(&self, metadata: &tracing::Metadata<'_>) -> bool {
+            metadata.target() == "dioxus_collection_store"
+        }
+        fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            tracing::span::Id::from_u64(1)
+        }
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+        fn event(&self, event: &tracing::Event<'_>) {
+            let mut fields = Fields(Vec::new());
+            event.record(&mut fields);
+            self.0.lock().unwrap().push(fields.0.join(" "));
+        }
+        fn enter(&self, _: &tracing::span::Id) {}
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    let events = Events::default();
+    tracing::subscriber::with_default(events.clone(), || {
+        test_outside_render!(|| {
+            let store = CollectionStore::new(vec![1, 2, 3]);
+            store.push(4);
+            assert!(!store.is_tracing());
+
+            store.enable_tracing();
+            store.swap(&0, &2).unwrap();
+            store.remove_many([1, 3]);
+            store.clear();
+            store.disable_tracing();
+            store.push(5);
+        });
+    });
+
+    assert_eq!(
+        *events.0.lock().unwrap(),
+        vec![
+            r#"operation="swap" keys="0, 2" len_before=4 len=4"#,
+            r#"operation="remove_many" keys="1, 3" len_before=4 len=2"#,
+            r#"operation="clear" keys="" len_before=2 len=0"#,
+        ]
+    );
+}
+
+#[test]
+fn test_permission_guard() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        let guarded = store.guard(Permissions {
+            can_remove: false,
+            can_reorder: false,
+            ..Permissions::all()
+        });
+        assert_eq!(
+            guarded.remove(&0),
+            Err(CollectionError::PermissionDenied { action: "remove" })
+        );
+        assert!(guarded.clear().is_err());
+        assert!(guarded.pop().is_err());
+        assert!(guarded.swap(&0, &1).is_err());
+        assert!(guarded.sort_by(|a, b| b.cmp(a)).is_err());
+        guarded.push(4).unwrap();
+        guarded.set(0, 10).unwrap();
+        assert_eq!(*store.items().read(), vec![10, 2, 3, 4]);
+
+        // Replacing an existing key is an edit
+        guarded.set_permissions(Permissions {
+            can_edit: false,
+            ..Permissions::all()
+        });
+        assert!(!guarded.permissions().can_edit);
+        assert!(guarded.insert(0, 1).is_err());
+        assert_eq!(guarded.insert(4, 5), Ok(None));
+        assert_eq!(
+            guarded.extend([(5, 6), (0, 1)]),
+            Err(CollectionError::PermissionDenied { action: "edit" })
+        );
+        assert_eq!(store.len(), 5);
+
+        // Items read freely but write through the same permissions
+        let item = guarded.get(&0);
+        assert_eq!(*item.read(), 10);
+        assert_eq!(
+            item.set(1),
+            Err(CollectionError::PermissionDenied { action: "edit" })
+        );
+        assert!(guarded.iter().all(|item| item.set(0).is_err()));
+        assert_eq!(*store.items().read(), vec![10, 2, 3, 4, 5]);
+
+        guarded.set_permissions(Permissions::read_only());
+        assert!(guarded.push(6).is_err());
+        assert!(guarded.select(&1).is_ok());
+        // The store itself is not guarded
+        store.push(6);
+        assert_eq!(guarded.len(), 6);
+    });
+}
+
+#[test]
+fn test_permission_guard_per_handle() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        let viewer = store.guard(Permissions::read_only());
+        let editor = store.guard(Permissions::all());
+        assert_eq!(viewer.permissions(), Permissions::read_only());
+        assert_eq!(editor.permissions(), Permissions::all());
+
+        // Guarding again doesn't give the first handle more rights
+        assert_eq!(
+            viewer.push(4),
+            Err(CollectionError::PermissionDenied { action: "insert" })
+        );
+        assert!(viewer.set(0, 10).is_err());
+        editor.push(4).unwrap();
+        assert_eq!(store.len(), 4);
+
+        // Changing the permissions of a handle leaves the other one alone
+        editor.set_permissions(Permissions::read_only());
+        viewer.set_permissions(Permissions::all());
+        assert!(editor.remove(&0).is_err());
+        viewer.remove(&0).unwrap();
+        assert_eq!(*store.items().peek(), vec![2, 3, 4]);
+    });
+}
+
+#[test]
+fn test_lock() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        store.select(&2).unwrap();
+        store.lock();
+        assert!(store.is_locked());
+
+        store.push(4);
+        store.set(0, 10);
+        store.get(&1).set(20);
+        assert_eq!(store.remove(&0), None);
+        assert_eq!(store.pop(), None);
+        assert!(store.remove_many([0, 1]).is_empty());
+        store.clear();
+        assert_eq!(store.swap(&0, &1), Err(CollectionError::Locked));
+        assert_eq!(store.insert_after(&0, 5), Err(CollectionError::Locked));
+        assert!(store.push_and_get(4).is_err());
+        assert_eq!(
+            store.guard(Permissions::all()).remove(&0),
+            Err(CollectionError::Locked)
+        );
+        assert_eq!(*store.items().read(), vec![1, 2, 3]);
+
+        // The try_ variants report the rejected writes
+        let locked = Err(CollectionError::Locked);
+        assert_eq!(store.try_push(4), locked);
+        assert_eq!(store.try_set(0, 10), locked);
+        assert_eq!(store.try_insert(0, 10), Err(CollectionError::Locked));
+        assert_eq!(store.try_remove(&0), Err(CollectionError::Locked));
+        assert_eq!(store.try_pop(), Err(CollectionError::Locked));
+        assert_eq!(store.try_swap_remove(&0), Err(CollectionError::Locked));
+        assert_eq!(store.try_remove_many([0]), Err(CollectionError::Locked));
+        assert_eq!(store.try_retain(|_, _| false), locked);
+        assert_eq!(store.try_map_in_place(|_, value| value + 1), locked);
+        assert_eq!(store.try_for_each_mut(|_, value| *value += 1), locked);
+        assert_eq!(store.try_drain(), Err(CollectionError::Locked));
+        assert_eq!(store.try_clear(), locked);
+        assert_eq!(store.try_extend([(3, 4)]), locked);
+        assert_eq!(store.try_sort_by(|a, b| b.cmp(a)), locked);
+        assert_eq!(store.try_rotate_left(1), locked);
+        assert_eq!(store.try_rotate_right(1), locked);
+        assert_eq!(store.try_append(&CollectionStore::new(vec![4])), locked);
+        assert_eq!(store.get(&1).try_set(20), locked);
+        assert_eq!(store.get(&1).try_remove(), Err(CollectionError::Locked));
+        assert_eq!(*store.items().read(), vec![1, 2, 3]);
+
+        // Selection is not a mutation of the collection
+        store.select(&0).unwrap();
+
+        store.unlock();
+        store.push(4);
+        assert_eq!(store.try_set(0, 10), Ok(()));
+        assert_eq!(store.get(&1).try_remove(), Ok(Some(2)));
+        assert_eq!(*store.items().read(), vec![10, 3, 4]);
+    });
+}
+
+#[test]
+fn test_edit_session() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["milk".to_string(), "eggs".to_string()]);
+        let session = store.get(&1).begin_edit();
+        assert!(!session.is_dirty());
+
+        session.draft().set("free range eggs".to_string());
+        assert!(session.is_dirty());
+        assert_eq!(*store.get(&1).read(), "eggs");
+
+        session.cancel();
+        assert_eq!(*session.draft().read(), "eggs");
+        assert!(!session.is_dirty());
+
+        session.draft().set("6 eggs".to_string());
+        store.lock();
+        assert_eq!(session.commit(), Err(CollectionError::Locked));
+        store.unlock();
+        session.commit().unwrap();
+        assert_eq!(*store.get(&1).read(), "6 eggs");
+        assert!(!session.is_dirty());
+
+        store.remove(&1);
+        assert_eq!(session.commit(), Err(CollectionError::KeyNotFound));
+    });
+}
+
+#[test]
+fn test_select_by_option_value() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["same", "same", "other"]);
+        assert_eq!(store.selected_option_value(), "");
+
+        store
+            .select_by_option_value(&store.option_value(&1))
+            .unwrap();
+        assert_eq!(store.selected_key(), Some(1));
+        assert_eq!(store.selected_option_value(), "1");
+
+        assert_eq!(
+            store.select_by_option_value("nope"),
+            Err(CollectionError::KeyNotFound)
+        );
+        assert_eq!(
+            store.select_by_option_value("7"),
+            Err(CollectionError::KeyNotFound)
+        );
+        store.select_by_option_value("").unwrap();
+        assert_eq!(store.selected_key(), None);
+
+        let onchange = use_collection_select(store);
+        onchange.call("2".to_string());
+        assert_eq!(store.selected_key(), Some(2));
+    });
+}
+
+#[test]
+fn test_borrowed_lookup() {
+    use std::collections::BTreeMap;
+
+    test_outside_render!(|| {
+        let scores = CollectionStore::new(HashMap::from([("ada".to_string(), 36)]));
+        assert!(scores.contains_borrowed("ada"));
+        assert!(!scores.contains_borrowed("alan"));
+        assert_eq!(scores.map_borrowed("ada", |age| age + 1), Some(37));
+        assert_eq!(scores.map_borrowed("alan", |age| age + 1), None);
+
+        // Lookups, items, selection and removal take a borrowed key too
+        assert!(scores.contains_key("ada"));
+        assert!(!scores.contains_key("alan"));
+        assert_eq!(*scores.read("ada").read(), 36);
+        assert_eq!(*scores.get("ada").read(), 36);
+        assert_eq!(scores.select("alan"), Err(CollectionError::KeyNotFound));
+        assert_eq!(scores.remove("alan"), None);
+        scores.select("ada").unwrap();
+        assert_eq!(scores.selected_key().as_deref(), Some("ada"));
+        assert_eq!(scores.remove("ada"), Some(36));
+        assert!(scores.selected_key().is_none());
+
+        let paths = CollectionStore::new(BTreeMap::from([(vec!["src".to_string()], 1)]));
+        assert!(paths.contains_key(&["src".to_string()][..]));
+        assert_eq!(paths.remove(&["src".to_string()][..]), Some(1));
+
+        let list = CollectionStore::new(vec!["a", "b"]);
+        assert!(list.contains_key(&1));
+        assert!(list.contains_borrowed(&1));
+        assert_eq!(list.map_borrowed(&1, |value| value.len()), Some(1));
+    });
+}
+
+#[test]
+fn test_keys_memo() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2]);
+        let keys = store.keys_memo();
+        assert_eq!(*keys.read(), vec![0, 1]);
+
+        store.set(0, 10);
+        assert_eq!(*keys.read(), vec![0, 1]);
+
+        store.push(3);
+        assert_eq!(*keys.read(), vec![0, 1, 2]);
+        store.remove(&0);
+        assert_eq!(*keys.read(), vec![0, 1]);
+    });
+}
+
+#[test]
+fn test_peek_accessors() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        assert_eq!(store.peek_len(), 3);
+        assert!(store.peek_contains_key(&2));
+        assert!(!store.peek_contains_key(&3));
+        assert_eq!(store.peek_selected_key(), None);
+
+        store.select(&1).unwrap();
+        store.remove(&0);
+        assert_eq!(store.peek_len(), 2);
+        assert_eq!(store.peek_selected_key(), store.selected_key());
+    });
+}
+
+#[test]
+fn test_set_if_changed_and_dedup_writes() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        store.start_recording();
+
+        assert!(!store.get(&0).set_if_changed(1));
+        assert!(store.get(&0).set_if_changed(10));
+        assert_eq!(store.peek_value(&0), Some(10));
+
+        store.set(1, 2);
+        assert!(!store.is_deduping_writes());
+        store.dedup_writes(true);
+        assert!(store.is_deduping_writes());
+        store.set(1, 2);
+        store.set(2, 30);
+        assert_eq!(store.stop_recording().unwrap().operations.len(), 3);
+
+        store.lock();
+        assert!(!store.set_if_changed(0, 100));
+        assert_eq!(store.peek_value(&0), Some(10));
+    });
+}
+
+#[test]
+fn test_generations() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+        let untracked = store.get(&3);
+        store.track_generations(true);
+        assert!(store.is_tracking_generations());
+
+        let first = store.get(&0);
+        let second = store.get(&1);
+        let mut last = store.get(&3);
+        store.remove(&1);
+        assert!(!first.is_stale());
+        assert_eq!(*first.read(), "a");
+        assert!(second.is_stale());
+        assert_eq!(
+            second.try_read_value().err(),
+            Some(CollectionError::StaleHandle)
+        );
+        assert_eq!(second.select(), Err(CollectionError::StaleHandle));
+        assert_eq!(second.remove(), None);
+        second.set("x");
+        assert_eq!(store.peek_value(&1), Some("c"));
+        assert_eq!(
+            last.try_write_value().err(),
+            Some(CollectionError::StaleHandle)
+        );
+
+        // Fresh handles and in-place updates are fine
+        let fresh = store.get(&1);
+        fresh.set("y");
+        assert!(!fresh.is_stale());
+        assert_eq!(*fresh.try_read_value().unwrap(), "y");
+
+        // Handles created before tracking never go stale
+        assert!(!untracked.is_stale());
+
+        store.clear();
+        assert!(first.is_stale());
+        store.push("z");
+        assert!(first.is_stale());
+        assert!(!store.get(&0).is_stale());
+    });
+}
+
+#[test]
+#[should_panic(expected = "stale CollectionItem")]
+fn test_generations_stale_read_panics() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        store.track_generations(true);
+        let item = store.get(&0);
+        store.swap(&0, &2).unwrap();
+        let _ = *item.read();
+    });
+}
+
+#[test]
+fn test_weak_handles() {
+    test_outside_render!(|| {
+        let owner = <UnsyncStorage as dioxus_signals::AnyStorage>::owner();
+        let store = dioxus_core::with_owner(owner.clone(), || CollectionStore::new(vec![1, 2, 3]));
+        let weak_store = store.downgrade();
+        let weak_item = store.get(&2).downgrade();
+
+        weak_store.upgrade().unwrap().push(4);
+        assert_eq!(*weak_item.upgrade().unwrap().read(), 3);
+
+        store.track_generations(true);
+        let weak_first = store.get(&0).downgrade();
+        store.remove(&0);
+        assert!(weak_first.upgrade().is_none());
+        store.pop();
+        store.pop();
+        assert!(weak_item.upgrade().is_none());
+
+        drop(owner);
+        assert!(weak_store.upgrade().is_none());
+    });
+}
+
+#[test]
+fn test_store_ownership() {
+    test_outside_render!(|| {
+        let owner = <UnsyncStorage as dioxus_signals::AnyStorage>::owner();
+        let (scoped, global) = dioxus_core::with_owner(owner.clone(), || {
+            (
+                CollectionStore::new(vec![1, 2]),
+                CollectionStore::new_global(vec![1, 2]),
+            )
+        });
+        let in_root = CollectionStore::new_in_scope(vec![1], ScopeId::ROOT);
+
+        drop(owner);
+        assert!(scoped.downgrade().upgrade().is_none());
+        global.push(3);
+        assert_eq!(global.len(), 3);
+        assert_eq!(in_root.len(), 1);
+    });
+}
+
+#[cfg(feature = "toasts")]
+#[test]
+fn test_toast_collection() {
+    test_outside_render!(|| {
+        let toasts = CollectionStore::new(ToastCollection::new(2));
+        toasts
+            .push_with_ttl("saved", std::time::Duration::from_secs(1))
+            .unwrap();
+        toasts.push_toast("offline").unwrap();
+        toasts.meta(&1).insert("level", "warning");
+        toasts.select(&1).unwrap();
+
+        // A full queue drops the oldest toast, and the metadata and selection follow their toast
+        toasts.push_toast("online").unwrap();
+        assert_eq!(toasts.len(), 2);
+        assert_eq!(*toasts.get(&0).read(), "offline");
+        assert_eq!(toasts.meta(&0).get("level").as_deref(), Some("warning"));
+        assert_eq!(toasts.selected_key(), Some(0));
+
+        // Toasts expire by identifier, whatever their current position
+        toasts.remove(&0);
+        toasts.expire_toast(2);
+        assert!(toasts.is_empty());
+        toasts.expire_toast(0);
+
+        toasts.lock();
+        assert_eq!(toasts.push_toast("locked"), Err(CollectionError::Locked));
+    });
+}
+
+#[test]
+fn test_collection_context() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static SEEN_LEN: AtomicUsize = AtomicUsize::new(0);
+
+    fn app() -> Element {
+        use_hook(|| provide_collection(CollectionStore::new(vec![1, 2, 3])));
+        assert!(try_use_collection_context::<Vec<&str>>().is_none());
+        rsx! { Child {} }
+    }
+
+    #[component]
+    fn Child() -> Element {
+        let store = use_collection_context::<Vec<i32>>();
+        SEEN_LEN.store(store.len(), Ordering::SeqCst);
+        rsx! { div {} }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    assert_eq!(SEEN_LEN.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_render_items() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+    fn app() -> Element {
+        let store = use_hook(|| provide_collection(CollectionStore::new(vec![1, 2, 3])));
+        store.render_items(|item| {
+            RENDERS.fetch_add(1, Ordering::SeqCst);
+            rsx! { div { "{item.read()}" } }
+        })
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    assert_eq!(RENDERS.load(Ordering::SeqCst), 3);
+
+    dom.in_scope(ScopeId::APP, || {
+        let store = use_collection_context::<Vec<i32>>();
+        let element = store.render_items(|_| VNode::empty()).unwrap();
+        let dioxus_core::DynamicNode::Fragment(children) = &element.dynamic_nodes[0] else {
+            panic!("expected a fragment of items");
+        };
+        let keys: Vec<_> = children.iter().map(|child| child.key.clone()).collect();
+        assert_eq!(keys, [Some("0".into()), Some("1".into()), Some("2".into())]);
+
+        store.clear();
+        assert!(store.render_items(|_| VNode::empty()).is_ok());
+    });
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+}
+
+#[cfg(feature = "components")]
+#[test]
+fn test_components_render() {
+    use crate::components::{ItemEditor, ItemList, Selector};
+    use std::collections::BTreeMap;
+
+    fn app() -> Element {
+        let tasks = use_hook(|| {
+            let tasks = CollectionStore::new(vec!["write docs".to_string()]);
+            tasks.select(&0).unwrap();
+            tasks
+        });
+        let scores = use_hook(|| CollectionStore::new(BTreeMap::<String, i32>::new()));
+        rsx! {
+            Selector { collection: tasks, class: "picker" }
+            ItemList { collection: tasks, item_class: "row" }
+            ItemList { collection: scores, show_key_label: true }
+            ItemEditor { item: tasks.get(&0), class: "editor" }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+}
+
+#[cfg(feature = "components")]
+#[test]
+fn test_selected_editor() {
+    use crate::components::{ItemEditor, SelectedEditor};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static EDITORS: AtomicUsize = AtomicUsize::new(0);
+
+    fn app() -> Element {
+        let tasks = use_hook(|| provide_collection(CollectionStore::new(vec![1, 2])));
+        rsx! {
+            SelectedEditor {
+                collection: tasks,
+                render: move |item| {
+                    EDITORS.fetch_add(1, Ordering::SeqCst);
+                    rsx! { ItemEditor { item } }
+                },
+                fallback: rsx! { p { "Nothing selected" } },
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    assert_eq!(EDITORS.load(Ordering::SeqCst), 0);
+
+    let tasks = dom.in_scope(ScopeId::APP, use_collection_context::<Vec<i32>>);
+    dom.in_scope(ScopeId::APP, || tasks.select(&1).unwrap());
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(EDITORS.load(Ordering::SeqCst), 1);
+
+    // The selected item disappears behind the store's back: the fallback is rendered
+    dom.in_scope(ScopeId::APP, || {
+        tasks.items().write().pop();
+    });
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    assert_eq!(EDITORS.load(Ordering::SeqCst), 1);
+}
+
+#[cfg(feature = "im")]
+#[test]
+fn test_im_collections() {
+    test_outside_render!(|| {
+        let todos = CollectionStore::new(im::Vector::from(vec!["a", "b", "c"]));
+        let snapshot = todos.items().peek().clone();
+        todos.push("d");
+        todos.insert_before(&1, "z").unwrap();
+        todos.remove(&0);
+        assert_eq!(Collection::keys(&*todos.items().peek()), vec![0, 1, 2, 3]);
+        assert_eq!(*todos.get(&0).read(), "z");
+        assert_eq!(snapshot.len(), 3);
+
+        let users = CollectionStore::new(im::HashMap::<u32, &str>::new());
+        users.insert(1, "Ada");
+        users.set(1, "Grace");
+        assert_eq!(users.find_key(|name| *name == "Grace"), Some(1));
+
+        let scores = CollectionStore::new(im::OrdMap::<i32, i32>::from(vec![(2, 20), (1, 10)]));
+        assert_eq!(Collection::keys(&*scores.items().peek()), vec![1, 2]);
+        scores.remove(&1);
+        assert_eq!(scores.len(), 1);
+    });
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_smallvec_collection() {
+    test_outside_render!(|| {
+        let tags = CollectionStore::new(smallvec::SmallVec::<[&str; 2]>::new());
+        tags.push("a");
+        tags.push("b");
+        tags.push("c");
+        assert!(tags.items().peek().spilled());
+        tags.swap_remove(&0);
+        assert_eq!(*tags.get(&0).read(), "c");
+        assert_eq!(tags.len(), 2);
+    });
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn test_arrayvec_collection() {
+    test_outside_render!(|| {
+        let recent = CollectionStore::new(arrayvec::ArrayVec::<i32, 2>::new());
+        recent.push_within_capacity(1).unwrap();
+        recent.push_within_capacity(2).unwrap();
+        assert_eq!(
+            recent.push_within_capacity(3),
+            Err(CollectionError::CapacityExceeded { capacity: 2 })
+        );
+
+        // A plain push drops the first item instead
+        recent.push(3);
+        assert_eq!(recent.items().peek().as_slice(), &[2, 3]);
+    });
+}
+
+#[cfg(feature = "slotmap")]
+#[test]
+fn test_slotmap_collection() {
+    use slotmap::{DefaultKey, DenseSlotMap, SlotMap};
+
+    test_outside_render!(|| {
+        let users = CollectionStore::new(SlotMap::<DefaultKey, &str>::new());
+        users.set_selection_behavior(SelectionBehavior::SelectOnInsert);
+        let ada = users.insert_value("Ada").unwrap();
+        let alan = users.insert_value("Alan").unwrap();
+        assert_eq!(users.selected_key(), Some(alan));
+
+        // Keys stay valid when other items are removed, and removed keys are refused
+        users.remove(&ada);
+        assert_eq!(users.peek_value(&alan), Some("Alan"));
+        assert_eq!(users.insert(ada, "Grace"), None);
+        assert_eq!(users.len(), 1);
+
+        let dense = CollectionStore::new(DenseSlotMap::<DefaultKey, i32>::new());
+        let key = dense.insert_value(1).unwrap();
+        dense.set(key, 2);
+        assert_eq!(dense.peek_value(&key), Some(2));
+        dense.lock();
+        assert_eq!(dense.insert_value(3), Err(CollectionError::Locked));
+    });
+}
+
+#[test]
+#[cfg(feature = "text-lines")]
+fn test_text_lines() {
+    test_outside_render!(|| {
+        let buffer = CollectionStore::new(TextLines::from("fn main() {\n}"));
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(TextLines::from("").lines(), &[String::new()]);
+
+        // Pressing Enter on the current line moves the end of the line and the cursor to a new line
+        buffer.select(&0).unwrap();
+        assert_eq!(buffer.split_line(0, 11), Ok(1));
+        assert_eq!(buffer.selected_key(), Some(1));
+        buffer.set(1, "    println!();".to_string());
+        assert_eq!(buffer.join_lines(), "fn main() {\n    println!();\n}");
+
+        assert_eq!(buffer.insert_line(0, "// entry point"), Ok(0));
+        assert_eq!(buffer.selected_key(), Some(2));
+        assert_eq!(buffer.insert_line(4, ""), Ok(4));
+        assert_eq!(
+            buffer.insert_line(9, ""),
+            Err(CollectionError::OutOfBounds { index: 9, len: 5 })
+        );
+        assert_eq!(
+            buffer.split_line(1, 40),
+            Err(CollectionError::OutOfBounds { index: 40, len: 11 })
+        );
+        assert_eq!(buffer.len(), 5);
+    });
+}
+
+#[test]
+fn test_chunks_and_columns() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3, 4, 5]);
+        let keys = |group: &Vec<CollectionItem<Vec<i32>>>| -> Vec<usize> {
+            group.iter().map(|item| item.key).collect()
+        };
+
+        let rows: Vec<_> = store.chunks(2).map(|row| keys(&row)).collect();
+        assert_eq!(rows, vec![vec![0, 1], vec![2, 3], vec![4]]);
+        assert_eq!(store.chunks(5).count(), 1);
+
+        let columns: Vec<_> = store.columns(3).iter().map(keys).collect();
+        assert_eq!(columns, vec![vec![0, 3], vec![1, 4], vec![2]]);
+        assert_eq!(store.columns(7).len(), 7);
+
+        // Items in a group are regular handles
+        store.chunks(2).nth(1).unwrap()[0].set(30);
+        assert_eq!(store.peek_value(&2), Some(30));
+
+        let empty = CollectionStore::new(Vec::<i32>::new());
+        assert_eq!(empty.chunks(2).count(), 0);
+    });
+}
+
+#[test]
+fn test_iter_rev() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c"]);
+        let iter = store.iter();
+        assert_eq!(iter.len(), 3);
+
+        let newest: Vec<usize> = store.iter_rev().map(|item| item.key).collect();
+        assert_eq!(newest, vec![2, 1, 0]);
+
+        let mut items = store.iter();
+        assert_eq!(items.next_back().map(|item| item.key), Some(2));
+        assert_eq!(items.next().map(|item| item.key), Some(0));
+        assert_eq!(items.len(), 1);
+    });
+}
+
+#[test]
+fn test_item_position() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(std::collections::BTreeMap::from([
+            (10, "a"),
+            (20, "b"),
+            (30, "c"),
+        ]));
+        let first = store.get(&10);
+        let middle = store.get(&20);
+        let last = store.get(&30);
+        assert_eq!(middle.position(), Some(1));
+        assert!(first.is_first() && !first.is_last());
+        assert!(!middle.is_first() && !middle.is_last());
+        assert!(last.is_last());
+
+        store.remove(&10);
+        assert_eq!(middle.position(), Some(0));
+        assert!(middle.is_first());
+        assert_eq!(first.position(), None);
+        assert!(!first.is_first() && !first.is_last());
+    });
+}
+
+#[test]
+fn test_iter_pairs() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![3, 5, 4]);
+        let pairs: Vec<(usize, Option<usize>)> = store
+            .iter_pairs()
+            .map(|(item, next)| (item.key, next.map(|next| next.key)))
+            .collect();
+        assert_eq!(pairs, vec![(0, Some(1)), (1, Some(2)), (2, None)]);
+
+        let empty = CollectionStore::new(Vec::<i32>::new());
+        assert_eq!(empty.iter_pairs().count(), 0);
+    });
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn test_collection_stats() {
+    let now = std::time::Instant::now();
+    let mut stats = CollectionStats::default();
+    stats.record_change(&[0, 1], &[0, 1, 2, 3], true, now);
+    stats.record_change(&[0, 1, 2, 3], &[0], true, now);
+    assert_eq!((stats.len, stats.added, stats.removed), (1, 2, 3));
+
+    // Map keys are compared: replacing "a" with "c" is a removal and an addition
+    let mut stats = CollectionStats::default();
+    stats.record_change(&["a", "b"], &["b", "c"], false, now);
+    assert_eq!((stats.len, stats.added, stats.removed), (2, 1, 1));
+    assert_eq!(stats.last_mutation, Some(now));
+
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        let stats = store.stats();
+        store.push(4);
+        // Refreshed on the next frame only
+        assert_eq!(stats.peek().len, 3);
+        assert_eq!(stats.peek().added, 0);
+    });
+}
+
+#[test]
+fn test_command_log_replay() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(HashMap::from([("a", 1)]));
+        assert!(store.command_log().is_empty());
+
+        store.start_recording();
+        store.insert("b", 2);
+        store.select(&"b").unwrap();
+        store.remove(&"a");
+        let commands = store.command_log();
+        assert_eq!(commands.len(), 3);
+        assert!(store.is_recording());
+
+        let mirror = CollectionStore::new(HashMap::from([("a", 1)]));
+        mirror.replay(commands).unwrap();
+        assert_eq!(*mirror.items().peek(), HashMap::from([("b", 2)]));
+        assert_eq!(mirror.peek_selected_key(), Some("b"));
+
+        // A command that doesn't apply means the stores diverged
+        let diverged = vec![CollectionOperation::Select { key: "a" }];
+        assert_eq!(mirror.replay(diverged), Err(CollectionError::KeyNotFound));
+    });
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_json_import_export() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a".to_string(), "b".to_string()]);
+        store.select(&1).unwrap();
+        let json = store.export_json().unwrap();
+        assert_eq!(json, r#"["a","b"]"#);
+
+        store.import_json(r#"["c"]"#).unwrap();
+        assert_eq!(*store.items().peek(), vec!["c".to_string()]);
+        assert_eq!(store.peek_selected_key(), None);
+
+        // Invalid JSON leaves the collection untouched
+        assert!(matches!(
+            store.import_json("{"),
+            Err(CollectionError::Serialization { .. })
+        ));
+        assert_eq!(store.len(), 1);
+
+        let users = CollectionStore::new(HashMap::<String, u32>::new());
+        users.import_json(r#"{"ada": 36}"#).unwrap();
+        assert_eq!(users.peek_value(&"ada".to_string()), Some(36));
+    });
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn test_csv_import_export() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct User {
+        name: String,
+        age: u32,
+    }
+
+    impl RowCodec for User {
+        fn headers() -> Vec<String> {
+            vec!["name".to_string(), "age".to_string()]
+        }
+
+        fn to_row(&self) -> Vec<String> {
+            vec![self.name.clone(), self.age.to_string()]
+        }
+
+        fn from_row(row: &[String]) -> Result<Self, String> {
+            match row {
+                [name, age] => Ok(User {
+                    name: name.clone(),
+                    age: age.parse().map_err(|_| format!("invalid age: {age}"))?,
+                }),
+                _ => Err(format!("expected 2 fields, got {}", row.len())),
+            }
+        }
+    }
+
+    test_outside_render!(|| {
+        let users = CollectionStore::new(vec![User {
+            name: "Hopper, \"Grace\"".to_string(),
+            age: 85,
+        }]);
+        let csv = users.export_csv();
+        assert_eq!(csv, "name,age\r\n\"Hopper, \"\"Grace\"\"\",85\r\n");
+
+        users
+            .import_csv(&format!("{csv}Ada,36\n\n\"multi\nline\",1"))
+            .unwrap();
+        assert_eq!(users.len(), 3);
+        assert_eq!(users.peek_value(&0).unwrap().name, "Hopper, \"Grace\"");
+        assert_eq!(users.peek_value(&2).unwrap().name, "multi\nline");
+
+        assert_eq!(
+            users.import_csv("name,age\nAda,old\n"),
+            Err(CollectionError::Serialization {
+                reason: "row 1: invalid age: old".to_string()
+            })
+        );
+        assert_eq!(users.len(), 3);
+    });
+}
+
+#[cfg(feature = "clipboard")]
+#[test]
+fn test_clipboard_without_platform() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec![1, 2, 3]);
+        let format = |value: &i32| value.to_string();
+        // Nothing selected: the clipboard isn't touched
+        assert_eq!(
+            poll_once(store.copy_selection_to_clipboard(format)),
+            Some(Ok(0))
+        );
+
+        // Without a web or desktop renderer, the clipboard is unavailable
+        store.select(&1).unwrap();
+        assert!(matches!(
+            poll_once(store.copy_selection_to_clipboard(format)),
+            Some(Err(CollectionError::Clipboard { .. }))
+        ));
+        assert!(matches!(
+            poll_once(store.paste_from_clipboard(|line| line.parse().ok())),
+            Some(Err(CollectionError::Clipboard { .. }))
+        ));
+        assert_eq!(store.len(), 3);
+    });
+}
+
+#[test]
+#[cfg(feature = "table")]
+fn test_table_view() {
+    test_with_runtime!(|| {
+        let users = CollectionStore::new(vec![("alan", 41), ("ada", 36), ("grace", 85)]);
+        users.select(&0).unwrap();
+        let table = users.as_table(vec![
+            ColumnModel::new("Name", |(name, _): &(&str, u32)| name.to_string())
+                .sort_by(|a, b| a.0.cmp(b.0)),
+            ColumnModel::new("Age", |(_, age): &(&str, u32)| age.to_string()).width(80),
+        ]);
+        assert_eq!(table.column_count(), 2);
+        assert_eq!(table.header(1), Some("Age".to_string()));
+        assert!(table.is_sortable(0) && !table.is_sortable(1));
+        assert_eq!(*table.width(1).unwrap().peek(), Some(80));
+
+        // Sorting orders the rows, leaving the store and its selection untouched
+        table.toggle_sort(0).unwrap();
+        assert_eq!(table.sort_state(), Some((0, SortDirection::Ascending)));
+        let names: Vec<_> = table
+            .rows()
+            .map(|row| table.cell(&row, 0).unwrap())
+            .collect();
+        assert_eq!(names, vec!["ada", "alan", "grace"]);
+        assert_eq!(users.peek_value(&0), Some(("alan", 41)));
+        assert_eq!(users.selected_key(), Some(0));
+
+        table.toggle_sort(0).unwrap();
+        assert_eq!(table.sort_state(), Some((0, SortDirection::Descending)));
+        let first = table.rows().next().unwrap();
+        assert_eq!(table.cell(&first, 1), Some("85".to_string()));
+
+        assert!(matches!(
+            table.toggle_sort(1),
+            Err(CollectionError::InvalidAccess { .. })
+        ));
+        assert_eq!(
+            table.sort(2, SortDirection::Ascending),
+            Err(CollectionError::OutOfBounds { index: 2, len: 2 })
+        );
+    });
+}
+
+#[test]
+#[cfg(feature = "table")]
+fn test_table_multi_key_sort() {
+    test_with_runtime!(|| {
+        let tasks = CollectionStore::new(vec![("b", 2), ("a", 1), ("a", 2), ("b", 1)]);
+        tasks.select(&0).unwrap();
+        let table = tasks.as_table(vec![
+            ColumnModel::new("Name", |(name, _): &(&str, u32)| name.to_string())
+                .sort_by(|a, b| a.0.cmp(b.0)),
+            ColumnModel::new("Priority", |(_, priority): &(&str, u32)| {
+                priority.to_string()
+            })
+            .sort_by(|a, b| a.1.cmp(&b.1)),
+        ]);
+
+        let values = || -> Vec<(&str, u32)> { table.rows().map(|row| *row.peek()).collect() };
+
+        // Sorting by priority, then by name: equal names keep the priority order
+        table.toggle_sort(1).unwrap();
+        table.toggle_sort(0).unwrap();
+        assert_eq!(
+            table.sort_keys(),
+            vec![(0, SortDirection::Ascending), (1, SortDirection::Ascending)]
+        );
+        assert_eq!(values(), vec![("a", 1), ("a", 2), ("b", 1), ("b", 2)]);
+        assert_eq!(tasks.selected_key(), Some(0));
+
+        table.toggle_sort(1).unwrap();
+        table.toggle_sort(1).unwrap();
+        assert_eq!(table.sort_state(), Some((1, SortDirection::Descending)));
+        assert_eq!(values(), vec![("a", 2), ("b", 2), ("a", 1), ("b", 1)]);
+
+        // Added and edited items take their sorted place
+        tasks.push(("c", 3));
+        tasks.set(2, ("a", 0));
+        assert_eq!(
+            values(),
+            vec![("c", 3), ("b", 2), ("a", 1), ("b", 1), ("a", 0)]
+        );
+        assert_eq!(tasks.peek_value(&4), Some(("c", 3)));
+
+        table.clear_sort();
+        assert_eq!(table.sort_state(), None);
+        assert_eq!(
+            table.rows().map(|row| row.key).collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        // Keyed stores are sorted the same way
+        let scores = CollectionStore::new(HashMap::from([("ada", 3), ("alan", 1), ("grace", 2)]));
+        let table = scores.as_table(vec![
+            ColumnModel::new("Score", |score: &u32| score.to_string()).sort_by(|a, b| a.cmp(b)),
+        ]);
+        table.sort(0, SortDirection::Descending).unwrap();
+        let names: Vec<_> = table.rows().map(|row| row.key).collect();
+        assert_eq!(names, vec!["ada", "grace", "alan"]);
+    });
+}
+
+#[test]
+#[cfg(feature = "table")]
+fn test_table_filters() {
+    test_with_runtime!(|| {
+        let tasks = CollectionStore::new(vec![("Done", 3), ("todo", 1), ("done", 1), ("todo", 5)]);
+        let table = tasks.as_table(vec![
+            ColumnModel::new("Status", |(status, _): &(&str, u32)| status.to_string()),
+            ColumnModel::new("Priority", |(_, priority): &(&str, u32)| {
+                priority.to_string()
+            }),
+        ]);
+        let keys = |rows: Vec<CollectionItem<Vec<(&'static str, u32)>>>| -> Vec<usize> {
+            rows.iter().map(|row| row.key).collect()
+        };
+        assert_eq!(keys(table.filtered_rows()), vec![0, 1, 2, 3]);
+
+        table.set_query("status:done priority>2").unwrap();
+        assert_eq!(keys(table.filtered_rows()), vec![0]);
+
+        table.set_query("status=todo OR priority<=1").unwrap();
+        assert_eq!(table.filters().mode(), FilterMode::Any);
+        assert_eq!(keys(table.filtered_rows()), vec![1, 2, 3]);
+
+        // An invalid query leaves the filters untouched
+        assert!(table.set_query("owner:me").is_err());
+        assert!(table.set_query("priority>high").is_err());
+        assert_eq!(table.filters().filters().len(), 2);
+
+        table.clear_filters();
+        table.set_filter(1, FilterOp::AtLeast(3.0)).unwrap();
+        table.set_filter(1, FilterOp::GreaterThan(3.0)).unwrap();
+        assert_eq!(keys(table.filtered_rows()), vec![3]);
+        assert_eq!(
+            table.set_filter(2, FilterOp::Equals("x".to_string())),
+            Err(CollectionError::OutOfBounds { index: 2, len: 2 })
+        );
+        table.remove_filter(1);
+        assert!(table.filters().is_empty());
+    });
+}
+
+#[test]
+#[cfg(feature = "table")]
+fn test_table_groups() {
+    test_with_runtime!(|| {
+        let orders = CollectionStore::new(vec![("ada", 10), ("alan", 5), ("ada", 7), ("grace", 1)]);
+        let table = orders.as_table(vec![
+            ColumnModel::new("Customer", |(name, _): &(&str, u32)| name.to_string()),
+            ColumnModel::new("Total", |(_, total): &(&str, u32)| total.to_string()),
+        ]);
+        assert_eq!(table.groups().len(), 1);
+        assert_eq!(table.groups()[0].count(), 4);
+
+        table.group_by_column(0).unwrap();
+        let groups = table.groups();
+        let labels: Vec<&str> = groups.iter().map(|group| group.label()).collect();
+        assert_eq!(labels, vec!["ada", "alan", "grace"]);
+        assert_eq!(groups[0].count(), 2);
+        assert_eq!(groups[0].sum(1), 17.0);
+        assert_eq!(groups[0].sum(0), 0.0);
+
+        // Groups are expanded by default, and keep their state across reads
+        assert!(groups[0].is_expanded());
+        groups[0].toggle();
+        let groups = table.groups();
+        assert!(!groups[0].is_expanded());
+        assert!(groups[0].visible_rows().is_empty());
+        assert_eq!(groups[1].visible_rows().len(), 1);
+
+        // Grouping applies to the filtered rows
+        table.set_query("total>4").unwrap();
+        assert_eq!(table.groups().len(), 2);
+
+        assert_eq!(
+            table.group_by_column(3),
+            Err(CollectionError::OutOfBounds { index: 3, len: 2 })
+        );
+        table.ungroup();
+        assert_eq!(table.grouped_by(), None);
+    });
+}
+
+#[test]
+#[cfg(feature = "table")]
+fn test_table_pages_and_views() {
+    test_with_runtime!(|| {
+        let tasks = CollectionStore::new(vec![
+            ("todo", 3),
+            ("done", 1),
+            ("todo", 2),
+            ("todo", 5),
+            ("done", 4),
+        ]);
+        let table = tasks.as_table(vec![
+            ColumnModel::new("Status", |(status, _): &(&str, u32)| status.to_string()),
+            ColumnModel::new("Priority", |(_, priority): &(&str, u32)| {
+                priority.to_string()
+            })
+            .sort_by(|a, b| a.1.cmp(&b.1)),
+            ColumnModel::new("Owner", |_: &(&str, u32)| "me".to_string()),
+        ]);
+        let priorities = |rows: Vec<CollectionItem<Vec<(&'static str, u32)>>>| -> Vec<u32> {
+            rows.iter()
+                .map(|row| table.cell(row, 1).unwrap().parse().unwrap())
+                .collect()
+        };
+        assert_eq!(table.page_count(), 1);
+        assert_eq!(table.page_rows().len(), 5);
+
+        table.set_page_size(Some(2));
+        table.set_page(2);
+        assert_eq!(table.page_count(), 3);
+        assert_eq!(priorities(table.page_rows()), vec![4]);
+
+        // The page is clamped when filtering leaves fewer pages
+        table
+            .set_filter(0, FilterOp::Equals("todo".to_string()))
+            .unwrap();
+        assert_eq!(table.page(), 1);
+        assert_eq!(priorities(table.page_rows()), vec![5]);
+
+        table.sort(1, SortDirection::Descending).unwrap();
+        tasks.select(&1).unwrap();
+        let saved = table.save_view("Open tasks");
+        assert_eq!(saved.selected_key, Some(1));
+        assert_eq!(saved.page_size, Some(2));
+
+        table.clear_sort();
+        table.clear_filters();
+        table.set_page_size(None);
+        tasks.clear_selection();
+        tasks.sort_by(|a, b| a.1.cmp(&b.1));
+        assert_eq!(priorities(table.page_rows()), vec![1, 2, 3, 4, 5]);
+
+        table.load_view("Open tasks").unwrap();
+        assert_eq!(table.sort_state(), Some((1, SortDirection::Descending)));
+        assert_eq!(table.page(), 1);
+        assert_eq!(priorities(table.page_rows()), vec![2]);
+        assert_eq!(tasks.selected_key(), Some(1));
+
+        // Saving under the same name replaces the preset
+        table.set_page(0);
+        table.save_view("Open tasks");
+        table.save_view("All");
+        let names: Vec<String> = table.views().into_iter().map(|view| view.name).collect();
+        assert_eq!(names, vec!["Open tasks", "All"]);
+        assert_eq!(table.views()[0].page, 0);
+
+        table.remove_view("All");
+        assert_eq!(table.load_view("All"), Err(CollectionError::KeyNotFound));
+
+        // A preset referring to missing columns leaves the table untouched
+        let mut invalid = saved.clone();
+        invalid.grouping = Some(3);
+        assert_eq!(
+            table.apply_view(&invalid),
+            Err(CollectionError::OutOfBounds { index: 3, len: 3 })
+        );
+        assert_eq!(table.grouped_by(), None);
+        invalid.grouping = None;
+        invalid.sort = vec![(2, SortDirection::Ascending)];
+        assert!(table.apply_view(&invalid).is_err());
+        assert_eq!(table.sort_state(), Some((1, SortDirection::Descending)));
+    });
+}
+
+#[test]
+#[cfg(feature = "prefetch")]
+fn test_select_prefetch() {
+    use crate::SelectionPrefetch;
+
+    fn app() -> Element {
+        let store = use_hook(|| provide_collection(CollectionStore::new(vec![1, 2, 3])));
+        let prefetch = use_hook(|| {
+            // The first item never finishes loading
+            store.on_select_prefetch(|key| async move {
+                if key == 0 {
+                    std::future::pending::<()>().await;
+                }
+                key * 10
+            })
+        });
+        use_context_provider(|| prefetch);
+        rsx! { div {} }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let (store, prefetch) = dom.in_scope(ScopeId::APP, || {
+        (
+            use_collection_context::<Vec<i32>>(),
+            consume_context::<SelectionPrefetch<usize, usize>>(),
+        )
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(*prefetch.selected_detail().peek(), None);
+        assert!(!prefetch.is_loading());
+        store.select(&2).unwrap();
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(*prefetch.selected_detail().peek(), Some(20));
+        assert_eq!(prefetch.loaded_key(), Some(2));
+        store.select(&0).unwrap();
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(*prefetch.selected_detail().peek(), None);
+        assert!(prefetch.is_loading());
+        // The pending load is cancelled by the next selection
+        store.select(&1).unwrap();
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(*prefetch.selected_detail().peek(), Some(10));
+        assert!(!prefetch.is_loading());
+        prefetch.stop();
+        store.clear_selection();
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(*prefetch.selected_detail().peek(), Some(10));
+    });
+}
+
+#[test]
+fn test_selection_model() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+        let selection = store.selection();
+        assert!(selection.is_empty());
+        assert_eq!(selection.len(), 0);
+
+        selection.select(&1).unwrap();
+        assert_eq!(selection.key(), Some(1));
+        assert!(selection.contains(&1));
+        assert_eq!(selection.len(), 1);
+
+        selection.extend_to(&3).unwrap();
+        assert_eq!(selection.anchor(), Some(1));
+        assert_eq!(selection.keys(), vec![1, 2, 3]);
+        assert_eq!(selection.len(), 3);
+        assert!(selection.contains(&2));
+        let values: Vec<&str> = selection.iter().map(|item| *item.read()).collect();
+        assert_eq!(values, vec!["b", "c", "d"]);
+
+        selection.focus(&0).unwrap();
+        selection.commit_focus().unwrap();
+        assert_eq!(selection.keys(), vec![0]);
+        selection.clear();
+        assert!(selection.is_empty());
+
+        // Reading the single selection doesn't subscribe to the item values
+        let notified = Arc::new(AtomicUsize::new(0));
+        let context = dioxus_core::ReactiveContext::new_with_callback(
+            {
+                let notified = notified.clone();
+                move || {
+                    notified.fetch_add(1, Ordering::SeqCst);
+                }
+            },
+            dioxus_core::current_scope_id(),
+            std::panic::Location::caller(),
+        );
+        context.run_in(|| (selection.len(), selection.key()));
+        store.set(0, "z");
+        store.push("e");
+        assert_eq!(notified.load(Ordering::SeqCst), 0);
+        selection.select(&2).unwrap();
+        assert_eq!(notified.load(Ordering::SeqCst), 1);
+    });
+}
+
+#[cfg(feature = "router")]
+#[test]
+fn test_collection_url_sync() {
+    use dioxus_history::{History, MemoryHistory, provide_history_context};
+    use std::rc::Rc;
+
+    type Tasks = Vec<(&'static str, u32)>;
+
+    fn app() -> Element {
+        use_hook(|| {
+            provide_history_context(Rc::new(MemoryHistory::with_initial_path(
+                "/tasks?tasks.sort=-1&tasks.page=2&tab=all#top",
+            )))
+        });
+        let store = use_hook(|| {
+            provide_collection(CollectionStore::new(vec![
+                ("todo", 3),
+                ("done", 1),
+                ("todo", 2),
+                ("to do", 5),
+            ]))
+        });
+        let table = use_hook(|| {
+            provide_context(store.as_table(vec![
+                ColumnModel::new("Status", |(status, _): &(&str, u32)| status.to_string()),
+                ColumnModel::new("Priority", |(_, priority): &(&str, u32)| {
+                    priority.to_string()
+                })
+                .sort_by(|a, b| a.1.cmp(&b.1)),
+            ]))
+        });
+        use_hook(|| table.set_page_size(Some(1)));
+        use_collection_url_sync(table, "tasks.");
+        rsx! { div {} }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    run_tasks(&mut dom);
+    let (store, table, history) = dom.in_scope(ScopeId::APP, || {
+        (
+            use_collection_context::<Tasks>(),
+            consume_context::<TableView<Tasks>>(),
+            consume_context::<Rc<dyn History>>(),
+        )
+    });
+    dom.in_scope(ScopeId::APP, || {
+        // The URL state is applied on the first render
+        assert_eq!(table.sort_state(), Some((1, SortDirection::Descending)));
+        assert_eq!(*table.rows().next().unwrap().peek(), ("to do", 5));
+        assert_eq!(table.page(), 1);
+
+        table
+            .set_filter(0, FilterOp::Contains("to d".to_string()))
+            .unwrap();
+        store.select(&0).unwrap();
+    });
+    run_tasks(&mut dom);
+    // View changes replace the view parameters, keeping the others
+    assert_eq!(
+        history.current_route(),
+        "/tasks?tab=all&tasks.sort=-1&tasks.filter=0%3Ato%20d&tasks.page=2&tasks.selected=0#top"
+    );
+    assert!(!history.can_go_back());
+
+    // A navigation is applied when the component renders again
+    history.push("/tasks?tasks.sort=1&tasks.selected=2".to_string());
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(table.sort_state(), Some((1, SortDirection::Ascending)));
+        assert!(table.filters().is_empty());
+        assert_eq!(table.page(), 0);
+        assert_eq!(store.selected_key(), Some(2));
+        assert_eq!(store.peek_value(&2), Some(("todo", 2)));
+    });
+    assert_eq!(
+        history.current_route(),
+        "/tasks?tasks.sort=1&tasks.selected=2"
+    );
+
+    // Parameters that can't be applied are replaced with the view state
+    history.push("/tasks?tasks.sort=7".to_string());
+    dom.mark_dirty(ScopeId::APP);
+    dom.render_immediate(&mut dioxus_core::NoOpMutations);
+    run_tasks(&mut dom);
+    assert_eq!(
+        history.current_route(),
+        "/tasks?tasks.sort=1&tasks.selected=2"
+    );
+}
+
+#[test]
+fn test_on_selection_change() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec!["a", "b", "c", "d"]);
+        let mut changes = Signal::new(Vec::new());
+        store.select(&0).unwrap();
+        let subscription = store.on_selection_change(move |old, new| {
+            // The previous item is still there to save its draft
+            if let Some(old) = &old {
+                old.set("saved");
+            }
+            changes.push((old.map(|item| item.key()), new.map(|item| item.key())));
+        });
+
+        // Each change is seen when it happens
+        store.select(&2).unwrap();
+        assert_eq!(*changes.peek(), vec![(Some(0), Some(2))]);
+        assert_eq!(store.peek_value(&0), Some("saved"));
+        store.select(&3).unwrap();
+        store.select(&3).unwrap();
+        assert_eq!(changes.peek()[1..], [(Some(2), Some(3))]);
+
+        // The selected item moving to another key isn't a change
+        store.remove(&0);
+        assert_eq!(store.selected_key(), Some(2));
+        assert_eq!(changes.peek().len(), 2);
+
+        // Removing the selected item clears the selection before it is removed
+        assert_eq!(store.remove(&2), Some("saved"));
+        assert_eq!(changes.peek()[2..], [(Some(2), None)]);
+
+        store.select_range(&0, &1).unwrap();
+        store.clear_selection();
+        assert_eq!(changes.peek()[3..], [(None, Some(1)), (Some(1), None)]);
+
+        subscription.cancel();
+        store.select(&0).unwrap();
+        assert_eq!(changes.peek().len(), 5);
+    });
+}
+
+#[test]
+fn test_selection_guard() {
+    fn app() -> Element {
+        let store = use_hook(|| provide_collection(CollectionStore::new(vec!["a", "b", "c", "d"])));
+        let answer: Signal<Option<bool>> = use_context_provider(|| Signal::new(None));
+        use_hook(|| {
+            store.set_selection_guard(move |current, next| {
+                if current.is_none() {
+                    return SelectionDecision::Allow;
+                }
+                match next.key() {
+                    1 => SelectionDecision::Block,
+                    2 => SelectionDecision::Redirect(3),
+                    _ => {
+                        // Wait for the answer of the "dialog"
+                        let changed = crate::waker::ChangeWaker::default();
+                        let context = changed.reactive_context();
+                        SelectionDecision::confirm(async move {
+                            loop {
+                                match context.reset_and_run_in(|| *answer.read()) {
+                                    Some(true) => return SelectionDecision::Allow,
+                                    Some(false) => return SelectionDecision::Block,
+                                    None => changed.changed().await,
+                                }
+                            }
+                        })
+                    }
+                }
+            });
+        });
+        rsx! { div {} }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let (store, mut answer) = dom.in_scope(ScopeId::APP, || {
+        (
+            use_collection_context::<Vec<&str>>(),
+            consume_context::<Signal<Option<bool>>>(),
+        )
+    });
+    dom.in_scope(ScopeId::APP, || {
+        store.select(&3).unwrap();
+        assert_eq!(store.select(&1), Err(CollectionError::SelectionBlocked));
+        assert_eq!(store.select(&3), Ok(()));
+        store.select_unguarded(&1).unwrap();
+        store.select(&2).unwrap();
+        assert_eq!(store.peek_selected_key(), Some(3));
+
+        // The async decision keeps the selection until it completes
+        assert_eq!(store.select(&0), Err(CollectionError::SelectionPending));
+        assert_eq!(store.pending_selection(), Some(0));
+        assert_eq!(store.peek_selected_key(), Some(3));
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(store.peek_selected_key(), Some(3));
+        answer.set(Some(true));
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(store.pending_selection(), None);
+        assert_eq!(store.peek_selected_key(), Some(0));
+
+        answer.set(None);
+        store.select(&3).ok();
+        store.cancel_pending_selection();
+        assert_eq!(store.pending_selection(), None);
+        store.clear_selection_guard();
+        store.select(&1).unwrap();
+        assert_eq!(store.peek_selected_key(), Some(1));
+    });
+}
+
+#[test]
+#[cfg(feature = "selection-group")]
+fn test_selection_group() {
+    use crate::SelectionGroup;
+
+    fn app() -> Element {
+        use_hook(|| {
+            let todo = CollectionStore::new(vec!["a", "b"]);
+            let done = CollectionStore::new(vec!["c"]);
+            todo.select(&1).unwrap();
+            done.select(&0).unwrap();
+            provide_context(SelectionGroup::new([todo.selection(), done.selection()]))
+        });
+        rsx! { div {} }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let group = dom.in_scope(ScopeId::APP, consume_context::<SelectionGroup<Vec<&str>>>);
+    let [todo, done] = [0, 1].map(|index| group.members()[index].store());
+    dom.in_scope(ScopeId::APP, || {
+        // The first member keeps its selection
+        assert_eq!(group.active_index(), Some(0));
+        assert_eq!(done.peek_selected_key(), None);
+
+        group.select(1, &0).unwrap();
+        assert_eq!(todo.peek_selected_key(), None);
+        assert_eq!(*group.active_item().unwrap().read(), "c");
+        assert_eq!(
+            group.select(2, &0),
+            Err(CollectionError::OutOfBounds { index: 2, len: 2 })
+        );
+
+        // Selecting through a store is enforced by the group's task
+        todo.select(&0).unwrap();
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(done.peek_selected_key(), None);
+        assert_eq!(group.active_store(), Some(todo));
+        todo.clear_selection();
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert_eq!(group.active_index(), None);
+        assert!(group.active_item().is_none());
+    });
+}
+
+#[test]
+fn test_newtype_keys() {
+    // No Display, Debug or From<usize>: only what HashMap needs
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    struct TaskId(u32);
+
+    test_outside_render!(|| {
+        let tasks = CollectionStore::new(HashMap::from([(TaskId(1), "write docs")]));
+        tasks.insert(TaskId(2), "release");
+        tasks.select(&TaskId(2)).unwrap();
+        assert!(tasks.selected_key() == Some(TaskId(2)));
+        assert_eq!(tasks.selection().len(), 1);
+        assert_eq!(tasks.iter().count(), 2);
+        assert!(tasks.get(&TaskId(2)).is_selected());
+        assert_eq!(tasks.remove_many([TaskId(1)]), vec!["write docs"]);
+        assert!(
+            tasks
+                .render_items_keyed(|id| id.0.to_string(), |_| VNode::empty())
+                .is_ok()
+        );
+
+        // Sequential collections find their first and last keys without converting positions
+        let list = CollectionStore::new(vec!["a", "b", "c"]);
+        assert_eq!(*list.first().unwrap().read(), "a");
+        assert_eq!(*list.last().unwrap().read(), "c");
+        list.clear();
+        assert!(list.first().is_none() && list.last().is_none());
+    });
+}
+
+#[test]
+fn test_btreemap_ordered_queries() {
+    use std::collections::BTreeMap;
+
+    test_outside_render!(|| {
+        let store = CollectionStore::new(BTreeMap::from([(30, "c"), (10, "a"), (20, "b")]));
+        assert_eq!(store.first_item().map(|item| item.key()), Some(10));
+        assert_eq!(store.last_item().map(|item| item.key()), Some(30));
+
+        let values: Vec<&str> = store.range(15..).map(|item| *item.read()).collect();
+        assert_eq!(values, vec!["b", "c"]);
+        let keys: Vec<i32> = store.range(..=20).rev().map(|item| item.key()).collect();
+        assert_eq!(keys, vec![20, 10]);
+        assert_eq!(store.range(11..20).len(), 0);
+
+        store.insert(5, "z");
+        assert_eq!(*store.first_item().unwrap().read(), "z");
+        store.clear();
+        assert!(store.first_item().is_none() && store.last_item().is_none());
+    });
+}
+
+#[test]
+fn test_btreemap_pop_first_last() {
+    use std::collections::BTreeMap;
+
+    test_outside_render!(|| {
+        let jobs = CollectionStore::new(BTreeMap::from([(3, "c"), (1, "a"), (2, "b")]));
+        jobs.select(&1).unwrap();
+        assert_eq!(jobs.pop_first(), Some((1, "a")));
+        assert_eq!(jobs.selected_key(), None);
+
+        jobs.select(&2).unwrap();
+        assert_eq!(jobs.pop_last(), Some((3, "c")));
+        assert_eq!(jobs.selected_key(), Some(2));
+
+        jobs.lock();
+        assert_eq!(jobs.pop_first(), None);
+        jobs.unlock();
+        assert_eq!(jobs.pop_first(), Some((2, "b")));
+        assert_eq!(jobs.pop_last(), None);
+    });
+}
+
+#[test]
+fn test_btreeset_ordered_queries() {
+    use std::collections::BTreeSet;
+
+    test_outside_render!(|| {
+        let store = CollectionStore::new(BTreeSet::from([3, 1, 4, 2]));
+        assert_eq!(store.first_item().map(|item| item.key()), Some(1));
+        assert_eq!(store.last_item().map(|item| item.key()), Some(4));
+        let keys: Vec<i32> = store.range(2..4).map(|item| item.key()).collect();
+        assert_eq!(keys, vec![2, 3]);
+        assert_eq!(store.range(..).next_back().map(|item| item.key()), Some(4));
+
+        assert_eq!(store.insert(0, ()), None);
+        assert_eq!(store.insert(0, ()), Some(()));
+        assert_eq!(store.first_item().map(|item| item.key()), Some(0));
+        assert_eq!(store.get(&3).position(), Some(3));
+        assert!(store.contains_key(&2));
+
+        let names = CollectionStore::new(BTreeSet::from(["bob".to_string()]));
+        assert!(names.contains_key("bob"));
+        assert!(names.remove("bob").is_some());
+        assert!(names.is_empty());
+    });
+}
+
+#[test]
+fn test_btreeset_pop_first_last() {
+    use std::collections::BTreeSet;
+
+    test_outside_render!(|| {
+        let jobs = CollectionStore::new(BTreeSet::from([3, 1, 2]));
+        jobs.select(&1).unwrap();
+        assert_eq!(jobs.pop_first(), Some(1));
+        assert_eq!(jobs.selected_key(), None);
+
+        jobs.select(&2).unwrap();
+        assert_eq!(jobs.pop_last(), Some(3));
+        assert_eq!(jobs.selected_key(), Some(2));
+
+        jobs.lock();
+        assert_eq!(jobs.pop_first(), None);
+        jobs.unlock();
+        assert_eq!(jobs.pop_first(), Some(2));
+        assert_eq!(jobs.pop_last(), None);
+    });
+}
+
+#[test]
+fn test_iter_sorted() {
+    test_with_runtime!(|| {
+        let store = CollectionStore::new(HashMap::from([(3, "carol"), (1, "bob"), (2, "ada")]));
+        let keys: Vec<i32> = store.iter_sorted_by_key().map(|item| item.key()).collect();
+        assert_eq!(keys, vec![1, 2, 3]);
+        let names: Vec<&str> = store
+            .iter_sorted_by(|a, b| a.cmp(b))
+            .rev()
+            .map(|item| *item.read())
+            .collect();
+        assert_eq!(names, vec!["carol", "bob", "ada"]);
+
+        // The memo keeps the order, and is refreshed when it changes
+        let order = store.sorted_keys_memo(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+        assert_eq!(*order.read(), vec![2, 1, 3]);
+        store.insert(4, "al");
+        assert_eq!(*order.read(), vec![4, 2, 1, 3]);
+    });
+}
+
+#[test]
+fn test_rename_key() {
+    test_outside_render!(|| {
+        let files = CollectionStore::new(HashMap::from([("a.txt", 1), ("b.txt", 2)]));
+        files.track_generations(true);
+        let old_handle = files.get(&"a.txt");
+        files.select(&"a.txt").unwrap();
+        files.focus(&"a.txt").unwrap();
+        files.check(&"a.txt").unwrap();
+        files.start_recording();
+
+        files.rename_key(&"a.txt", "c.txt").unwrap();
+        assert_eq!(files.peek_value(&"c.txt"), Some(1));
+        assert!(!files.contains_key(&"a.txt"));
+        assert_eq!(files.selected_key(), Some("c.txt"));
+        assert_eq!(files.focused_key(), Some("c.txt"));
+        assert!(files.is_checked(&"c.txt"));
+        assert!(old_handle.is_stale());
+        assert_eq!(
+            files.command_log(),
+            vec![CollectionOperation::RenameKey {
+                from: "a.txt",
+                to: "c.txt"
+            }]
+        );
+
+        assert_eq!(
+            files.rename_key(&"c.txt", "b.txt"),
+            Err(CollectionError::DuplicateKey)
+        );
+        assert_eq!(
+            files.rename_key(&"a.txt", "d.txt"),
+            Err(CollectionError::KeyNotFound)
+        );
+        assert_eq!(files.rename_key(&"c.txt", "c.txt"), Ok(()));
+
+        let list = CollectionStore::new(vec![1, 2]);
+        assert!(matches!(
+            list.rename_key(&0, 5),
+            Err(CollectionError::InvalidAccess { .. })
+        ));
+    });
+}
+
+#[test]
+fn test_merge() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct Task {
+        title: String,
+        done: bool,
+    }
+
+    #[derive(Default)]
+    struct TaskPatch {
+        title: Option<String>,
+        done: Option<bool>,
+    }
+
+    impl Merge for Task {
+        type Patch = TaskPatch;
+
+        fn merge(&mut self, patch: TaskPatch) {
+            if let Some(title) = patch.title {
+                self.title = title;
+            }
+            if let Some(done) = patch.done {
+                self.done = done;
+            }
+        }
+    }
+
+    test_outside_render!(|| {
+        let tasks = CollectionStore::new(HashMap::from([(
+            1,
+            Task {
+                title: "edited locally".to_string(),
+                done: false,
+            },
+        )]));
+        tasks.start_recording();
+        let patch = TaskPatch {
+            done: Some(true),
+            ..Default::default()
+        };
+        tasks.merge(1, patch).unwrap();
+        let expected = Task {
+            title: "edited locally".to_string(),
+            done: true,
+        };
+        assert_eq!(tasks.peek_value(&1), Some(expected.clone()));
+        assert_eq!(
+            tasks.command_log(),
+            vec![CollectionOperation::Set {
+                key: 1,
+                value: expected
+            }]
+        );
+        assert_eq!(
+            tasks.merge(2, TaskPatch::default()),
+            Err(CollectionError::KeyNotFound)
+        );
+    });
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive_merge() {
+    #[derive(Debug, Clone, PartialEq, Merge)]
+    #[merge(derive(Debug, PartialEq))]
+    struct Address {
+        city: String,
+        zip: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Merge)]
+    #[merge(derive(Debug, PartialEq))]
+    struct User {
+        name: String,
+        #[merge(nested)]
+        address: Address,
+    }
+
+    test_outside_render!(|| {
+        let users = CollectionStore::new(vec![User {
+            name: "Ada".to_string(),
+            address: Address {
+                city: "London".to_string(),
+                zip: "W1".to_string(),
+            },
+        }]);
+        let patch = UserPatch {
+            address: Some(AddressPatch {
+                zip: Some("NW1".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(patch.clone(), patch);
+        users.merge(0, patch).unwrap();
+        let user = users.peek_value(&0).unwrap();
+        assert_eq!(user.name, "Ada");
+        assert_eq!(user.address.city, "London");
+        assert_eq!(user.address.zip, "NW1");
+    });
+}
+
+#[cfg(feature = "crdt")]
+#[test]
+fn test_lww_map_merge_remote_state() {
+    test_outside_render!(|| {
+        let laptop = CollectionStore::new(LwwMap::new(1));
+        let phone = CollectionStore::new(LwwMap::new(2));
+        laptop.insert("title".to_string(), "Draft".to_string());
+        laptop.insert("tags".to_string(), "work".to_string());
+        phone
+            .merge_remote_state(&laptop.state_bytes().unwrap())
+            .unwrap();
+        assert_eq!(
+            phone.items().peek().keys(),
+            vec!["tags".to_string(), "title".to_string()]
+        );
+
+        // Concurrent edits: the phone renames, the laptop removes the tags
+        phone.select(&"title".to_string()).unwrap();
+        phone.set("title".to_string(), "Final".to_string());
+        laptop.remove(&"tags".to_string());
+        phone.select(&"tags".to_string()).unwrap();
+        phone.focus(&"tags".to_string()).unwrap();
+        phone.meta(&"tags".to_string()).set_flag("editing", true);
+        phone.meta(&"title".to_string()).set_flag("editing", true);
+
+        phone.start_recording();
+        let laptop_state = laptop.state_bytes().unwrap();
+        phone.merge_remote_state(&laptop_state).unwrap();
+        assert_eq!(phone.len(), 1);
+        assert_eq!(phone.focused_key(), None);
+        assert!(!phone.meta(&"tags".to_string()).flag("editing"));
+        assert!(phone.meta(&"title".to_string()).flag("editing"));
+        assert_eq!(
+            phone.command_log(),
+            vec![CollectionOperation::Remove {
+                key: "tags".to_string()
+            }]
+        );
+        assert_eq!(phone.selected_key(), None);
+        laptop
+            .merge_remote_state(&phone.state_bytes().unwrap())
+            .unwrap();
+        assert_eq!(laptop.items().peek().keys(), vec!["title".to_string()]);
+        assert_eq!(
+            laptop.peek_value(&"title".to_string()),
+            Some("Final".to_string())
+        );
+        assert_eq!(laptop.items().peek().keys(), phone.items().peek().keys());
+
+        // Merging again changes nothing
+        phone.merge_remote_state(&laptop_state).unwrap();
+        assert_eq!(phone.command_log().len(), 1);
+        assert_eq!(phone.items().peek().tombstones(), 1);
+        assert!(matches!(
+            phone.merge_remote_state(b"not json"),
+            Err(CollectionError::Serialization { .. })
+        ));
+    });
+}
+
+#[test]
+#[cfg(feature = "offline")]
+fn test_offline_queue() {
+    test_outside_render!(|| {
+        let store = CollectionStore::new(vec![1, 2]);
+        store.enable_offline_queue();
+        store.push(3);
+        assert!(store.is_online());
+        assert_eq!(store.pending_count(), 0);
+
+        store.set_online(false);
+        store.push(4);
+        store.select(&0).unwrap();
+        store.set(0, 10);
+        assert!(!store.is_online());
+        assert_eq!(
+            store.pending_ops(),
+            vec![
+                CollectionOperation::Push { value: 4 },
+                CollectionOperation::Set { key: 0, value: 10 },
+            ]
+        );
+
+        // A failed flush keeps the operations, ahead of the ones queued meanwhile
+        let result = poll_once(store.flush(|ops| async move {
+            assert_eq!(ops.len(), 2);
+            store.push(5);
+            Err("offline")
+        }))
+        .expect("the flush should be ready");
+        assert_eq!(result, Err("offline"));
+        assert_eq!(store.pending_count(), 3);
+        assert_eq!(
+            store.pending_ops()[2],
+            CollectionOperation::Push { value: 5 }
+        );
+
+        store.set_online(true);
+        let mut sent = Vec::new();
+        let result: Result<(), ()> = poll_once(store.flush(|ops| {
+            sent = ops;
+            async { Ok(()) }
+        }))
+        .expect("the flush should be ready");
+        assert_eq!(result, Ok(()));
+        assert_eq!(sent.len(), 3);
+        assert_eq!(store.pending_count(), 0);
+        assert_eq!(store.len(), 5);
+    });
+}
+
+#[test]
+fn test_item_actions() {
+    fn app() -> Element {
+        use_hook(|| provide_collection(CollectionStore::new(vec!["", "hello", "world"])));
+        rsx! { div {} }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let (store, pending) = dom.in_scope(ScopeId::APP, || {
+        let store = use_collection_context::<Vec<&str>>();
+        let send = |mail: &'static str| async move {
+            if mail.is_empty() {
+                return Err("empty mail");
+            }
+            Ok(())
+        };
+        store.get(&0).run_action(send).unwrap();
+        store.get(&1).run_action(send).unwrap();
+        let pending = store
+            .get(&2)
+            .run_action(|_| std::future::pending::<Result<(), String>>())
+            .unwrap();
+        assert!(store.get(&0).is_running());
+        assert!(matches!(
+            store.run_action(&3, send),
+            Err(CollectionError::KeyNotFound)
+        ));
+        // The running status follows the item to its new key
+        store.remove(&1);
+        (store, pending)
+    });
+    run_tasks(&mut dom);
+    dom.in_scope(ScopeId::APP, || {
+        assert!(!store.get(&0).is_running());
+        assert_eq!(store.get(&0).last_error(), Some("empty mail".to_string()));
+        assert!(store.get(&1).is_running());
+        assert!(pending.is_running());
+        pending.cancel();
+        assert!(!pending.is_running());
+        assert!(!store.get(&1).is_running());
+        assert_eq!(store.get(&1).last_error(), None);
+    });
+}
+
+#[cfg(feature = "ttl")]
+#[test]
+fn test_insert_with_ttl() {
+    use std::time::Duration;
+
+    test_outside_render!(|| {
+        let banners = CollectionStore::new(vec!["welcome"]);
+        banners
+            .insert_with_ttl(1, "maintenance", Duration::ZERO)
+            .unwrap();
+        banners
+            .insert_with_ttl(2, "sale", Duration::from_secs(60))
+            .unwrap();
+        let remaining = banners.get(&2).remaining().unwrap();
+        assert!(remaining > Duration::from_secs(50) && remaining <= Duration::from_secs(60));
+        assert_eq!(banners.get(&0).expires_at(), None);
+        // A single sweep waits for the earliest deadline
+        let scheduled = banners
+            .inner
+            .ttl_sweep()
+            .peek()
+            .as_ref()
+            .map(|sweep| sweep.deadline);
+        assert_eq!(scheduled, banners.expires_at(&1));
+        assert_eq!(
+            banners.insert_with_ttl(9, "nope", Duration::ZERO),
+            Err(CollectionError::KeyNotFound)
+        );
+
+        // Expired items are removed, the deadlines follow the remaining items
+        assert_eq!(banners.sweep_expired(), vec!["maintenance"]);
+        assert_eq!(*banners.items().read(), vec!["welcome", "sale"]);
+        assert!(banners.remaining(&1).is_some());
+
+        banners.lock();
+        assert_eq!(
+            banners.set_ttl(&0, Duration::ZERO),
+            Err(CollectionError::Locked)
+        );
+        banners.unlock();
+        banners.set_ttl(&0, Duration::ZERO).unwrap();
+        banners.clear_ttl(&0);
+        assert!(banners.sweep_expired().is_empty());
+        assert_eq!(banners.len(), 2);
+    });
+}
+
+#[test]
+fn test_refresh_from() {
+    test_outside_render!(|| {
+        let prices = CollectionStore::new(std::collections::BTreeMap::from([
+            ("btc", 60_000),
+            ("eth", 3_000),
+            ("doge", 1),
+        ]));
+        prices.select(&"eth").unwrap();
+        prices.meta(&"btc").insert("alert", "above 55k");
+        prices.start_recording();
+        prices
+            .refresh_from(std::collections::BTreeMap::from([
+                ("btc", 60_000),
+                ("eth", 3_100),
+                ("sol", 150),
+            ]))
+            .unwrap();
+        assert_eq!(
+            prices.command_log(),
+            vec![
+                CollectionOperation::RemoveMany { keys: vec!["doge"] },
+                CollectionOperation::Set {
+                    key: "eth",
+                    value: 3_100
+                },
+                CollectionOperation::Insert {
+                    key: "sol",
+                    value: 150
+                },
+            ]
+        );
+        assert_eq!(prices.selected_key(), Some("eth"));
+        assert_eq!(
+            prices.meta(&"btc").get("alert").as_deref(),
+            Some("above 55k")
+        );
+
+        // Sequential collections are diffed by position
+        let lines = CollectionStore::new(vec!["a", "b", "c"]);
+        lines.select(&1).unwrap();
+        lines.refresh_from(vec!["a", "B"]).unwrap();
+        assert_eq!(*lines.items().read(), vec!["a", "B"]);
+        assert_eq!(lines.selected_key(), Some(1));
+        lines.refresh_from(vec!["a", "B", "c", "d"]).unwrap();
+        assert_eq!(*lines.items().read(), vec!["a", "B", "c", "d"]);
+
+        lines.lock();
+        assert_eq!(lines.refresh_from(Vec::new()), Err(CollectionError::Locked));
+    });
+}
+
+#[cfg(feature = "polling")]
+#[test]
+fn test_use_polling_collection() {
+    use std::time::Duration;
+
+    fn app() -> Element {
+        let store = use_polling_collection(Duration::from_secs(60), || async {
+            Some(HashMap::from([(1, "fetched")]))
+        });
+        use_hook(|| provide_collection(store));
+        rsx! { div {} }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    dom.rebuild_in_place();
+    let _ = poll_once(dom.wait_for_work());
+    dom.in_scope(ScopeId::APP, || {
+        let store = consume_context::<CollectionStore<HashMap<i32, &str>>>();
+        assert_eq!(store.peek_value(&1), Some("fetched"));
+    });
+}
+
+#[test]
+fn test_reconcile() {
+    #[derive(Debug, Clone, PartialEq)]
+    struct User {
+        id: u32,
+        name: &'static str,
+    }
+
+    test_outside_render!(|| {
+        let users = CollectionStore::new(HashMap::from([
+            (1, User { id: 1, name: "Ada" }),
+            (
+                2,
+                User {
+                    id: 2,
+                    name: "Alan",
+                },
+            ),
+            (
+                3,
+                User {
+                    id: 3,
+                    name: "Grace",
+                },
+            ),
+        ]));
+        users.select(&1).unwrap();
+        users.focus(&3).unwrap();
+        users.meta(&1).insert("draft", "hi");
+        users.start_recording();
+        let fetched = vec![
+            User { id: 1, name: "Ada" },
+            User {
+                id: 3,
+                name: "Grace H.",
+            },
+            User {
+                id: 4,
+                name: "Edsger",
+            },
+        ];
+        users.reconcile(fetched, |user| user.id).unwrap();
+        assert_eq!(users.len(), 3);
+        assert_eq!(users.peek_value(&3).unwrap().name, "Grace H.");
+        assert_eq!(users.selected_key(), Some(1));
+        assert_eq!(users.focused_key(), Some(3));
+        assert_eq!(users.meta(&1).get("draft").as_deref(), Some("hi"));
+        let log = users.command_log();
+        assert_eq!(log[0], CollectionOperation::RemoveMany { keys: vec![2] });
+        assert_eq!(log.len(), 3);
+
+        // Reconciling identical items changes nothing
+        let same: Vec<User> = users.items().peek().values().cloned().collect();
+        users.reconcile(same, |user| user.id).unwrap();
+        assert_eq!(users.command_log().len(), 3);
+
+        let list = CollectionStore::new(vec![1, 2]);
+        assert!(matches!(
+            list.reconcile(vec![3], |value| *value),
+            Err(CollectionError::InvalidAccess { .. })
+        ));
+    });
+}
+
+#[test]
+fn test_access_tracking() {
+    test_outside_render!(|| {
+        let recent = CollectionStore::new(vec!["a", "b", "c"]);
+        recent.touch(&0);
+        assert!(recent.recently_used(3).is_empty());
+
+        recent.track_access(true);
+        recent.touch(&2);
+        recent.select(&0).unwrap();
+        // Reading doesn't touch
+        let _ = recent.read(&2);
+        recent.touch(&1);
+        let keys = |store: CollectionStore<Vec<&'static str>>| -> Vec<usize> {
+            store
+                .recently_used(10)
+                .iter()
+                .map(|item| item.key())
+                .collect()
+        };
+        assert_eq!(keys(recent), vec![1, 0, 2]);
+        assert_eq!(recent.recently_used(1).len(), 1);
+
+        // Bounded stores evict the least recently used items, which follow their keys
+        recent.set_lru_capacity(Some(3));
+        recent.push("d");
+        assert_eq!(*recent.items().read(), vec!["a", "b", "d"]);
+        assert_eq!(keys(recent), vec![2, 1, 0]);
+        recent.set_lru_capacity(Some(1));
+        assert_eq!(*recent.items().read(), vec!["d"]);
+
+        recent.track_access(false);
+        assert!(recent.recently_used(3).is_empty());
+        assert!(!recent.has_meta());
+    });
+}
@@ -0,0 +1,241 @@
+//! Line-oriented collection for text buffers
+//!
+//! `TextLines` holds a text as one `String` per line, so a simple editor renders each line as an item
+//! and uses the selection as the current line. Editing one line only re-renders that line, and
+//! `split_line` / `join_lines` cover pressing Enter and saving the buffer.
+//!
+//! ```rust,ignore
+//! let buffer = use_collection(|| TextLines::from("fn main() {\n}"));
+//! rsx! {
+//!     {buffer.render_items(|line| rsx! {
+//!         pre { class: if line.is_selected() { "current" }, onclick: move |_| { line.select().ok(); }, "{line}" }
+//!     })}
+//! }
+//! ```
+
+use crate::{
+    Collection, CollectionError, CollectionOperation, CollectionResult, CollectionStore, KeyIter,
+    Replay, SequentialCollection,
+};
+use dioxus_signals::*;
+use std::cmp::Ordering;
+
+/// A text buffer stored as one `String` per line
+///
+/// Keys are line numbers starting at 0, like `Vec`. Lines don't include their `'\n'` terminator.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextLines {
+    lines: Vec<String>,
+}
+
+impl TextLines {
+    /// Create an empty buffer, without any line
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the lines
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Join the lines with `'\n'`, the inverse of `TextLines::from(text)`
+    pub fn join_lines(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// Split a text on `'\n'`: an empty text is a single empty line
+impl From<&str> for TextLines {
+    fn from(text: &str) -> Self {
+        Self {
+            lines: text.split('\n').map(str::to_string).collect(),
+        }
+    }
+}
+
+impl From<Vec<String>> for TextLines {
+    fn from(lines: Vec<String>) -> Self {
+        Self { lines }
+    }
+}
+
+impl Collection for TextLines {
+    type Key = usize;
+    type Value = String;
+
+    const POSITIONAL_KEYS: bool = true;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        Collection::get(&self.lines, key)
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        Collection::get_mut(&mut self.lines, key)
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        Collection::set(&mut self.lines, key, value)
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        Collection::insert(&mut self.lines, key, value)
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        Collection::remove(&mut self.lines, key)
+    }
+
+    fn find_key<F>(&self, predicate: F) -> Option<Self::Key>
+    where
+        F: FnMut(&Self::Value) -> bool,
+    {
+        self.lines.iter().position(predicate)
+    }
+
+    fn append_entries<I: IntoIterator<Item = (Self::Key, Self::Value)>>(&mut self, entries: I) {
+        self.lines.append_entries(entries);
+    }
+
+    fn retain<F>(&mut self, keep: F)
+    where
+        F: FnMut(&Self::Key, &Self::Value) -> bool,
+    {
+        Collection::retain(&mut self.lines, keep);
+    }
+
+    fn drain(&mut self) -> Vec<(Self::Key, Self::Value)> {
+        Collection::drain(&mut self.lines)
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        (0..self.lines.len()).collect()
+    }
+
+    fn key_iter(&self) -> Box<dyn KeyIter<Item = Self::Key>> {
+        Box::new(0..self.lines.len())
+    }
+
+    fn last_key(&self) -> Option<Self::Key> {
+        self.lines.len().checked_sub(1)
+    }
+
+    fn key_position(&self, key: &Self::Key) -> Option<usize> {
+        (*key < self.lines.len()).then_some(*key)
+    }
+
+    fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn clear(&mut self) {
+        self.lines.clear();
+    }
+}
+
+impl SequentialCollection for TextLines {
+    fn push(&mut self, value: Self::Value) {
+        self.lines.push(value);
+    }
+
+    fn pop(&mut self) -> Option<Self::Value> {
+        self.lines.pop()
+    }
+
+    fn first(&self) -> Option<&Self::Value> {
+        self.lines.first()
+    }
+
+    fn swap(&mut self, key1: &Self::Key, key2: &Self::Key) {
+        SequentialCollection::swap(&mut self.lines, key1, key2);
+    }
+
+    fn insert_at(&mut self, key: &Self::Key, value: Self::Value) -> bool {
+        self.lines.insert_at(key, value)
+    }
+
+    fn swap_remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        SequentialCollection::swap_remove(&mut self.lines, key)
+    }
+
+    fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&Self::Value, &Self::Value) -> Ordering,
+    {
+        self.lines.sort_by(compare);
+    }
+
+    fn move_item(&mut self, from: &Self::Key, to: &Self::Key) -> bool {
+        self.lines.move_item(from, to)
+    }
+}
+
+impl Replay for TextLines {
+    fn apply(
+        store: &CollectionStore<Self>,
+        operation: CollectionOperation<usize, String>,
+    ) -> CollectionResult<()> {
+        operation.apply_sequential(store)
+    }
+}
+
+impl CollectionStore<TextLines> {
+    /// Insert a line at line number `at`, moving the following lines down
+    ///
+    /// `at` may be the number of lines, to append a line. Returns the line number of the new line,
+    /// and the selection follows the current line to its new line number.
+    pub fn insert_line(&self, at: usize, text: impl Into<String>) -> CollectionResult<usize> {
+        self.ensure_unlocked()?;
+        let len = self.peek_len();
+        match at.cmp(&len) {
+            Ordering::Less => self.insert_before(&at, text.into()),
+            Ordering::Equal => {
+                self.push(text.into());
+                Ok(at)
+            }
+            Ordering::Greater => Err(CollectionError::OutOfBounds { index: at, len }),
+        }
+    }
+
+    /// Join the lines with `'\n'`, to save the buffer
+    ///
+    /// This subscribes to the whole buffer like `iter()`.
+    pub fn join_lines(&self) -> String {
+        self.items().read().join_lines()
+    }
+
+    /// Split line `key` at byte offset `at`, moving the end of the line to a new line below
+    ///
+    /// Returns the line number of the new line. If the split line was the current (selected) line,
+    /// the new line becomes the current line, like pressing Enter in an editor.
+    /// Fails with `OutOfBounds` if `at` isn't a char boundary of the line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionStore, TextLines};
+    ///
+    /// let buffer = CollectionStore::new(TextLines::from("hello world"));
+    /// let new_line = buffer.split_line(0, 5).unwrap();
+    /// assert_eq!(new_line, 1);
+    /// assert_eq!(buffer.join_lines(), "hello\n world");
+    /// ```
+    pub fn split_line(&self, key: usize, at: usize) -> CollectionResult<usize> {
+        self.ensure_unlocked()?;
+        let line = self.peek_value(&key).ok_or(CollectionError::KeyNotFound)?;
+        if !line.is_char_boundary(at) {
+            return Err(CollectionError::OutOfBounds {
+                index: at,
+                len: line.len(),
+            });
+        }
+        let (head, tail) = line.split_at(at);
+        let was_current = self.peek_selected_key() == Some(key);
+        self.set(key, head.to_string());
+        let new_key = self.insert_after(&key, tail.to_string())?;
+        if was_current {
+            self.select(&new_key).ok();
+        }
+        Ok(new_key)
+    }
+}
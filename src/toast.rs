@@ -0,0 +1,242 @@
+//! Toast notification queue
+//!
+//! `ToastCollection` is a bounded sequential collection for notifications: pushing past its capacity drops
+//! the oldest toast, and `store.push_with_ttl(value, ttl)` removes the toast once `ttl` has elapsed.
+//! Each toast gets an identifier when pushed, so its expiry removes it even after the toasts before it
+//! were dismissed and its key (position) changed.
+//!
+//! ```rust,ignore
+//! let toasts = use_collection(|| ToastCollection::new(5));
+//! toasts.push_with_ttl("Saved".to_string(), Duration::from_secs(3)).ok();
+//! rsx! {
+//!     for toast in toasts.iter() {
+//!         div { class: "toast", onclick: move |_| { toast.remove(); }, "{toast}" }
+//!     }
+//! }
+//! ```
+
+use crate::{
+    Collection, CollectionOperation, CollectionResult, CollectionStore, KeyIter, Replay,
+    SequentialCollection,
+};
+use dioxus_core::spawn;
+use dioxus_signals::*;
+use std::cmp::Ordering;
+use std::time::Duration;
+
+/// A bounded queue of notifications, oldest first
+///
+/// Keys are positions, like `Vec`. Once `capacity` toasts are shown, pushing a new one drops the oldest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToastCollection<T> {
+    toasts: Vec<(u64, T)>,
+    capacity: usize,
+    next_id: u64,
+}
+
+impl<T> ToastCollection<T> {
+    /// Create an empty queue showing at most `capacity` toasts
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "a ToastCollection must hold at least one toast"
+        );
+        Self {
+            toasts: Vec::new(),
+            capacity,
+            next_id: 0,
+        }
+    }
+
+    /// Get the maximum number of toasts
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Check if pushing a new toast would drop the oldest one
+    pub fn is_full(&self) -> bool {
+        self.toasts.len() >= self.capacity
+    }
+
+    /// Find the position of the toast pushed with identifier `id`
+    fn position_of_id(&self, id: u64) -> Option<usize> {
+        self.toasts.iter().position(|(toast_id, _)| *toast_id == id)
+    }
+
+    /// Identifier of the last pushed toast
+    fn last_id(&self) -> Option<u64> {
+        self.toasts.last().map(|(id, _)| *id)
+    }
+}
+
+impl<T> Collection for ToastCollection<T> {
+    type Key = usize;
+    type Value = T;
+
+    const POSITIONAL_KEYS: bool = true;
+
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value> {
+        self.toasts.as_slice().get(*key).map(|(_, value)| value)
+    }
+
+    fn get_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value> {
+        self.toasts
+            .as_mut_slice()
+            .get_mut(*key)
+            .map(|(_, value)| value)
+    }
+
+    fn set(&mut self, key: Self::Key, value: Self::Value) -> bool {
+        match self.get_mut(&key) {
+            Some(current) => {
+                *current = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
+        if key == self.toasts.len() {
+            SequentialCollection::push(self, value);
+            None
+        } else {
+            self.get_mut(&key)
+                .map(|current| std::mem::replace(current, value))
+        }
+    }
+
+    fn remove(&mut self, key: &Self::Key) -> Option<Self::Value> {
+        (*key < self.toasts.len()).then(|| self.toasts.remove(*key).1)
+    }
+
+    fn append_entries<I: IntoIterator<Item = (Self::Key, Self::Value)>>(&mut self, entries: I) {
+        for (_, value) in entries {
+            SequentialCollection::push(self, value);
+        }
+    }
+
+    fn keys(&self) -> Vec<Self::Key> {
+        (0..self.toasts.len()).collect()
+    }
+
+    fn key_iter(&self) -> Box<dyn KeyIter<Item = Self::Key>> {
+        Box::new(0..self.toasts.len())
+    }
+
+    fn last_key(&self) -> Option<Self::Key> {
+        self.toasts.len().checked_sub(1)
+    }
+
+    fn key_position(&self, key: &Self::Key) -> Option<usize> {
+        (*key < self.toasts.len()).then_some(*key)
+    }
+
+    fn len(&self) -> usize {
+        self.toasts.len()
+    }
+
+    fn clear(&mut self) {
+        self.toasts.clear();
+    }
+}
+
+impl<T> SequentialCollection for ToastCollection<T> {
+    /// Push a toast, dropping the oldest one if the queue is full
+    fn push(&mut self, value: Self::Value) {
+        if self.is_full() {
+            self.toasts.remove(0);
+        }
+        self.toasts.push((self.next_id, value));
+        self.next_id += 1;
+    }
+
+    fn pop(&mut self) -> Option<Self::Value> {
+        self.toasts.pop().map(|(_, value)| value)
+    }
+
+    fn first(&self) -> Option<&Self::Value> {
+        self.toasts.first().map(|(_, value)| value)
+    }
+
+    fn swap(&mut self, key1: &Self::Key, key2: &Self::Key) {
+        if *key1 < self.toasts.len() && *key2 < self.toasts.len() {
+            self.toasts.as_mut_slice().swap(*key1, *key2);
+        }
+    }
+
+    fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&Self::Value, &Self::Value) -> Ordering,
+    {
+        self.toasts.sort_by(|(_, a), (_, b)| compare(a, b));
+    }
+}
+
+impl<T: Clone + 'static> Replay for ToastCollection<T> {
+    fn apply(
+        store: &CollectionStore<Self>,
+        operation: CollectionOperation<usize, T>,
+    ) -> CollectionResult<()> {
+        operation.apply_sequential(store)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "toasts")))]
+impl<T> CollectionStore<ToastCollection<T>>
+where
+    T: Clone + 'static,
+{
+    /// Show a toast until it is dismissed
+    ///
+    /// If the queue is full, the oldest toast is removed through the store first,
+    /// so the item metadata and focus follow the remaining toasts.
+    pub fn push_toast(&self, value: T) -> CollectionResult<()> {
+        self.ensure_unlocked()?;
+        if self.items().peek().is_full() {
+            self.remove(&0);
+        }
+        self.push(value);
+        Ok(())
+    }
+
+    /// Show a toast, and remove it once `ttl` has elapsed
+    ///
+    /// Must be called from a Dioxus runtime, the removal runs in a spawned task.
+    /// Dismissing the toast earlier is fine: the expiry then does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::{CollectionStore, ToastCollection};
+    /// use std::time::Duration;
+    ///
+    /// let toasts = CollectionStore::new(ToastCollection::new(3));
+    /// toasts.push_with_ttl("Saved", Duration::from_secs(3)).ok();
+    /// assert_eq!(toasts.len(), 1);
+    /// ```
+    pub fn push_with_ttl(&self, value: T, ttl: Duration) -> CollectionResult<()> {
+        self.push_toast(value)?;
+        let id = self.items().peek().last_id();
+        if let Some(id) = id {
+            let store = *self;
+            spawn(async move {
+                futures_timer::Delay::new(ttl).await;
+                store.expire_toast(id);
+            });
+        }
+        Ok(())
+    }
+
+    /// Remove the toast pushed with identifier `id`, unless it is already gone
+    pub(crate) fn expire_toast(&self, id: u64) {
+        let position = self.items().peek().position_of_id(id);
+        if let Some(position) = position {
+            self.remove(&position);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Tracing instrumentation of mutations
+//!
+//! With the `tracing` feature, a store can emit a `tracing` event for every mutation made through its API,
+//! with the operation, the keys concerned and the collection length before and after.
+//! Events are emitted at the `DEBUG` level with the `dioxus_collection_store` target:
+//!
+//! ```text
+//! DEBUG dioxus_collection_store: collection mutation operation="clear" keys="" len_before=3 len=0
+//! ```
+//!
+//! Like recordings, mutations made through `CollectionItem::write()` bypass the store API and are not traced.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionOperation, CollectionStore};
+use dioxus_signals::*;
+use std::fmt::Debug;
+
+/// Tracing state kept in the store while tracing is enabled
+pub struct Tracer<C>
+where
+    C: Collection,
+{
+    /// Captured when tracing is enabled, so mutation methods don't need a `Clone` bound
+    pub(crate) clone_value: fn(&C::Value) -> C::Value,
+    /// Captured when tracing is enabled, so mutation methods don't need a `Debug` bound
+    format_key: fn(&C::Key) -> String,
+    /// Keys and collection length of the mutation in progress, until its event is emitted
+    pending: Option<(String, usize)>,
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Emit a `tracing` event for every mutation made through this store
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// store.enable_tracing();
+    /// // DEBUG dioxus_collection_store: collection mutation operation="clear" keys="" len_before=3 len=0
+    /// store.clear();
+    /// ```
+    pub fn enable_tracing(&self)
+    where
+        C::Key: Debug,
+        C::Value: Clone,
+    {
+        self.inner.tracer().set(Some(Tracer {
+            clone_value: C::Value::clone,
+            format_key: |key| format!("{key:?}"),
+            pending: None,
+        }));
+    }
+
+    /// Stop emitting events for mutations
+    pub fn disable_tracing(&self) {
+        self.inner.tracer().set(None);
+    }
+
+    /// Check if tracing is enabled
+    pub fn is_tracing(&self) -> bool {
+        self.inner.tracer().peek().is_some()
+    }
+
+    /// Remember an operation about to be applied, until `finish_trace` emits its event
+    pub(crate) fn start_trace(&self, operation: &CollectionOperation<C::Key, C::Value>) {
+        let len = self.inner.items().peek().len();
+        if let Some(tracer) = self.inner.tracer().write().as_mut() {
+            let keys = operation_keys(operation)
+                .into_iter()
+                .map(tracer.format_key)
+                .collect::<Vec<_>>()
+                .join(", ");
+            tracer.pending = Some((keys, len));
+        }
+    }
+
+    /// Emit the event for the operation that was just applied
+    pub(crate) fn finish_trace(&self, operation: &str) {
+        let pending = match self.inner.tracer().write().as_mut() {
+            Some(tracer) => tracer.pending.take(),
+            None => return,
+        };
+        let Some((keys, len_before)) = pending else {
+            return;
+        };
+        tracing::debug!(
+            target: "dioxus_collection_store",
+            operation,
+            keys,
+            len_before,
+            len = self.inner.items().peek().len(),
+            "collection mutation"
+        );
+    }
+}
+
+/// Keys concerned by an operation, in the order they appear in it
+fn operation_keys<K, V>(operation: &CollectionOperation<K, V>) -> Vec<&K> {
+    match operation {
+        CollectionOperation::Insert { key, .. }
+        | CollectionOperation::Set { key, .. }
+        | CollectionOperation::Remove { key }
+        | CollectionOperation::Select { key }
+        | CollectionOperation::SwapRemove { key }
+        | CollectionOperation::InsertBefore { key, .. }
+        | CollectionOperation::InsertAfter { key, .. } => vec![key],
+        CollectionOperation::RemoveMany { keys } | CollectionOperation::Reorder { order: keys } => {
+            keys.iter().collect()
+        }
+        CollectionOperation::Extend { items } | CollectionOperation::AppendEntries { items } => {
+            items.iter().map(|(key, _)| key).collect()
+        }
+        CollectionOperation::Swap { key1, key2 } => vec![key1, key2],
+        CollectionOperation::MoveItem { from, to }
+        | CollectionOperation::RenameKey { from, to }
+        | CollectionOperation::SelectRange { from, to } => {
+            vec![from, to]
+        }
+        CollectionOperation::Clear
+        | CollectionOperation::ClearSelection
+        | CollectionOperation::Push { .. }
+        | CollectionOperation::Pop => Vec::new(),
+    }
+}
@@ -0,0 +1,110 @@
+//! Per-item state tracking across structural changes
+//!
+//! Some store state refers to items by key: metadata, the focus cursor... When the collection changes
+//! structurally, that state must follow its item. Keyed collections only need to drop the state of removed keys,
+//! while positional collections (`Collection::POSITIONAL_KEYS`) must also move it when positions shift.
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionStore};
+use dioxus_signals::*;
+
+/// Find the key of an item after a structural change
+///
+/// For positional keys, `new_position` maps the position of an item before the change to its position after it,
+/// or `None` if the item was removed. Other keys are kept as long as they're still in the collection.
+pub(crate) fn follow_key<C>(
+    items: &C,
+    old_keys: &[C::Key],
+    new_keys: &[C::Key],
+    key: &C::Key,
+    new_position: &impl Fn(usize) -> Option<usize>,
+) -> Option<C::Key>
+where
+    C: Collection,
+    C::Key: PartialEq,
+{
+    if !C::POSITIONAL_KEYS {
+        return items.get(key).is_some().then(|| key.clone());
+    }
+    let position = old_keys.iter().position(|k| k == key)?;
+    follow_key_at(items, new_keys, key, position, new_position)
+}
+
+/// Find the key of an item after a structural change, like `follow_key`, given its position before it
+///
+/// Saves the scan of `old_keys` when walking all of them.
+pub(crate) fn follow_key_at<C>(
+    items: &C,
+    new_keys: &[C::Key],
+    key: &C::Key,
+    position: usize,
+    new_position: &impl Fn(usize) -> Option<usize>,
+) -> Option<C::Key>
+where
+    C: Collection,
+    C::Key: PartialEq,
+{
+    if !C::POSITIONAL_KEYS {
+        return items.get(key).is_some().then(|| key.clone());
+    }
+    new_keys.get(new_position(position)?).cloned()
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Snapshot the keys before a structural change, if any per-item state must follow the items
+    pub(crate) fn tracked_keys(&self) -> Option<Vec<C::Key>> {
+        if !self.has_meta()
+            && self.inner.focused_key().peek().is_none()
+            && self.inner.selection_anchor().peek().is_none()
+            && !self.is_tracking_positions()
+            && !self.is_tracking_generations()
+        {
+            None
+        } else {
+            Some(self.inner.items().peek().keys())
+        }
+    }
+
+    /// Move the per-item state along with the items after a structural change
+    ///
+    /// `old_keys` comes from `tracked_keys` before the change, see `follow_key` for `new_position`.
+    pub(crate) fn remap_tracked(
+        &self,
+        old_keys: Option<Vec<C::Key>>,
+        new_position: impl Fn(usize) -> Option<usize>,
+    ) {
+        let Some(old_keys) = old_keys else {
+            return;
+        };
+        self.remap_meta(&old_keys, &new_position);
+        self.remap_focus(&old_keys, &new_position);
+        self.remap_anchor(&old_keys, &new_position);
+        self.record_previous_positions(&old_keys, &new_position);
+        self.remap_generations(&old_keys, &new_position);
+    }
+
+    /// Move the selection along with its item after a structural change
+    ///
+    /// Unlike the other per-item state, the caller always snapshots `old_keys` since the selection is common.
+    pub(crate) fn remap_selection(
+        &self,
+        old_keys: &[C::Key],
+        new_position: &impl Fn(usize) -> Option<usize>,
+    ) {
+        let Some(selected) = self.inner.selected_key().peek().clone() else {
+            return;
+        };
+        let new_selection = {
+            let items = self.inner.items();
+            let items = items.peek();
+            follow_key(&*items, old_keys, &items.keys(), &selected, new_position)
+        };
+        if new_selection.as_ref() != Some(&selected) {
+            self.inner.selected_key().set(new_selection);
+        }
+    }
+}
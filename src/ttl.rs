@@ -0,0 +1,202 @@
+//! Expiring items
+//!
+//! Sessions, temporary banners and cached entries are only valid for a while. `store.insert_with_ttl(key,
+//! value, ttl)` adds an item that is removed once `ttl` has elapsed, and `item.remaining()` tells how long
+//! it has left, for countdowns. The deadline is stored in the item metadata, so it follows the item when the
+//! collection changes, and inserting the item again with a new TTL pushes it back.
+//!
+//! ```rust,ignore
+//! let sessions = use_collection(HashMap::new);
+//! sessions.insert_with_ttl(token, user, Duration::from_secs(15 * 60)).ok();
+//! rsx! {
+//!     for session in sessions.iter() {
+//!         li { "{session.key()} expires in {session.remaining().unwrap_or_default().as_secs()}s" }
+//!     }
+//! }
+//! ```
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionError, CollectionItem, CollectionResult, CollectionStore};
+use dioxus_core::{Task, spawn_forever};
+use dioxus_signals::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+/// Metadata holding the deadline of an expiring item
+#[derive(Default)]
+struct Deadline(Option<Instant>);
+
+/// The task sweeping the expired items of a store, waiting for the earliest deadline
+pub(crate) struct TtlSweep {
+    pub(crate) deadline: Instant,
+    task: Task,
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "ttl")))]
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Insert an item, and remove it once `ttl` has elapsed
+    ///
+    /// Must be called from a Dioxus runtime: a single task per store sweeps the expired items when the earliest
+    /// deadline elapses, then waits for the next one. It holds a weak handle, so it stops with the store.
+    /// Items whose TTL was extended or cleared meanwhile are kept, and a locked store keeps its expired items
+    /// until the next sweep. Fails with `Locked`, or with `KeyNotFound` if the collection
+    /// refused the key (like an out of bounds `Vec` index).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    /// use std::collections::HashMap;
+    /// use std::time::Duration;
+    ///
+    /// let banners = CollectionStore::new(HashMap::new());
+    /// banners.insert_with_ttl("maintenance", "Back at 6pm", Duration::from_secs(60)).unwrap();
+    /// assert!(banners.get(&"maintenance").remaining().is_some());
+    /// ```
+    pub fn insert_with_ttl(
+        &self,
+        key: C::Key,
+        value: C::Value,
+        ttl: Duration,
+    ) -> CollectionResult<()>
+    where
+        C::Value: Clone,
+    {
+        self.ensure_unlocked()?;
+        self.insert(key.clone(), value);
+        if !self.peek_contains_key(&key) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        self.set_ttl(&key, ttl)
+    }
+
+    /// Remove an existing item once `ttl` has elapsed, replacing its previous TTL
+    ///
+    /// Must be called from a Dioxus runtime, like `insert_with_ttl`. Fails with `Locked` while the store is locked.
+    pub fn set_ttl(&self, key: &C::Key, ttl: Duration) -> CollectionResult<()>
+    where
+        C::Value: Clone,
+    {
+        self.ensure_unlocked()?;
+        if !self.peek_contains_key(key) {
+            return Err(CollectionError::KeyNotFound);
+        }
+        let deadline = Instant::now() + ttl;
+        self.write_meta_table(|deadlines| deadlines.insert(key.clone(), Deadline(Some(deadline))));
+        self.schedule_sweep(deadline);
+        Ok(())
+    }
+
+    /// Make the sweep task wake up at `deadline`, unless it already wakes up earlier
+    fn schedule_sweep(&self, deadline: Instant)
+    where
+        C::Value: Clone,
+    {
+        let mut sweep = self.inner.ttl_sweep();
+        if sweep
+            .peek()
+            .as_ref()
+            .is_some_and(|scheduled| scheduled.deadline <= deadline)
+        {
+            return;
+        }
+        if let Some(scheduled) = sweep.write().take() {
+            scheduled.task.cancel();
+        }
+        let weak = self.downgrade();
+        let task = spawn_forever(async move {
+            futures_timer::Delay::new(deadline.saturating_duration_since(Instant::now())).await;
+            let Some(store) = weak.upgrade() else {
+                return;
+            };
+            store.inner.ttl_sweep().set(None);
+            store.sweep_expired();
+            if let Some(next) = store.next_deadline() {
+                store.schedule_sweep(next);
+            }
+        });
+        sweep.set(Some(TtlSweep { deadline, task }));
+    }
+
+    /// Get the earliest deadline still to come
+    ///
+    /// Expired items a locked store kept are left to the sweep of the next deadline.
+    fn next_deadline(&self) -> Option<Instant> {
+        let now = Instant::now();
+        self.peek_meta_table(|deadlines: Option<&HashMap<C::Key, Deadline>>| {
+            deadlines
+                .into_iter()
+                .flatten()
+                .filter_map(|(_, Deadline(deadline))| *deadline)
+                .filter(|deadline| *deadline > now)
+                .min()
+        })
+    }
+
+    /// Keep an item that was inserted with a TTL
+    pub fn clear_ttl(&self, key: &C::Key) {
+        self.meta_of::<Deadline>(key).take();
+    }
+
+    /// Get the deadline of an expiring item (reactive)
+    pub fn expires_at(&self, key: &C::Key) -> Option<Instant> {
+        self.meta_of::<Deadline>(key).read().0
+    }
+
+    /// Get the time left before an expiring item is removed (reactive)
+    ///
+    /// Only the deadline is reactive, not the passing time: re-render on an interval to show a countdown.
+    pub fn remaining(&self, key: &C::Key) -> Option<Duration> {
+        Some(
+            self.expires_at(key)?
+                .saturating_duration_since(Instant::now()),
+        )
+    }
+
+    /// Remove the items whose TTL has elapsed, in a single write
+    ///
+    /// Called when each TTL elapses. Returns the removed values in collection order.
+    pub fn sweep_expired(&self) -> Vec<C::Value>
+    where
+        C::Value: Clone,
+    {
+        let now = Instant::now();
+        let expired: Vec<C::Key> =
+            self.peek_meta_table(|deadlines: Option<&HashMap<C::Key, Deadline>>| {
+                deadlines
+                    .into_iter()
+                    .flatten()
+                    .filter(|(_, Deadline(deadline))| {
+                        deadline.is_some_and(|deadline| deadline <= now)
+                    })
+                    .map(|(key, _)| key.clone())
+                    .collect()
+            });
+        if expired.is_empty() {
+            return Vec::new();
+        }
+        self.remove_many(expired)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "ttl")))]
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq + Hash + Eq,
+{
+    /// Get the deadline of this item, if it expires (reactive)
+    pub fn expires_at(&self) -> Option<Instant> {
+        self.store.expires_at(&self.key)
+    }
+
+    /// Get the time left before this item is removed, if it expires (reactive)
+    pub fn remaining(&self) -> Option<Duration> {
+        self.store.remaining(&self.key)
+    }
+}
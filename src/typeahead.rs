@@ -0,0 +1,231 @@
+//! Typeahead selection
+//!
+//! In a listbox, typing the first letters of an item selects it, and pressing the same letter again cycles
+//! through the items starting with it. `store.select_by_prefix(prefix)` does the matching, and
+//! `use_typeahead(store)` keeps the typed buffer, resetting it after a pause:
+//!
+//! ```rust,ignore
+//! let typeahead = use_typeahead(store);
+//! rsx! {
+//!     ul {
+//!         onkeydown: move |event| {
+//!             if listbox.handle_key(&event.key().to_string()) || typeahead.handle_key(&event.key().to_string()) {
+//!                 event.prevent_default();
+//!             }
+//!         },
+//!         ...
+//!     }
+//! }
+//! ```
+
+use crate::collection_store::CollectionDataStoreExt;
+use crate::{Collection, CollectionStore};
+use dioxus_core::use_hook;
+use dioxus_signals::*;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+/// Pause after which `Typeahead` starts a new search
+pub const TYPEAHEAD_TIMEOUT: Duration = Duration::from_millis(500);
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Select the next item whose displayed value starts with `prefix`, ignoring case
+    ///
+    /// A prefix repeating a single character (`"b"`, `"bb"`...) cycles through the items starting with it,
+    /// from the one after the selection. Longer prefixes keep the selection while it still matches. Items that
+    /// can't be selected are skipped. Returns the selected key, or `None` if no item matches.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let fruits = CollectionStore::new(vec!["Banana", "Cherry", "Blueberry"]);
+    /// assert_eq!(fruits.select_by_prefix("b"), Some(0));
+    /// assert_eq!(fruits.select_by_prefix("bb"), Some(2));
+    /// assert_eq!(fruits.select_by_prefix("bl"), Some(2));
+    /// assert_eq!(fruits.select_by_prefix("ch"), Some(1));
+    /// ```
+    pub fn select_by_prefix(&self, prefix: &str) -> Option<C::Key>
+    where
+        C::Value: Display,
+    {
+        self.select_matching_prefix(prefix, |_, value| value.to_string())
+    }
+
+    /// Select the next item whose key starts with `prefix`, ignoring case, like `select_by_prefix`
+    pub fn select_by_key_prefix(&self, prefix: &str) -> Option<C::Key>
+    where
+        C::Key: Display,
+    {
+        self.select_matching_prefix(prefix, |key, _| key.to_string())
+    }
+
+    /// Select the next selectable item whose `text` starts with `prefix`
+    fn select_matching_prefix(
+        &self,
+        prefix: &str,
+        text: impl Fn(&C::Key, &C::Value) -> String,
+    ) -> Option<C::Key> {
+        let mut chars = prefix.chars();
+        let first = chars.next()?;
+        let cycling = chars.all(|c| c == first);
+        let prefix = if cycling {
+            first.to_lowercase().collect()
+        } else {
+            prefix.to_lowercase()
+        };
+
+        let found = {
+            let items = self.inner.items();
+            let items = items.peek();
+            let keys = items.keys();
+            let start = self
+                .peek_selected_key()
+                .and_then(|selected| keys.iter().position(|k| *k == selected))
+                .map_or(0, |position| position + usize::from(cycling));
+            (0..keys.len())
+                .map(|offset| &keys[(start + offset) % keys.len()])
+                .find(|key| {
+                    items
+                        .get(key)
+                        .is_some_and(|value| text(key, value).to_lowercase().starts_with(&prefix))
+                        && self.peek_is_selectable(key)
+                })?
+                .clone()
+        };
+        self.select(&found).ok()?;
+        Some(found)
+    }
+}
+
+/// Typed buffer of a typeahead
+struct TypeaheadState {
+    buffer: String,
+    last_key: Option<Instant>,
+    timeout: Duration,
+}
+
+/// Typeahead handle over a store, see `use_typeahead`
+pub struct Typeahead<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+    state: CopyValue<TypeaheadState>,
+}
+
+impl<C> Clone for Typeahead<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Typeahead<C> where C: Collection + 'static {}
+
+impl<C> PartialEq for Typeahead<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner && self.state == other.state
+    }
+}
+
+impl<C> Typeahead<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Display,
+{
+    /// Create a typeahead over a store, resetting its buffer after `TYPEAHEAD_TIMEOUT`
+    pub fn new(store: CollectionStore<C>) -> Self {
+        Self {
+            store,
+            state: CopyValue::new(TypeaheadState {
+                buffer: String::new(),
+                last_key: None,
+                timeout: TYPEAHEAD_TIMEOUT,
+            }),
+        }
+    }
+
+    /// Reset the buffer after `timeout` without key presses instead
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.state.write_unchecked().timeout = timeout;
+        self
+    }
+
+    /// Handle a key press, returns `true` if the key was handled
+    ///
+    /// Printable characters are added to the buffer, which then selects with `select_by_prefix`. A space
+    /// starting the buffer isn't handled, to leave it to the listbox.
+    pub fn handle_key(&self, key: &str) -> bool {
+        let mut chars = key.chars();
+        let (Some(c), None) = (chars.next(), chars.next()) else {
+            return false;
+        };
+        if c.is_control() {
+            return false;
+        }
+        let prefix = {
+            let mut state = self.state.write_unchecked();
+            let now = Instant::now();
+            if state
+                .last_key
+                .is_some_and(|last| now.duration_since(last) >= state.timeout)
+            {
+                state.buffer.clear();
+            }
+            if c == ' ' && state.buffer.is_empty() {
+                return false;
+            }
+            state.buffer.push(c);
+            state.last_key = Some(now);
+            state.buffer.clone()
+        };
+        self.store.select_by_prefix(&prefix);
+        true
+    }
+
+    /// Get the typed buffer, empty once it was reset
+    pub fn buffer(&self) -> String {
+        self.state.peek().buffer.clone()
+    }
+
+    /// Clear the buffer, to start a new search with the next key press
+    pub fn reset(&self) {
+        let mut state = self.state.write_unchecked();
+        state.buffer.clear();
+        state.last_key = None;
+    }
+}
+
+/// Hook creating a typeahead over a store
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use dioxus_collection_store::{use_collection, use_typeahead};
+///
+/// let store = use_collection(|| vec!["Apple", "Banana", "Blueberry"]);
+/// let typeahead = use_typeahead(store);
+/// typeahead.handle_key("b");
+/// typeahead.handle_key("l");
+/// assert_eq!(store.selected_key(), Some(2));
+/// ```
+pub fn use_typeahead<C>(store: CollectionStore<C>) -> Typeahead<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+    C::Value: Display,
+{
+    use_hook(|| Typeahead::new(store))
+}
@@ -0,0 +1,49 @@
+//! Watcher tasks woken by signal changes
+//!
+//! Async helpers (`on_select_prefetch`, `SelectionGroup`, the URL sync of table views...) run a task that
+//! reads some signals, then sleeps until one of them changes. `ChangeWaker` is the reactive context of such a
+//! task, turning its change notifications into a future.
+
+use dioxus_core::{ReactiveContext, current_scope_id};
+use std::future::poll_fn;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+
+/// Wakes a watcher task when the signals read in its reactive context change
+#[derive(Clone, Default)]
+pub(crate) struct ChangeWaker {
+    state: Arc<Mutex<(bool, Option<Waker>)>>,
+}
+
+impl ChangeWaker {
+    /// Create a reactive context in the current scope, waking `changed()` when the signals read in it change
+    #[track_caller]
+    pub(crate) fn reactive_context(&self) -> ReactiveContext {
+        let state = self.state.clone();
+        ReactiveContext::new_with_callback(
+            move || {
+                let mut state = state.lock().unwrap();
+                state.0 = true;
+                if let Some(waker) = state.1.take() {
+                    waker.wake();
+                }
+            },
+            current_scope_id(),
+            std::panic::Location::caller(),
+        )
+    }
+
+    /// Wait until the signals read in the reactive context change
+    pub(crate) async fn changed(&self) {
+        poll_fn(|cx| {
+            let mut state = self.state.lock().unwrap();
+            if std::mem::take(&mut state.0) {
+                Poll::Ready(())
+            } else {
+                state.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+}
@@ -0,0 +1,161 @@
+//! Weak store and item handles for background tasks
+//!
+//! Store handles are `Copy` and never keep the store alive: the store is dropped with the scope that created it,
+//! and reading it afterwards panics. A spawned task that outlives the component (a websocket listener, a timer...)
+//! should hold a weak handle instead, and `upgrade()` it whenever it needs the store, stopping once it gets `None`.
+//!
+//! ```rust,ignore
+//! let weak = store.downgrade();
+//! spawn_forever(async move {
+//!     while let Some(message) = receiver.next().await {
+//!         let Some(store) = weak.upgrade() else { break };
+//!         store.push(message);
+//!     }
+//! });
+//! ```
+
+use crate::{Collection, CollectionItem, CollectionStore};
+use dioxus_signals::*;
+
+/// A handle to a store that may have been dropped
+///
+/// Created with `CollectionStore::downgrade`.
+pub struct WeakCollectionStore<C>
+where
+    C: Collection + 'static,
+{
+    store: CollectionStore<C>,
+}
+
+impl<C> Copy for WeakCollectionStore<C> where C: Collection + 'static {}
+
+impl<C> Clone for WeakCollectionStore<C>
+where
+    C: Collection + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> PartialEq for WeakCollectionStore<C>
+where
+    C: Collection + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store.inner == other.store.inner
+    }
+}
+
+impl<C> WeakCollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the store, or `None` if it was dropped with its scope
+    pub fn upgrade(&self) -> Option<CollectionStore<C>> {
+        self.store
+            .alive
+            .try_peek_unchecked()
+            .is_ok()
+            .then_some(self.store)
+    }
+}
+
+/// A handle to an item of a store that may have been dropped
+///
+/// Created with `CollectionItem::downgrade`.
+pub struct WeakCollectionItem<C>
+where
+    C: Collection + 'static,
+{
+    store: WeakCollectionStore<C>,
+    key: C::Key,
+    generation: Option<u64>,
+}
+
+impl<C> Clone for WeakCollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store,
+            key: self.key.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+impl<C> PartialEq for WeakCollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.store == other.store && self.key == other.key && self.generation == other.generation
+    }
+}
+
+impl<C> WeakCollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get the key of the item
+    pub fn key(&self) -> C::Key {
+        self.key.clone()
+    }
+
+    /// Get the item, or `None` if the store was dropped or the item is no longer in it
+    ///
+    /// If the store tracks generations, an item that was removed or moved also returns `None`.
+    pub fn upgrade(&self) -> Option<CollectionItem<C>> {
+        let store = self.store.upgrade()?;
+        let item = CollectionItem {
+            store,
+            key: self.key.clone(),
+            generation: self.generation,
+        };
+        (store.peek_contains_key(&self.key) && !item.is_stale()).then_some(item)
+    }
+}
+
+impl<C> CollectionStore<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get a weak handle to this store, for tasks that may outlive its scope
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use dioxus_collection_store::CollectionStore;
+    ///
+    /// let store = CollectionStore::new(vec![1, 2, 3]);
+    /// let weak = store.downgrade();
+    /// if let Some(store) = weak.upgrade() {
+    ///     store.push(4);
+    /// }
+    /// ```
+    pub fn downgrade(&self) -> WeakCollectionStore<C> {
+        WeakCollectionStore { store: *self }
+    }
+}
+
+impl<C> CollectionItem<C>
+where
+    C: Collection + 'static,
+    C::Key: Clone + PartialEq,
+{
+    /// Get a weak handle to this item, for tasks that may outlive the store's scope
+    pub fn downgrade(&self) -> WeakCollectionItem<C> {
+        WeakCollectionItem {
+            store: self.store.downgrade(),
+            key: self.key.clone(),
+            generation: self.generation,
+        }
+    }
+}